@@ -0,0 +1,315 @@
+//! # 打码平台客户端
+//!
+//! 对接 2captcha、Anti-Captcha、CapSolver 等第三方打码服务：
+//! 提交任务 → 按 `timeout_seconds` 轮询结果 → 返回 token
+//!
+//! Anti-Captcha 与 CapSolver 使用相同的 `createTask` / `getTaskResult` 协议，
+//! 2captcha 使用 `in.php` / `res.php` 旧版接口。
+
+use super::ChallengeType;
+use crate::{Result, RuntimeError};
+use crawler_schema::config::{CaptchaProvider, ExternalHandler};
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// 2captcha 轮询间隔
+const TWO_CAPTCHA_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 任务协议平台的轮询间隔
+const TASK_API_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// 打码请求参数
+#[derive(Debug, Clone)]
+pub struct CaptchaTask<'a> {
+    /// 验证类型
+    pub challenge_type: &'a ChallengeType,
+    /// 站点密钥（sitekey）
+    pub site_key: &'a str,
+    /// 出现验证的页面 URL
+    pub page_url: &'a str,
+    /// 平台特定的额外参数，合并到提交的任务中
+    pub extra_params: Option<&'a HashMap<String, Value>>,
+}
+
+/// 调用打码平台求解验证，返回解答 token
+///
+/// `api_key` 须由调用方预先渲染；`client` 为空时创建新的 HTTP 客户端。
+/// 超过 `config.timeout_seconds` 仍未得到结果时返回 `ExecutionTimeout`。
+pub async fn solve(
+    config: &ExternalHandler,
+    api_key: &str,
+    task: CaptchaTask<'_>,
+    client: Option<&reqwest::Client>,
+) -> Result<String> {
+    let client = client.cloned().unwrap_or_default();
+    let timeout = Duration::from_secs(config.timeout_seconds as u64);
+
+    match config.provider {
+        CaptchaProvider::TwoCaptcha => {
+            let endpoint = config.endpoint.as_deref().unwrap_or("https://2captcha.com");
+            solve_with_2captcha(&client, endpoint, api_key, &task, timeout).await
+        }
+        CaptchaProvider::AntiCaptcha => {
+            let endpoint = config
+                .endpoint
+                .as_deref()
+                .unwrap_or("https://api.anti-captcha.com");
+            let task_type = match task.challenge_type {
+                ChallengeType::RecaptchaV2 => "RecaptchaV2TaskProxyless",
+                ChallengeType::RecaptchaV3 => "RecaptchaV3TaskProxyless",
+                ChallengeType::Hcaptcha => "HCaptchaTaskProxyless",
+                ChallengeType::CloudflareTurnstile => "TurnstileTaskProxyless",
+                other => return Err(unsupported(other)),
+            };
+            solve_with_task_api(
+                &client,
+                "Anti-Captcha",
+                endpoint,
+                api_key,
+                task_type,
+                &task,
+                timeout,
+            )
+            .await
+        }
+        CaptchaProvider::CapSolver => {
+            let endpoint = config
+                .endpoint
+                .as_deref()
+                .unwrap_or("https://api.capsolver.com");
+            let task_type = match task.challenge_type {
+                ChallengeType::RecaptchaV2 => "ReCaptchaV2TaskProxyLess",
+                ChallengeType::RecaptchaV3 => "ReCaptchaV3TaskProxyLess",
+                ChallengeType::Hcaptcha => "HCaptchaTurboTask",
+                ChallengeType::CloudflareTurnstile => "AntiTurnstileTaskProxyLess",
+                other => return Err(unsupported(other)),
+            };
+            solve_with_task_api(
+                &client,
+                "CapSolver",
+                endpoint,
+                api_key,
+                task_type,
+                &task,
+                timeout,
+            )
+            .await
+        }
+        CaptchaProvider::Custom => Err(RuntimeError::ChallengeFailed(
+            "自定义打码服务需要自行实现".to_string(),
+        )),
+    }
+}
+
+// ============================================================================
+// 2captcha
+// ============================================================================
+
+async fn solve_with_2captcha(
+    client: &reqwest::Client,
+    endpoint: &str,
+    api_key: &str,
+    task: &CaptchaTask<'_>,
+    timeout: Duration,
+) -> Result<String> {
+    let method = match task.challenge_type {
+        ChallengeType::RecaptchaV2 | ChallengeType::RecaptchaV3 => "userrecaptcha",
+        ChallengeType::Hcaptcha => "hcaptcha",
+        ChallengeType::CloudflareTurnstile => "turnstile",
+        other => return Err(unsupported(other)),
+    };
+
+    // 1. 提交任务
+    let mut params: Vec<(String, String)> = vec![
+        ("key".to_string(), api_key.to_string()),
+        ("method".to_string(), method.to_string()),
+        ("sitekey".to_string(), task.site_key.to_string()),
+        ("pageurl".to_string(), task.page_url.to_string()),
+        ("json".to_string(), "1".to_string()),
+    ];
+    if matches!(task.challenge_type, ChallengeType::RecaptchaV3) {
+        params.push(("version".to_string(), "v3".to_string()));
+    }
+    for (key, value) in task.extra_params.into_iter().flatten() {
+        let value = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        params.push((key.clone(), value));
+    }
+
+    let result = send_json(client.post(format!("{}/in.php", endpoint)).form(&params)).await?;
+    if result["status"].as_i64() != Some(1) {
+        return Err(two_captcha_error(&result));
+    }
+    let task_id = result["request"]
+        .as_str()
+        .ok_or_else(|| RuntimeError::ChallengeFailed("无效的任务 ID".to_string()))?
+        .to_string();
+
+    // 2. 轮询结果
+    let poll_url = format!("{}/res.php", endpoint);
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        tokio::time::sleep(TWO_CAPTCHA_POLL_INTERVAL).await;
+
+        let query = [
+            ("key", api_key),
+            ("action", "get"),
+            ("id", task_id.as_str()),
+            ("json", "1"),
+        ];
+        let result = send_json(client.get(&poll_url).query(&query)).await?;
+
+        if result["status"].as_i64() == Some(1) {
+            return result["request"]
+                .as_str()
+                .map(|s| s.to_string())
+                .ok_or_else(|| RuntimeError::ChallengeFailed("无效的响应".to_string()));
+        }
+        if result["request"].as_str() != Some("CAPCHA_NOT_READY") {
+            return Err(two_captcha_error(&result));
+        }
+    }
+
+    Err(timed_out("2captcha", start, timeout))
+}
+
+/// 将 2captcha 错误码映射为运行时错误
+fn two_captcha_error(result: &Value) -> RuntimeError {
+    let code = result["request"].as_str().unwrap_or("UNKNOWN");
+    let reason = match code {
+        "ERROR_WRONG_USER_KEY" | "ERROR_KEY_DOES_NOT_EXIST" => {
+            return RuntimeError::InvalidConfigValue {
+                field: "challenge.handler.api_key".to_string(),
+                reason: format!("2captcha 拒绝了 API 密钥 ({})", code),
+            };
+        }
+        "ERROR_ZERO_BALANCE" => "账户余额不足",
+        "ERROR_NO_SLOT_AVAILABLE" => "服务繁忙，暂无可用工人",
+        "ERROR_CAPTCHA_UNSOLVABLE" => "验证无法被解答",
+        "ERROR_WRONG_GOOGLEKEY" | "ERROR_GOOGLEKEY" => "站点密钥无效",
+        "ERROR_PAGEURL" => "页面 URL 无效",
+        "IP_BANNED" => "IP 已被封禁",
+        _ => "未知错误",
+    };
+    RuntimeError::ChallengeFailed(format!("2captcha 错误 {}: {}", code, reason))
+}
+
+// ============================================================================
+// createTask / getTaskResult 协议（Anti-Captcha、CapSolver）
+// ============================================================================
+
+async fn solve_with_task_api(
+    client: &reqwest::Client,
+    provider: &str,
+    endpoint: &str,
+    api_key: &str,
+    task_type: &str,
+    task: &CaptchaTask<'_>,
+    timeout: Duration,
+) -> Result<String> {
+    // 1. 创建任务
+    let mut task_body = Map::new();
+    task_body.insert("type".to_string(), Value::from(task_type));
+    task_body.insert("websiteURL".to_string(), Value::from(task.page_url));
+    task_body.insert("websiteKey".to_string(), Value::from(task.site_key));
+    for (key, value) in task.extra_params.into_iter().flatten() {
+        task_body.insert(key.clone(), value.clone());
+    }
+    let create_task = serde_json::json!({
+        "clientKey": api_key,
+        "task": task_body,
+    });
+
+    let result = send_json(
+        client
+            .post(format!("{}/createTask", endpoint))
+            .json(&create_task),
+    )
+    .await?;
+    if result["errorId"].as_i64() != Some(0) {
+        return Err(task_api_error(provider, &result));
+    }
+    // Anti-Captcha 返回数字 ID，CapSolver 返回字符串 ID
+    let task_id = match &result["taskId"] {
+        id @ (Value::Number(_) | Value::String(_)) => id.clone(),
+        _ => return Err(RuntimeError::ChallengeFailed("无效的任务 ID".to_string())),
+    };
+
+    // 2. 轮询结果
+    let get_result = serde_json::json!({
+        "clientKey": api_key,
+        "taskId": task_id,
+    });
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        tokio::time::sleep(TASK_API_POLL_INTERVAL).await;
+
+        let result = send_json(
+            client
+                .post(format!("{}/getTaskResult", endpoint))
+                .json(&get_result),
+        )
+        .await?;
+
+        if result["errorId"].as_i64() != Some(0) {
+            return Err(task_api_error(provider, &result));
+        }
+        if result["status"].as_str() == Some("ready") {
+            let solution = &result["solution"];
+            return solution["gRecaptchaResponse"]
+                .as_str()
+                .or_else(|| solution["token"].as_str())
+                .map(|s| s.to_string())
+                .ok_or_else(|| RuntimeError::ChallengeFailed("无效的响应".to_string()));
+        }
+    }
+
+    Err(timed_out(provider, start, timeout))
+}
+
+/// 将任务协议的错误码映射为运行时错误
+fn task_api_error(provider: &str, result: &Value) -> RuntimeError {
+    let code = result["errorCode"].as_str().unwrap_or("UNKNOWN");
+    let description = result["errorDescription"].as_str().unwrap_or("");
+    match code {
+        "ERROR_KEY_DOES_NOT_EXIST" | "ERROR_INVALID_CLIENTKEY" | "ERROR_KEY_DENIED_ACCESS" => {
+            RuntimeError::InvalidConfigValue {
+                field: "challenge.handler.api_key".to_string(),
+                reason: format!("{} 拒绝了 API 密钥 ({})", provider, code),
+            }
+        }
+        _ => RuntimeError::ChallengeFailed(format!("{} 错误 {}: {}", provider, code, description)),
+    }
+}
+
+// ============================================================================
+// 辅助函数
+// ============================================================================
+
+/// 发送请求并解析 JSON 响应
+async fn send_json(request: reqwest::RequestBuilder) -> Result<Value> {
+    request
+        .send()
+        .await
+        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?
+        .json()
+        .await
+        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))
+}
+
+fn unsupported(challenge_type: &ChallengeType) -> RuntimeError {
+    RuntimeError::ChallengeFailed(format!("打码平台不支持的验证类型: {:?}", challenge_type))
+}
+
+fn timed_out(provider: &str, start: Instant, timeout: Duration) -> RuntimeError {
+    RuntimeError::ExecutionTimeout {
+        operation: provider.to_string(),
+        elapsed_ms: start.elapsed().as_millis() as u64,
+        limit_ms: timeout.as_millis() as u64,
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! 检测 HTTP 响应是否为人机验证页面
 
-use crate::Result;
+use crate::{
+    Result,
+    script::{ScriptContext, ScriptEngineFactory, ScriptExecutor},
+};
 use crawler_schema::config::{
     ChallengeDetector,
     CloudflareDetector,
@@ -12,6 +15,7 @@ use crawler_schema::config::{
     RecaptchaVersion,
 };
 use regex::Regex;
+use serde_json::{Value, json};
 use std::collections::HashMap;
 
 /// 检测结果
@@ -336,6 +340,7 @@ fn detect_custom(config: &CustomDetector, response: &ResponseContext) -> Detecti
         if config.body_patterns.is_none()
             && config.headers.is_none()
             && config.url_pattern.is_none()
+            && config.detect_script.is_none()
         {
             return DetectionResult::not_detected();
         }
@@ -387,6 +392,42 @@ fn detect_custom(config: &CustomDetector, response: &ResponseContext) -> Detecti
         }
     }
 
+    // 检测脚本：其余条件满足后由脚本做最终判断
+    if let Some(script) = &config.detect_script
+        && !run_detect_script(script, response)
+    {
+        return DetectionResult::not_detected();
+    }
+
     // 如果所有条件都满足（或没有条件），检测为自定义验证
     DetectionResult::detected(ChallengeType::Custom)
 }
+
+/// 执行自定义检测脚本
+///
+/// 脚本输入为响应体，可通过 `status`、`headers`、`url` 变量访问其余信息；
+/// 返回 `true` 表示检测到验证。脚本执行失败视为未检测到。
+fn run_detect_script(script: &crawler_schema::script::Script, response: &ResponseContext) -> bool {
+    let code = match ScriptExecutor::load_script_code(script) {
+        Ok(code) => code,
+        Err(e) => {
+            tracing::warn!("验证检测脚本加载失败: {}", e);
+            return false;
+        }
+    };
+
+    let mut variables: HashMap<String, Value> = HashMap::new();
+    variables.insert("status".to_string(), json!(response.status_code));
+    variables.insert("headers".to_string(), json!(response.headers));
+    variables.insert("url".to_string(), json!(response.final_url));
+
+    let engine = ScriptEngineFactory::create(ScriptExecutor::language(script));
+    let context = ScriptContext::new(response.body.clone(), variables);
+    match engine.execute(&code, &context) {
+        Ok(output) => output.trim().eq_ignore_ascii_case("true"),
+        Err(e) => {
+            tracing::warn!("验证检测脚本执行失败: {}", e);
+            false
+        }
+    }
+}
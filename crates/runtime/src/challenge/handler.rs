@@ -2,14 +2,20 @@
 //!
 //! 处理检测到的人机验证，支持多种策略
 
-use super::{ChallengeType, DetectionResult, ResponseContext};
+use super::{
+    DetectionResult,
+    ResponseContext,
+    captcha::{self, CaptchaTask},
+};
 use crate::{
     Result,
     RuntimeError,
+    context::{FlowContext, RuntimeContext},
+    http::HttpClient,
+    template::TemplateExt,
     webview::{SharedWebViewProvider, WebViewCloseReason, WebViewRequest},
 };
 use crawler_schema::config::{
-    CaptchaProvider,
     ChallengeHandler,
     CookieHandler,
     CookieSource,
@@ -18,8 +24,9 @@ use crawler_schema::config::{
     ScriptHandler,
     WebviewHandler,
 };
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::sync::RwLock;
+use url::Url;
 
 /// 验证凭证
 #[derive(Debug, Clone, Default)]
@@ -91,6 +98,19 @@ impl ChallengeCredentials {
             .collect::<Vec<_>>()
             .join("; ")
     }
+
+    /// 将凭证写入共享 HTTP 客户端
+    ///
+    /// Cookie 保存到客户端的 Cookie 存储（作为 `url` 所在域名的 Cookie），
+    /// Header 设为会话请求头，之后的请求自动携带
+    pub fn apply_to(&self, http_client: &HttpClient, url: &Url) {
+        if !self.cookies.is_empty() {
+            http_client.set_cookies(url, &self.to_cookie_string());
+        }
+        for (name, value) in &self.headers {
+            http_client.set_session_header(name, value);
+        }
+    }
 }
 
 /// 验证处理器执行上下文
@@ -103,8 +123,10 @@ pub struct HandlerContext {
     pub detection: DetectionResult,
     /// 响应上下文
     pub response: ResponseContext,
-    /// HTTP 客户端（用于重试）
-    pub http_client: Option<reqwest::Client>,
+    /// HTTP 客户端（用于重试和打码平台请求）
+    pub http_client: reqwest::Client,
+    /// 运行时上下文（用于渲染 API 密钥等模板）
+    pub runtime_context: Arc<RuntimeContext>,
 }
 
 /// 验证处理器 trait
@@ -193,12 +215,18 @@ fn build_webview_request(config: &WebviewHandler, ctx: &HandlerContext) -> WebVi
 // 重试处理器
 // ============================================================================
 
-async fn handle_retry(config: &RetryHandler, ctx: &HandlerContext) -> Result<ChallengeCredentials> {
-    let client = ctx
-        .http_client
-        .as_ref()
-        .ok_or_else(|| RuntimeError::ChallengeFailed("重试处理需要 HTTP 客户端".to_string()))?;
+/// WebView 渲染重试时的单次超时
+const RETRY_WEBVIEW_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// WebView 渲染重试的成功检测脚本：页面不再包含验证特征
+const RETRY_WEBVIEW_SUCCESS_CHECK: &str = "!/Just a moment|Checking your browser|cf-please-wait|g-recaptcha|h-captcha/.test(document.documentElement.outerHTML)";
+
+/// 重试处理
+///
+/// 每次重试前等待 `delay_ms`，之后按 `backoff_factor` 递增；
+/// `use_webview` 为 true 时通过 WebView 渲染页面（适用于需要执行 JS 的验证），
+/// 并返回 WebView 中获取的 Cookie 作为凭证
+async fn handle_retry(config: &RetryHandler, ctx: &HandlerContext) -> Result<ChallengeCredentials> {
     let mut delay = config.delay_ms;
     let backoff = config.backoff_factor.unwrap_or(1.5);
 
@@ -206,24 +234,13 @@ async fn handle_retry(config: &RetryHandler, ctx: &HandlerContext) -> Result<Cha
         // 等待
         tokio::time::sleep(Duration::from_millis(delay as u64)).await;
 
-        // 重试请求
-        let response = client
-            .get(&ctx.url)
-            .send()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-        // 检查是否仍然是验证页面
-        let status = response.status().as_u16();
-        let body = response
-            .text()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-        // 简单检查：如果状态码变为 200 且不包含验证特征，认为成功
-        if status == 200 && !contains_challenge_patterns(&body) {
-            // 成功绕过，但没有额外凭证
-            return Ok(ChallengeCredentials::new());
+        let passed = if config.use_webview {
+            retry_with_webview(ctx).await?
+        } else {
+            retry_with_http(ctx).await?
+        };
+        if let Some(credentials) = passed {
+            return Ok(credentials);
         }
 
         // 增加延迟
@@ -237,6 +254,61 @@ async fn handle_retry(config: &RetryHandler, ctx: &HandlerContext) -> Result<Cha
     })
 }
 
+/// 直接重新请求，页面不再是验证页时视为通过（无额外凭证）
+async fn retry_with_http(ctx: &HandlerContext) -> Result<Option<ChallengeCredentials>> {
+    // 重试请求
+    let response = ctx
+        .http_client
+        .get(&ctx.url)
+        .send()
+        .await
+        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
+
+    // 检查是否仍然是验证页面
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
+
+    // 简单检查：如果状态码变为 200 且不包含验证特征，认为成功
+    if status == 200 && !contains_challenge_patterns(&body) {
+        return Ok(Some(ChallengeCredentials::new()));
+    }
+    Ok(None)
+}
+
+/// 通过 WebView 渲染页面，验证特征消失后取回 Cookie
+///
+/// WebView 不可用时直接返回错误，不再继续重试
+async fn retry_with_webview(ctx: &HandlerContext) -> Result<Option<ChallengeCredentials>> {
+    if !ctx.webview_provider.is_available() {
+        return Err(RuntimeError::WebViewUnavailable(format!(
+            "{} 无法渲染页面",
+            ctx.webview_provider.name()
+        )));
+    }
+
+    let request = WebViewRequest::new(&ctx.url)
+        .with_timeout(RETRY_WEBVIEW_TIMEOUT)
+        .with_success_check(RETRY_WEBVIEW_SUCCESS_CHECK);
+
+    match ctx.webview_provider.open(request).await {
+        Ok(response) if response.success => Ok(Some(
+            ChallengeCredentials::new().with_cookies(response.cookies),
+        )),
+        Ok(response) => {
+            tracing::debug!("WebView 渲染重试未通过: {:?}", response.close_reason);
+            Ok(None)
+        }
+        Err(e @ RuntimeError::WebViewUnavailable(_)) => Err(e),
+        Err(e) => {
+            tracing::debug!("WebView 渲染重试失败: {}", e);
+            Ok(None)
+        }
+    }
+}
+
 /// 检查是否包含验证特征
 fn contains_challenge_patterns(body: &str) -> bool {
     const PATTERNS: &[&str] = &[
@@ -315,23 +387,23 @@ async fn handle_external(
         .as_ref()
         .ok_or_else(|| RuntimeError::ChallengeFailed("未知验证类型".to_string()))?;
 
-    // 根据提供商调用 API
-    let token = match config.provider {
-        CaptchaProvider::TwoCaptcha => {
-            solve_with_2captcha(config, &ctx.url, site_key, challenge_type).await?
-        }
-        CaptchaProvider::AntiCaptcha => {
-            solve_with_anticaptcha(config, &ctx.url, site_key, challenge_type).await?
-        }
-        CaptchaProvider::CapSolver => {
-            solve_with_capsolver(config, &ctx.url, site_key, challenge_type).await?
-        }
-        CaptchaProvider::Custom => {
-            return Err(RuntimeError::ChallengeFailed(
-                "自定义打码服务需要自行实现".to_string(),
-            ));
-        }
+    // 渲染 API 密钥（可引用运行时全局变量，如 `{{ $.captcha_key }}`）
+    let api_key = config
+        .api_key
+        .render(&FlowContext::new(Arc::clone(&ctx.runtime_context)))?;
+    if api_key.trim().is_empty() {
+        return Err(RuntimeError::ChallengeFailed(
+            "打码平台 API 密钥渲染结果为空".to_string(),
+        ));
+    }
+
+    let task = CaptchaTask {
+        challenge_type,
+        site_key,
+        page_url: &ctx.url,
+        extra_params: config.extra_params.as_ref(),
     };
+    let token = captcha::solve(config, &api_key, task, Some(&ctx.http_client)).await?;
 
     // 根据验证类型返回不同格式的凭证
     let mut credentials = ChallengeCredentials::new();
@@ -340,324 +412,6 @@ async fn handle_external(
     Ok(credentials)
 }
 
-async fn solve_with_2captcha(
-    config: &ExternalHandler,
-    page_url: &str,
-    site_key: &str,
-    challenge_type: &ChallengeType,
-) -> Result<String> {
-    let api_key: &str = config.api_key.as_str();
-    let endpoint = config.endpoint.as_deref().unwrap_or("https://2captcha.com");
-
-    let method = match challenge_type {
-        ChallengeType::RecaptchaV2 => "userrecaptcha",
-        ChallengeType::RecaptchaV3 => "userrecaptcha",
-        ChallengeType::Hcaptcha => "hcaptcha",
-        ChallengeType::CloudflareTurnstile => "turnstile",
-        _ => {
-            return Err(RuntimeError::ChallengeFailed(
-                "不支持的验证类型".to_string(),
-            ));
-        }
-    };
-
-    let client = reqwest::Client::new();
-
-    // 1. 提交任务
-    let mut params = vec![
-        ("key", api_key),
-        ("method", method),
-        ("sitekey", site_key),
-        ("pageurl", page_url),
-        ("json", "1"),
-    ];
-
-    if matches!(challenge_type, ChallengeType::RecaptchaV3) {
-        params.push(("version", "v3"));
-    }
-
-    let submit_url = format!("{}/in.php", endpoint);
-    let response = client
-        .post(&submit_url)
-        .form(&params)
-        .send()
-        .await
-        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-    let result: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-    if result["status"].as_i64() != Some(1) {
-        return Err(RuntimeError::ChallengeFailed(format!(
-            "2captcha 提交失败: {}",
-            result["request"]
-        )));
-    }
-
-    let task_id = result["request"]
-        .as_str()
-        .ok_or_else(|| RuntimeError::ChallengeFailed("无效的任务 ID".to_string()))?;
-
-    // 2. 轮询结果
-    let poll_url = format!(
-        "{}/res.php?key={}&action=get&id={}&json=1",
-        endpoint, api_key, task_id
-    );
-
-    let timeout = Duration::from_secs(config.timeout_seconds as u64);
-    let start = std::time::Instant::now();
-
-    while start.elapsed() < timeout {
-        tokio::time::sleep(Duration::from_secs(5)).await;
-
-        let response = client
-            .get(&poll_url)
-            .send()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-        let result: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-        if result["status"].as_i64() == Some(1) {
-            return result["request"]
-                .as_str()
-                .map(|s| s.to_string())
-                .ok_or_else(|| RuntimeError::ChallengeFailed("无效的响应".to_string()));
-        }
-
-        let request = result["request"].as_str().unwrap_or("");
-        if request != "CAPCHA_NOT_READY" {
-            return Err(RuntimeError::ChallengeFailed(format!(
-                "2captcha 错误: {}",
-                request
-            )));
-        }
-    }
-
-    Err(RuntimeError::ExecutionTimeout {
-        operation: "2captcha".to_string(),
-        elapsed_ms: timeout.as_millis() as u64,
-        limit_ms: timeout.as_millis() as u64,
-    })
-}
-
-async fn solve_with_anticaptcha(
-    config: &ExternalHandler,
-    page_url: &str,
-    site_key: &str,
-    challenge_type: &ChallengeType,
-) -> Result<String> {
-    let api_key = config.api_key.as_str();
-    let endpoint = config
-        .endpoint
-        .as_deref()
-        .unwrap_or("https://api.anti-captcha.com");
-
-    let task_type = match challenge_type {
-        ChallengeType::RecaptchaV2 => "RecaptchaV2TaskProxyless",
-        ChallengeType::RecaptchaV3 => "RecaptchaV3TaskProxyless",
-        ChallengeType::Hcaptcha => "HCaptchaTaskProxyless",
-        ChallengeType::CloudflareTurnstile => "TurnstileTaskProxyless",
-        _ => {
-            return Err(RuntimeError::ChallengeFailed(
-                "不支持的验证类型".to_string(),
-            ));
-        }
-    };
-
-    let client = reqwest::Client::new();
-
-    // 1. 创建任务
-    let create_task = serde_json::json!({
-        "clientKey": api_key,
-        "task": {
-            "type": task_type,
-            "websiteURL": page_url,
-            "websiteKey": site_key
-        }
-    });
-
-    let response = client
-        .post(format!("{}/createTask", endpoint))
-        .json(&create_task)
-        .send()
-        .await
-        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-    let result: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-    if result["errorId"].as_i64() != Some(0) {
-        return Err(RuntimeError::ChallengeFailed(format!(
-            "Anti-Captcha 错误: {}",
-            result["errorDescription"]
-        )));
-    }
-
-    let task_id = result["taskId"]
-        .as_i64()
-        .ok_or_else(|| RuntimeError::ChallengeFailed("无效的任务 ID".to_string()))?;
-
-    // 2. 获取结果
-    let get_result = serde_json::json!({
-        "clientKey": api_key,
-        "taskId": task_id
-    });
-
-    let timeout = Duration::from_secs(config.timeout_seconds as u64);
-    let start = std::time::Instant::now();
-
-    while start.elapsed() < timeout {
-        tokio::time::sleep(Duration::from_secs(5)).await;
-
-        let response = client
-            .post(format!("{}/getTaskResult", endpoint))
-            .json(&get_result)
-            .send()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-        let result: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-        if result["status"].as_str() == Some("ready") {
-            return result["solution"]["gRecaptchaResponse"]
-                .as_str()
-                .or_else(|| result["solution"]["token"].as_str())
-                .map(|s| s.to_string())
-                .ok_or_else(|| RuntimeError::ChallengeFailed("无效的响应".to_string()));
-        }
-
-        if result["errorId"].as_i64() != Some(0) {
-            return Err(RuntimeError::ChallengeFailed(format!(
-                "Anti-Captcha 错误: {}",
-                result["errorDescription"]
-            )));
-        }
-    }
-
-    Err(RuntimeError::ExecutionTimeout {
-        operation: "Anti-Captcha".to_string(),
-        elapsed_ms: timeout.as_millis() as u64,
-        limit_ms: timeout.as_millis() as u64,
-    })
-}
-
-async fn solve_with_capsolver(
-    config: &ExternalHandler,
-    page_url: &str,
-    site_key: &str,
-    challenge_type: &ChallengeType,
-) -> Result<String> {
-    let api_key = config.api_key.as_str();
-    let endpoint = config
-        .endpoint
-        .as_deref()
-        .unwrap_or("https://api.capsolver.com");
-
-    let task_type = match challenge_type {
-        ChallengeType::RecaptchaV2 => "ReCaptchaV2TaskProxyLess",
-        ChallengeType::RecaptchaV3 => "ReCaptchaV3TaskProxyLess",
-        ChallengeType::Hcaptcha => "HCaptchaTurboTask",
-        ChallengeType::CloudflareTurnstile => "AntiTurnstileTaskProxyLess",
-        _ => {
-            return Err(RuntimeError::ChallengeFailed(
-                "不支持的验证类型".to_string(),
-            ));
-        }
-    };
-
-    let client = reqwest::Client::new();
-
-    // 创建任务
-    let create_task = serde_json::json!({
-        "clientKey": api_key,
-        "task": {
-            "type": task_type,
-            "websiteURL": page_url,
-            "websiteKey": site_key
-        }
-    });
-
-    let response = client
-        .post(format!("{}/createTask", endpoint))
-        .json(&create_task)
-        .send()
-        .await
-        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-    let result: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-    if result["errorId"].as_i64() != Some(0) {
-        return Err(RuntimeError::ChallengeFailed(format!(
-            "CapSolver 错误: {}",
-            result["errorDescription"]
-        )));
-    }
-
-    let task_id = result["taskId"]
-        .as_str()
-        .ok_or_else(|| RuntimeError::ChallengeFailed("无效的任务 ID".to_string()))?;
-
-    // 获取结果
-    let get_result = serde_json::json!({
-        "clientKey": api_key,
-        "taskId": task_id
-    });
-
-    let timeout = Duration::from_secs(config.timeout_seconds as u64);
-    let start = std::time::Instant::now();
-
-    while start.elapsed() < timeout {
-        tokio::time::sleep(Duration::from_secs(3)).await;
-
-        let response = client
-            .post(format!("{}/getTaskResult", endpoint))
-            .json(&get_result)
-            .send()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-        let result: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(e.to_string()))?;
-
-        if result["status"].as_str() == Some("ready") {
-            return result["solution"]["gRecaptchaResponse"]
-                .as_str()
-                .or_else(|| result["solution"]["token"].as_str())
-                .map(|s| s.to_string())
-                .ok_or_else(|| RuntimeError::ChallengeFailed("无效的响应".to_string()));
-        }
-
-        if result["errorId"].as_i64() != Some(0) {
-            return Err(RuntimeError::ChallengeFailed(format!(
-                "CapSolver 错误: {}",
-                result["errorDescription"]
-            )));
-        }
-    }
-
-    Err(RuntimeError::ExecutionTimeout {
-        operation: "CapSolver".to_string(),
-        elapsed_ms: timeout.as_millis() as u64,
-        limit_ms: timeout.as_millis() as u64,
-    })
-}
-
 // ============================================================================
 // 脚本处理器
 // ============================================================================
@@ -677,6 +431,7 @@ async fn handle_script(
 // ============================================================================
 
 /// 凭证缓存
+#[derive(Debug)]
 pub struct CredentialsCache {
     cache: RwLock<HashMap<String, ChallengeCredentials>>,
 }
@@ -11,7 +11,7 @@ use super::{
     HandlerContext,
     ResponseContext,
 };
-use crate::{Result, RuntimeError, webview::SharedWebViewProvider};
+use crate::{Result, RuntimeError, context::RuntimeContext};
 use crawler_schema::{
     config::ChallengeConfig,
     script::{Script, ScriptSource},
@@ -25,28 +25,31 @@ use url::Url;
 pub struct ChallengeManager {
     /// 验证配置
     config: ChallengeConfig,
-    /// WebView 提供者
-    webview_provider: SharedWebViewProvider,
     /// 凭证缓存
     credentials_cache: Arc<CredentialsCache>,
-    /// HTTP 客户端
-    http_client: Option<reqwest::Client>,
+    /// HTTP 客户端（用于重试处理器和打码平台请求）
+    http_client: reqwest::Client,
+    /// 运行时上下文（提供 WebView、共享 HTTP 客户端，并用于渲染处理器中的模板）
+    runtime_context: Arc<RuntimeContext>,
 }
 
 impl ChallengeManager {
     /// 创建新的验证管理器
-    pub fn new(config: ChallengeConfig, webview_provider: SharedWebViewProvider) -> Self {
+    ///
+    /// WebView 提供者与 HTTP 客户端取自运行时上下文，处理器中的模板（如 `api_key`）
+    /// 使用其全局变量渲染
+    pub fn new(config: ChallengeConfig, runtime_context: Arc<RuntimeContext>) -> Self {
         Self {
             config,
-            webview_provider,
             credentials_cache: Arc::new(CredentialsCache::new()),
-            http_client: None,
+            http_client: runtime_context.http_client().inner().clone(),
+            runtime_context,
         }
     }
 
     /// 设置 HTTP 客户端（用于重试处理器）
     pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
-        self.http_client = Some(client);
+        self.http_client = client;
         self
     }
 
@@ -79,7 +82,8 @@ impl ChallengeManager {
 
     /// 处理验证
     ///
-    /// 返回验证凭证，调用方需要将凭证应用到后续请求
+    /// 返回验证凭证。新获取的凭证同时写入运行时上下文的共享 HTTP 客户端
+    /// （见 [`ChallengeCredentials::apply_to`]），之后的请求自动携带
     pub async fn handle(
         &self,
         url: &str,
@@ -104,11 +108,12 @@ impl ChallengeManager {
 
         // 构建处理上下文
         let ctx = HandlerContext {
-            webview_provider: self.webview_provider.clone(),
+            webview_provider: self.runtime_context.webview_provider().clone(),
             url: url.to_string(),
             detection,
             response,
             http_client: self.http_client.clone(),
+            runtime_context: Arc::clone(&self.runtime_context),
         };
 
         // 尝试处理
@@ -124,6 +129,9 @@ impl ChallengeManager {
                         creds = creds.with_ttl(duration);
                     }
                     self.credentials_cache.set(&domain, creds).await;
+                    if let Ok(url) = Url::parse(url) {
+                        credentials.apply_to(self.runtime_context.http_client(), &url);
+                    }
 
                     tracing::info!("验证处理成功");
                     return Ok(credentials);
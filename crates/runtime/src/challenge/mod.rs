@@ -18,10 +18,12 @@
 //! 验证凭证 (Cookie, Headers)
 //! ```
 
+mod captcha;
 mod detector;
 mod handler;
 mod manager;
 
+pub use captcha::{CaptchaTask, solve as solve_captcha};
 pub use detector::*;
 pub use handler::*;
 pub use manager::*;
@@ -2,11 +2,30 @@
 //!
 //! 每次流程调用时创建的临时上下文
 
-use super::RuntimeContext;
-use crate::Result;
+pub use super::limits::DEFAULT_MAX_HTTP_REQUESTS;
+use super::{FlowLimits, RuntimeContext, limits::FlowUsage};
+use crate::{Result, error::RuntimeError, extractor::value::ExtractValueData};
+use serde::de::DeserializeOwned;
 use serde_json::{Map, Value};
 use std::sync::Arc;
 
+/// 追踪导出时默认脱敏的变量名关键字（不区分大小写，按子串匹配）
+pub const DEFAULT_REDACTED_KEYS: &[&str] = &[
+    "password",
+    "passwd",
+    "token",
+    "secret",
+    "cookie",
+    "authorization",
+    "api_key",
+];
+
+/// 追踪导出时单个变量序列化后的默认最大长度（字节）
+pub const DEFAULT_TRACE_VALUE_LIMIT: usize = 4096;
+
+/// 脱敏后的占位值
+const REDACTED_PLACEHOLDER: &str = "[REDACTED]";
+
 /// 流程上下文
 ///
 /// 每次流程调用时创建，执行完毕后丢弃。
@@ -14,9 +33,17 @@ use std::sync::Arc;
 ///
 /// # 变量作用域
 ///
-/// - 无前缀变量：先查 Flow，再查 Runtime
+/// - 无前缀变量：先查 Flow（当前作用域，再逐级查父作用域），再查 Runtime
 /// - `$` 前缀变量：仅查 Runtime 全局变量
 ///
+/// # 子作用域
+///
+/// [`child`](Self::child) 创建读穿父作用域、写入仅限本地的子上下文，
+/// 用于映射的每次迭代和组件调用，避免临时变量在迭代或调用之间泄漏。
+/// 子上下文与父作用域共享变量表（写时复制）与 HTTP 请求计数，创建开销与变量数量无关。
+/// 需要交还给父作用域的变量由子上下文 [`export`](Self::export) 标记，
+/// 调用方在子上下文执行完毕后以 [`merge_exports`](Self::merge_exports) 写回。
+///
 /// # 示例
 ///
 /// ```rust,ignore
@@ -33,36 +60,369 @@ use std::sync::Arc;
 /// ```
 #[derive(Debug, Clone)]
 pub struct FlowContext {
-    /// 流程变量
-    data: Map<String, Value>,
+    /// 流程变量（当前作用域，写时复制）
+    data: Arc<Map<String, Value>>,
+    /// 父作用域链（仅子上下文存在）
+    parent: Option<Arc<ParentScope>>,
     /// 运行时上下文引用
     runtime: Arc<RuntimeContext>,
+    /// 请求计数与计时起点（与子作用域、派生上下文共享）
+    usage: Arc<FlowUsage>,
+    /// 资源限制
+    limits: FlowLimits,
+    /// 作用域嵌套深度（根作用域为 0）
+    depth: u32,
+    /// 执行完毕后提升到父作用域的变量名
+    exports: Vec<String>,
+    /// 当前组件调用链（由外到内），不属于流程变量
+    components: Arc<[String]>,
+    /// 是否为演练模式（不发起网络请求）
+    dry_run: bool,
+    /// 演练模式下代替首个网络请求响应的样本
+    fixture: Option<String>,
 }
 
 impl FlowContext {
     /// 创建新的流程上下文
     pub fn new(runtime: Arc<RuntimeContext>) -> Self {
         Self {
-            data: Map::new(),
+            data: Arc::new(Map::new()),
+            parent: None,
             runtime,
+            usage: Arc::new(FlowUsage::start()),
+            limits: FlowLimits::default(),
+            depth: 0,
+            exports: Vec::new(),
+            components: Arc::from([]),
+            dry_run: false,
+            fixture: None,
+        }
+    }
+
+    /// 创建子作用域
+    ///
+    /// 子上下文读取时先查本地、再查父作用域，写入只影响本地；
+    /// HTTP 请求计数与计时起点与父作用域共享，资源限制沿用父作用域
+    pub fn child(&self) -> Self {
+        Self {
+            data: Arc::new(Map::new()),
+            parent: Some(Arc::new(ParentScope {
+                data: Arc::clone(&self.data),
+                parent: self.parent.clone(),
+            })),
+            runtime: self.runtime.clone(),
+            usage: Arc::clone(&self.usage),
+            limits: self.limits,
+            depth: self.depth + 1,
+            exports: Vec::new(),
+            components: Arc::clone(&self.components),
+            dry_run: self.dry_run,
+            fixture: None,
+        }
+    }
+
+    /// 标记变量在子作用域执行完毕后提升到父作用域
+    ///
+    /// 仅记录变量名，由调用方通过父作用域的 [`merge_exports`](Self::merge_exports) 写回；
+    /// 未标记的临时变量随子作用域丢弃
+    pub fn export<K: Into<String>>(&mut self, name: K) {
+        let name = name.into();
+        if !self.exports.contains(&name) {
+            self.exports.push(name);
+        }
+    }
+
+    /// 将子作用域导出的变量写入当前作用域
+    ///
+    /// 只写回子作用域本地定义的变量；标记导出但未定义的变量被忽略
+    pub fn merge_exports(&mut self, child: &FlowContext) {
+        let data = &child.data;
+        let exported = child
+            .exports
+            .iter()
+            .filter_map(|name| data.get(name).map(|value| (name.clone(), value.clone())));
+        self.extend(exported);
+    }
+
+    /// 创建组件调用的子作用域
+    ///
+    /// 与 [`child`](Self::child) 相同，并将 `name` 追加到组件调用链；
+    /// 调用链上已有该组件（循环引用）时返回 `CircularReference`。
+    /// 嵌套层数与其他子作用域一样受 `max_depth` 限制
+    pub fn component_scope(&self, name: &str) -> Result<Self> {
+        let chain = self.components.iter().map(String::as_str);
+        if self.components.iter().any(|component| component == name) {
+            let path: Vec<&str> = chain.chain(std::iter::once(name)).collect();
+            return Err(RuntimeError::CircularReference {
+                path: path.join(" -> "),
+            });
         }
+
+        let mut scope = self.child();
+        scope.components = chain
+            .chain(std::iter::once(name))
+            .map(str::to_string)
+            .collect();
+        Ok(scope)
+    }
+
+    /// 当前组件调用链（由外到内）
+    pub fn component_stack(&self) -> &[String] {
+        &self.components
+    }
+
+    /// 派生同一次流程调用中的并发任务上下文（如详情预取）
+    ///
+    /// 派生的上下文不含任何流程变量，但与当前上下文共享请求计数、计时起点与资源限制，
+    /// 其中发起的请求计入同一个 `max_http_requests` 上限
+    pub fn fork(&self) -> Self {
+        Self {
+            data: Arc::new(Map::new()),
+            parent: None,
+            runtime: self.runtime.clone(),
+            usage: Arc::clone(&self.usage),
+            limits: self.limits,
+            depth: 0,
+            exports: Vec::new(),
+            components: Arc::from([]),
+            dry_run: self.dry_run,
+            fixture: None,
+        }
+    }
+
+    /// 覆盖本次流程的资源限制
+    pub fn with_limits(mut self, limits: FlowLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 获取本次流程的资源限制
+    pub fn limits(&self) -> &FlowLimits {
+        &self.limits
+    }
+
+    /// 覆盖本次流程的最大 HTTP 请求数
+    pub fn with_max_http_requests(mut self, limit: u32) -> Self {
+        self.limits.max_http_requests = limit;
+        self
+    }
+
+    /// 获取本次流程的最大 HTTP 请求数
+    pub fn max_http_requests(&self) -> u32 {
+        self.limits.max_http_requests
+    }
+
+    /// 获取本次流程已发起的 HTTP 请求数
+    pub fn http_request_count(&self) -> u32 {
+        self.usage.http_requests()
+    }
+
+    /// 记录一次 HTTP 请求
+    ///
+    /// 应在每次发起请求前调用，超出上限时返回 `ResourceLimitExceeded`，
+    /// 作为独立于分页 `max_pages` 的安全网。计数由子作用域共享，
+    /// 子作用域中发起的请求同样计入整个流程
+    pub fn record_http_request(&self) -> Result<()> {
+        self.check_deadline("http_request")?;
+        let max = self.limits.max_http_requests;
+        if !self.usage.try_record_http_request(max) {
+            return Err(RuntimeError::ResourceLimitExceeded {
+                limit_type: "http_requests".to_string(),
+                limit: max as u64,
+            });
+        }
+        Ok(())
+    }
+
+    /// 进入演练模式
+    ///
+    /// 首个网络请求以 `fixture` 作为响应正文，之后（或未提供样本时）的网络请求
+    /// 返回 `DryRunNetwork` 错误
+    pub fn with_fixture(mut self, fixture: Option<String>) -> Self {
+        self.dry_run = true;
+        self.fixture = fixture;
+        self
+    }
+
+    /// 是否为演练模式
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// 取出代替网络请求的响应样本
+    ///
+    /// 非演练模式返回 `None`，由调用方正常发起请求；
+    /// 演练模式下样本已用完时返回 `DryRunNetwork`
+    pub fn take_fixture(&mut self, url: &str) -> Result<Option<String>> {
+        if !self.dry_run {
+            return Ok(None);
+        }
+        match self.fixture.take() {
+            Some(fixture) => Ok(Some(fixture)),
+            None => Err(RuntimeError::DryRunNetwork {
+                url: url.to_string(),
+            }),
+        }
+    }
+
+    /// 检查是否超出最长执行时间，超出时返回 `ExecutionTimeout`
+    pub fn check_deadline(&self, operation: &str) -> Result<()> {
+        if let Some(timeout) = self.limits.timeout {
+            let elapsed = self.usage.elapsed();
+            if elapsed > timeout {
+                return Err(RuntimeError::ExecutionTimeout {
+                    operation: operation.to_string(),
+                    elapsed_ms: elapsed.as_millis() as u64,
+                    limit_ms: timeout.as_millis() as u64,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// 提取步骤执行前的检查：执行时间与作用域嵌套深度
+    pub fn check_step_limits(&self) -> Result<()> {
+        self.check_deadline("extract_step")?;
+        if self.depth > self.limits.max_depth {
+            return Err(RuntimeError::ResourceLimitExceeded {
+                limit_type: "scope_depth".to_string(),
+                limit: self.limits.max_depth as u64,
+            });
+        }
+        Ok(())
+    }
+
+    /// 提取步骤执行后的检查：结果数组长度
+    pub fn check_step_output(&self, output: &ExtractValueData) -> Result<()> {
+        let len = match output {
+            ExtractValueData::Array(items) => items.len(),
+            ExtractValueData::Json(json) => json.as_array().map_or(0, Vec::len),
+            _ => 0,
+        };
+        if len > self.limits.max_array_len {
+            return Err(RuntimeError::ResourceLimitExceeded {
+                limit_type: "array_len".to_string(),
+                limit: self.limits.max_array_len as u64,
+            });
+        }
+        Ok(())
     }
 
     /// 设置流程变量
     pub fn set<K: Into<String>>(&mut self, key: K, value: Value) {
-        self.data.insert(key.into(), value);
+        Arc::make_mut(&mut self.data).insert(key.into(), value);
     }
 
-    /// 获取流程变量（仅查 Flow）
+    /// 获取流程变量（仅查 Flow，含父作用域）
     pub fn get(&self, key: &str) -> Option<&Value> {
-        self.data.get(key)
+        self.data
+            .get(key)
+            .or_else(|| self.parent.as_ref().and_then(|parent| parent.get(key)))
     }
 
     /// 获取变量（先查 Flow，再查 Runtime）
     pub fn resolve(&self, key: &str) -> Option<&Value> {
-        self.data
-            .get(key)
-            .or_else(|| self.runtime.globals().get(key))
+        self.get(key).or_else(|| self.runtime.globals().get(key))
+    }
+
+    /// 按路径获取变量，支持 `.` 访问对象字段与 `[n]` 访问数组元素
+    ///
+    /// 如 `item.author.name`、`data[0].title`、`$.base_url`。`$.` 前缀仅查 Runtime 全局变量，
+    /// 否则根变量先查 Flow 再查 Runtime。路径中任一段不存在时返回 `Extraction` 错误，
+    /// 错误信息指明失败的路径段
+    pub fn resolve_path(&self, path: &str) -> Result<&Value> {
+        let (globals_only, path) = match path.strip_prefix("$.") {
+            Some(rest) => (true, rest),
+            None => (false, path),
+        };
+        let mut segments = parse_path(path)?.into_iter();
+        let Some(PathSegment::Key(root)) = segments.next() else {
+            return Err(RuntimeError::Extraction(format!(
+                "Invalid variable path '{}': must start with a variable name",
+                path
+            )));
+        };
+
+        let root_value = if globals_only {
+            self.runtime.globals().get(root)
+        } else {
+            self.resolve(root)
+        };
+        let mut current = root_value.ok_or_else(|| {
+            RuntimeError::Extraction(format!("Variable '{}' is not defined", root))
+        })?;
+        let mut walked = root.to_string();
+        for segment in segments {
+            let next = match segment {
+                PathSegment::Key(key) => {
+                    walked.push('.');
+                    walked.push_str(key);
+                    current.as_object().and_then(|object| object.get(key))
+                }
+                PathSegment::Index(index) => {
+                    walked.push_str(&format!("[{}]", index));
+                    current.as_array().and_then(|array| array.get(index))
+                }
+            };
+            current = next.ok_or_else(|| {
+                RuntimeError::Extraction(format!(
+                    "Variable path '{}' not found: '{}' does not exist",
+                    path, walked
+                ))
+            })?;
+        }
+        Ok(current)
+    }
+
+    /// 获取字符串变量（先查 Flow，再查 Runtime）
+    ///
+    /// 数字与布尔值转换为字符串，其他类型返回 `None`
+    pub fn get_str(&self, key: &str) -> Option<String> {
+        match self.resolve(key)? {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            _ => None,
+        }
+    }
+
+    /// 获取整数变量（先查 Flow，再查 Runtime）
+    ///
+    /// 接受整数、无小数部分的浮点数，以及可解析为整数的字符串（如页码 `"2"`）
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        match self.resolve(key)? {
+            Value::Number(n) => n
+                .as_i64()
+                .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64)),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// 获取布尔变量（先查 Flow，再查 Runtime）
+    ///
+    /// 接受布尔值、数字（非 0 为真）以及字符串 `true`/`false`/`1`/`0`
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.resolve(key)? {
+            Value::Bool(b) => Some(*b),
+            Value::Number(n) => n.as_f64().map(|f| f != 0.0),
+            Value::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" => Some(true),
+                "false" | "0" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// 获取数组变量（先查 Flow，再查 Runtime）
+    pub fn get_array(&self, key: &str) -> Option<&[Value]> {
+        self.resolve(key)?.as_array().map(Vec::as_slice)
+    }
+
+    /// 将变量反序列化为指定类型（先查 Flow，再查 Runtime），失败时返回 `None`
+    pub fn get_as<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        serde_json::from_value(self.resolve(key)?.clone()).ok()
     }
 
     /// 获取运行时上下文
@@ -70,11 +430,23 @@ impl FlowContext {
         &self.runtime
     }
 
-    /// 获取流程变量 Map
+    /// 获取当前作用域的流程变量 Map（不含父作用域）
     pub fn data(&self) -> &Map<String, Value> {
         &self.data
     }
 
+    /// 获取所有可见的流程变量（父作用域在前，当前作用域覆盖同名变量）
+    pub fn variables(&self) -> Map<String, Value> {
+        let mut vars = match &self.parent {
+            Some(parent) => parent.variables(),
+            None => Map::new(),
+        };
+        for (k, v) in self.data.iter() {
+            vars.insert(k.clone(), v.clone());
+        }
+        vars
+    }
+
     /// 转换为 tera::Context
     ///
     /// 合并两层变量：
@@ -90,8 +462,8 @@ impl FlowContext {
         }
 
         // 2. 再放 Flow 变量（覆盖同名全局变量）
-        for (k, v) in &self.data {
-            merged.insert(k.clone(), v.clone());
+        for (k, v) in self.variables() {
+            merged.insert(k, v);
         }
 
         // 3. 将全局变量放入 $ 命名空间
@@ -99,16 +471,14 @@ impl FlowContext {
         merged.insert("$".to_string(), globals_obj);
 
         // 使用 from_value 零拷贝转换
-        tera::Context::from_value(Value::Object(merged)).map_err(|e| {
-            crate::error::RuntimeError::TemplateError {
-                error: format!("创建模板上下文失败: {}", e),
-            }
+        tera::Context::from_value(Value::Object(merged)).map_err(|e| RuntimeError::TemplateError {
+            error: format!("创建模板上下文失败: {}", e),
         })
     }
 
-    /// 清空流程变量
+    /// 清空当前作用域的流程变量
     pub fn clear(&mut self) {
-        self.data.clear();
+        Arc::make_mut(&mut self.data).clear();
     }
 
     /// 批量设置流程变量
@@ -117,8 +487,186 @@ impl FlowContext {
         I: IntoIterator<Item = (K, Value)>,
         K: Into<String>,
     {
+        let data = Arc::make_mut(&mut self.data);
         for (k, v) in iter {
-            self.data.insert(k.into(), v);
+            data.insert(k.into(), v);
+        }
+    }
+
+    /// 导出上下文快照，用于问题复现和错误报告
+    ///
+    /// 使用 [`DEFAULT_REDACTED_KEYS`] 脱敏敏感变量，
+    /// 并按 [`DEFAULT_TRACE_VALUE_LIMIT`] 截断过大的变量值。
+    pub fn export_trace(&self) -> Value {
+        self.export_trace_with(DEFAULT_REDACTED_KEYS, DEFAULT_TRACE_VALUE_LIMIT)
+    }
+
+    /// 使用自定义脱敏列表和大小上限导出上下文快照
+    ///
+    /// - 变量名或嵌套字段名包含 `redacted_keys` 中任一关键字（不区分大小写）时， 值替换为
+    ///   `[REDACTED]`
+    /// - 变量值序列化后超过 `max_value_len` 字节时，替换为截断说明及前缀预览
+    pub fn export_trace_with(&self, redacted_keys: &[&str], max_value_len: usize) -> Value {
+        let sanitize = |vars: &Map<String, Value>| -> Value {
+            let mut out = Map::new();
+            for (k, v) in vars {
+                out.insert(
+                    k.clone(),
+                    sanitize_trace_value(k, v, redacted_keys, max_value_len),
+                );
+            }
+            Value::Object(out)
+        };
+
+        let meta = &self.runtime.rule().meta;
+        serde_json::json!({
+            "rule": {
+                "name": meta.name,
+                "version": meta.version,
+                "domain": meta.domain,
+            },
+            "globals": sanitize(self.runtime.globals()),
+            "flow": sanitize(&self.variables()),
+        })
+    }
+}
+
+/// 父作用域快照
+///
+/// 子上下文创建时共享父作用域的变量表；子上下文存续期间父作用域写入变量会复制变量表，
+/// 因此子上下文看到的是创建时的变量
+#[derive(Debug)]
+struct ParentScope {
+    /// 父作用域变量
+    data: Arc<Map<String, Value>>,
+    /// 更外层的作用域
+    parent: Option<Arc<ParentScope>>,
+}
+
+impl ParentScope {
+    /// 逐级查找变量
+    fn get(&self, key: &str) -> Option<&Value> {
+        self.data
+            .get(key)
+            .or_else(|| self.parent.as_ref().and_then(|parent| parent.get(key)))
+    }
+
+    /// 合并所有层级的变量（外层在前）
+    fn variables(&self) -> Map<String, Value> {
+        let mut vars = match &self.parent {
+            Some(parent) => parent.variables(),
+            None => Map::new(),
+        };
+        for (k, v) in self.data.iter() {
+            vars.insert(k.clone(), v.clone());
+        }
+        vars
+    }
+}
+
+/// 对单个变量执行脱敏和大小截断
+///
+/// 嵌套对象中的敏感字段（如 `headers.Authorization`、`body.password`）同样脱敏
+fn sanitize_trace_value(
+    key: &str,
+    value: &Value,
+    redacted_keys: &[&str],
+    max_value_len: usize,
+) -> Value {
+    if is_redacted_key(key, redacted_keys) {
+        return Value::String(REDACTED_PLACEHOLDER.to_string());
+    }
+
+    let value = redact_nested(value, redacted_keys);
+    let serialized = value.to_string();
+    if serialized.len() <= max_value_len {
+        return value;
+    }
+
+    let preview: String = serialized
+        .char_indices()
+        .take_while(|(i, _)| *i < max_value_len)
+        .map(|(_, c)| c)
+        .collect();
+    Value::String(format!(
+        "[TRUNCATED {} bytes] {}",
+        serialized.len(),
+        preview
+    ))
+}
+
+/// 变量名是否包含任一脱敏关键字（不区分大小写）
+fn is_redacted_key(key: &str, redacted_keys: &[&str]) -> bool {
+    let lower = key.to_lowercase();
+    redacted_keys
+        .iter()
+        .any(|r| lower.contains(&r.to_lowercase()))
+}
+
+/// 递归脱敏对象与数组中的敏感字段
+fn redact_nested(value: &Value, redacted_keys: &[&str]) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let v = if is_redacted_key(k, redacted_keys) {
+                        Value::String(REDACTED_PLACEHOLDER.to_string())
+                    } else {
+                        redact_nested(v, redacted_keys)
+                    };
+                    (k.clone(), v)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| redact_nested(item, redacted_keys))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+/// 变量路径段
+enum PathSegment<'a> {
+    /// 对象字段
+    Key(&'a str),
+    /// 数组下标
+    Index(usize),
+}
+
+/// 解析变量路径，如 `data[0].title` → `data`、`[0]`、`title`
+fn parse_path(path: &str) -> Result<Vec<PathSegment<'_>>> {
+    let invalid = |reason: &str| {
+        RuntimeError::Extraction(format!("Invalid variable path '{}': {}", path, reason))
+    };
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let (key, mut rest) = match part.find('[') {
+            Some(pos) => part.split_at(pos),
+            None => (part, ""),
+        };
+        if key.is_empty() && (segments.is_empty() || rest.is_empty()) {
+            return Err(invalid("empty segment"));
+        }
+        if !key.is_empty() {
+            segments.push(PathSegment::Key(key));
+        }
+        while let Some(inner) = rest.strip_prefix('[') {
+            let (index, after) = inner
+                .split_once(']')
+                .ok_or_else(|| invalid("unclosed '['"))?;
+            let index = index
+                .trim()
+                .parse()
+                .map_err(|_| invalid(&format!("'{}' is not an array index", index)))?;
+            segments.push(PathSegment::Index(index));
+            rest = after;
+        }
+        if !rest.is_empty() {
+            return Err(invalid(&format!("unexpected '{}'", rest)));
         }
     }
+    Ok(segments)
 }
@@ -0,0 +1,131 @@
+//! # 流程资源限制
+//!
+//! 防止失控的规则耗尽内存或发起大量请求。限制随 [`FlowContext`](super::FlowContext)
+//! 传递；请求计数与计时起点保存在 [`FlowUsage`] 中，由同一次流程调用派生的所有上下文
+//! （子作用域、详情预取等并发任务）共享，在以下位置检查：
+//!
+//! - 每次发起 HTTP 请求前：请求数与执行时间
+//! - 每个提取步骤执行前：执行时间与作用域嵌套深度
+//! - 每个提取步骤执行后：结果数组长度
+
+use crawler_schema::config::LimitsConfig;
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::{Duration, Instant},
+};
+
+/// 单次流程默认允许的最大 HTTP 请求数
+pub const DEFAULT_MAX_HTTP_REQUESTS: u32 = 500;
+
+/// 默认最大作用域嵌套深度（`map`、组件等每层创建一个子作用域）
+pub const DEFAULT_MAX_DEPTH: u32 = 32;
+
+/// 单个步骤结果数组的默认最大长度
+pub const DEFAULT_MAX_ARRAY_LEN: usize = 100_000;
+
+/// 流程资源限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlowLimits {
+    /// 最大 HTTP 请求数
+    pub max_http_requests: u32,
+    /// 最长执行时间（默认不限制，WebView 登录等流程需要等待用户操作）
+    pub timeout: Option<Duration>,
+    /// 最大作用域嵌套深度
+    pub max_depth: u32,
+    /// 单个步骤结果数组的最大长度
+    pub max_array_len: usize,
+}
+
+impl Default for FlowLimits {
+    fn default() -> Self {
+        Self {
+            max_http_requests: DEFAULT_MAX_HTTP_REQUESTS,
+            timeout: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_array_len: DEFAULT_MAX_ARRAY_LEN,
+        }
+    }
+}
+
+impl FlowLimits {
+    /// 设置最大 HTTP 请求数
+    pub fn with_max_http_requests(mut self, limit: u32) -> Self {
+        self.max_http_requests = limit;
+        self
+    }
+
+    /// 设置最长执行时间
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 设置最大作用域嵌套深度
+    pub fn with_max_depth(mut self, depth: u32) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// 设置单个步骤结果数组的最大长度
+    pub fn with_max_array_len(mut self, len: usize) -> Self {
+        self.max_array_len = len;
+        self
+    }
+}
+
+impl From<&LimitsConfig> for FlowLimits {
+    /// 规则中的限制配置，未填写的字段使用默认值
+    fn from(config: &LimitsConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            max_http_requests: config
+                .max_http_requests
+                .unwrap_or(defaults.max_http_requests),
+            timeout: config.timeout.map(Duration::from_secs).or(defaults.timeout),
+            max_depth: config.max_depth.unwrap_or(defaults.max_depth),
+            max_array_len: config.max_array_len.map_or(defaults.max_array_len, |len| {
+                usize::try_from(len).unwrap_or(usize::MAX)
+            }),
+        }
+    }
+}
+
+/// 流程资源用量
+///
+/// 记录一次流程调用已发起的 HTTP 请求数与开始时间，以 `Arc` 在派生的上下文之间共享
+#[derive(Debug)]
+pub(crate) struct FlowUsage {
+    /// 已发起的 HTTP 请求数
+    http_requests: AtomicU32,
+    /// 流程开始时间
+    started_at: Instant,
+}
+
+impl FlowUsage {
+    /// 从当前时刻开始计时
+    pub(crate) fn start() -> Self {
+        Self {
+            http_requests: AtomicU32::new(0),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// 已发起的 HTTP 请求数
+    pub(crate) fn http_requests(&self) -> u32 {
+        self.http_requests.load(Ordering::Relaxed)
+    }
+
+    /// 未达到 `max` 时计入一次请求，返回是否成功
+    pub(crate) fn try_record_http_request(&self, max: u32) -> bool {
+        self.http_requests
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                (count < max).then_some(count + 1)
+            })
+            .is_ok()
+    }
+
+    /// 流程开始以来经过的时间
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
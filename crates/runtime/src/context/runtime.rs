@@ -3,12 +3,17 @@
 //! 爬虫实例级的共享资源和全局变量
 
 use crate::{
+    RuntimeError,
+    challenge::CredentialsCache,
+    extractor::{SelectorValidator, ValidationReport},
     http::HttpClient,
-    script::{ScriptEngine, ScriptLanguage},
+    script::{ScriptEngine, ScriptEngineFactory, ScriptLanguage},
     webview::{SharedWebViewProvider, noop_provider},
 };
 use crawler_schema::core::CrawlerRule;
 use dashmap::DashMap;
+use regex::Regex;
+use scraper::Selector;
 use serde_json::{Map, Value};
 use std::sync::Arc;
 
@@ -25,6 +30,8 @@ use std::sync::Arc;
 /// - `template_engine`: 模板渲染引擎
 /// - `globals`: 全局变量（base_url, domain 等）
 /// - `webview_provider`: WebView 提供者（可选）
+/// - `selectors`/`regexes`: 已编译的 CSS 选择器与正则缓存，按表达式复用
+/// - `challenge_credentials`: 人机验证凭证缓存，按域名复用
 #[derive(Debug)]
 pub struct RuntimeContext {
     /// 爬虫规则
@@ -37,6 +44,14 @@ pub struct RuntimeContext {
     webview_provider: SharedWebViewProvider,
     /// 脚本引擎缓存（按语言类型懒加载）
     script_engines: Arc<DashMap<ScriptLanguage, Arc<dyn ScriptEngine>>>,
+    /// 已编译的 CSS 选择器（按选择器文本缓存）
+    selectors: DashMap<String, Arc<Selector>>,
+    /// 已编译的正则（按模式文本缓存）
+    regexes: DashMap<String, Arc<Regex>>,
+    /// 加载规则时的校验结果（仅含警告，存在错误时无法创建上下文）
+    validation: ValidationReport,
+    /// 人机验证凭证缓存（按域名，各流程共享）
+    challenge_credentials: Arc<CredentialsCache>,
 }
 
 impl RuntimeContext {
@@ -50,9 +65,50 @@ impl RuntimeContext {
         rule: CrawlerRule,
         webview_provider: SharedWebViewProvider,
     ) -> crate::Result<Self> {
+        Self::with_resources(rule, webview_provider, None)
+    }
+
+    /// 使用外部资源创建运行时上下文
+    ///
+    /// `http_client` 为空时按规则的 HTTP 配置创建新客户端；
+    /// 传入共享客户端时复用其连接池与 Cookie，规则的 HTTP 配置不再生效
+    pub fn with_resources(
+        rule: CrawlerRule,
+        webview_provider: SharedWebViewProvider,
+        http_client: Option<Arc<HttpClient>>,
+    ) -> crate::Result<Self> {
+        // 预编译选择器，尽早暴露语法错误
+        let report = SelectorValidator::check_rule(&rule);
+        for warning in report.warnings() {
+            tracing::warn!("{}", warning);
+        }
+        if let Some(error) = report.errors().next() {
+            return Err(error.clone());
+        }
+
+        // 规则要求渲染页面但没有可用的 WebView 时尽早提示，实际执行时返回错误
+        let uses_render = [
+            rule.http.as_ref(),
+            rule.search.http.as_ref(),
+            rule.detail.http.as_ref(),
+            rule.discovery.as_ref().and_then(|f| f.http.as_ref()),
+            rule.content.as_ref().and_then(|f| f.http.as_ref()),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|http| http.render.is_some());
+        if uses_render && !webview_provider.is_available() {
+            tracing::warn!(
+                "规则配置了 http.render，但 {} 不可用，相关流程将失败",
+                webview_provider.name()
+            );
+        }
+
         // 创建 HTTP 客户端
-        let http_config = rule.http.clone().unwrap_or_default();
-        let http_client = Arc::new(HttpClient::new(http_config)?);
+        let http_client = match http_client {
+            Some(client) => client,
+            None => Arc::new(HttpClient::new(rule.http.clone().unwrap_or_default())?),
+        };
 
         // 初始化全局变量
         let mut globals = Map::new();
@@ -71,6 +127,10 @@ impl RuntimeContext {
             globals,
             webview_provider,
             script_engines: Arc::new(DashMap::new()),
+            selectors: DashMap::new(),
+            regexes: DashMap::new(),
+            validation: report,
+            challenge_credentials: Arc::new(CredentialsCache::new()),
         })
     }
 
@@ -89,6 +149,11 @@ impl RuntimeContext {
         &self.globals
     }
 
+    /// 获取人机验证凭证缓存
+    pub fn challenge_credentials(&self) -> &Arc<CredentialsCache> {
+        &self.challenge_credentials
+    }
+
     /// 获取 WebView 提供者
     pub fn webview_provider(&self) -> &SharedWebViewProvider {
         &self.webview_provider
@@ -96,7 +161,7 @@ impl RuntimeContext {
 
     /// 检查是否支持 WebView
     pub fn has_webview_support(&self) -> bool {
-        self.webview_provider.name() != "NoopWebViewProvider"
+        self.webview_provider.is_available()
     }
 
     /// 获取基础 URL
@@ -113,4 +178,47 @@ impl RuntimeContext {
     pub fn get_global(&self, key: &str) -> Option<&Value> {
         self.globals.get(key)
     }
+
+    /// 获取脚本引擎（按语言缓存，首次使用时创建）
+    pub fn script_engine(&self, language: ScriptLanguage) -> Arc<dyn ScriptEngine> {
+        self.script_engines
+            .entry(language)
+            .or_insert_with(|| ScriptEngineFactory::create(language))
+            .clone()
+    }
+
+    /// 获取加载规则时的校验结果
+    pub fn validation(&self) -> &ValidationReport {
+        &self.validation
+    }
+
+    /// 获取编译后的 CSS 选择器（按选择器文本缓存，首次使用时编译）
+    pub fn css_selector(&self, expr: &str) -> crate::Result<Arc<Selector>> {
+        if let Some(selector) = self.selectors.get(expr) {
+            return Ok(selector.clone());
+        }
+        let selector = Selector::parse(expr).map_err(|e| {
+            RuntimeError::Extraction(format!("Invalid CSS selector '{}': {:?}", expr, e))
+        })?;
+        Ok(self
+            .selectors
+            .entry(expr.to_string())
+            .or_insert_with(|| Arc::new(selector))
+            .clone())
+    }
+
+    /// 获取编译后的正则（按模式文本缓存，首次使用时编译）
+    pub fn regex(&self, pattern: &str) -> crate::Result<Arc<Regex>> {
+        if let Some(re) = self.regexes.get(pattern) {
+            return Ok(re.clone());
+        }
+        let re = Regex::new(pattern).map_err(|e| {
+            RuntimeError::Extraction(format!("Invalid regex pattern '{}': {}", pattern, e))
+        })?;
+        Ok(self
+            .regexes
+            .entry(pattern.to_string())
+            .or_insert_with(|| Arc::new(re))
+            .clone())
+    }
 }
@@ -0,0 +1,95 @@
+//! # 运行时构建器
+//!
+//! 组装规则、WebView 提供者与 HTTP 客户端，创建 [`CrawlerRuntime`]
+
+use super::{CrawlerRuleExt, CrawlerRuntime};
+use crate::{
+    Result,
+    RuntimeError,
+    context::{FlowLimits, RuntimeContext},
+    http::HttpClient,
+    webview::{SharedWebViewProvider, WebViewProvider, noop_provider},
+};
+use crawler_schema::core::CrawlerRule;
+use std::sync::Arc;
+
+/// 运行时构建器
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let runtime = CrawlerRuntime::builder()
+///     .rule(rule)
+///     .webview_provider(TauriWebViewProvider::new())
+///     .build()?;
+/// ```
+#[derive(Default)]
+pub struct CrawlerRuntimeBuilder {
+    rule: Option<CrawlerRule>,
+    webview_provider: Option<SharedWebViewProvider>,
+    http_client: Option<Arc<HttpClient>>,
+    limits: Option<FlowLimits>,
+}
+
+impl CrawlerRuntimeBuilder {
+    /// 创建构建器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置爬虫规则（必需）
+    pub fn rule(mut self, rule: CrawlerRule) -> Self {
+        self.rule = Some(rule);
+        self
+    }
+
+    /// 设置 WebView 提供者（未设置时使用空实现，登录与人机验证不可用）
+    pub fn webview_provider<P: WebViewProvider + 'static>(mut self, provider: P) -> Self {
+        self.webview_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// 设置共享的 WebView 提供者
+    pub fn shared_webview_provider(mut self, provider: SharedWebViewProvider) -> Self {
+        self.webview_provider = Some(provider);
+        self
+    }
+
+    /// 使用共享的 HTTP 客户端
+    ///
+    /// 多个运行时共享连接池与 Cookie；设置后规则中的全局 HTTP 配置不再用于创建客户端
+    pub fn http_client(mut self, http_client: Arc<HttpClient>) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// 设置每次流程调用的资源限制，覆盖规则中的 `limits` 配置
+    ///
+    /// 未设置时使用规则中的配置，规则未配置的字段取 [`FlowLimits::default`]
+    pub fn limits(mut self, limits: FlowLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// 构建运行时
+    ///
+    /// 规范化并校验规则，校验失败或未设置规则时返回错误
+    pub fn build(self) -> Result<CrawlerRuntime> {
+        let rule = self.rule.ok_or_else(|| RuntimeError::MissingConfig {
+            field: "rule".to_string(),
+        })?;
+        let rule = rule.normalize();
+        let limits = self.limits.unwrap_or_else(|| {
+            rule.limits
+                .as_ref()
+                .map(FlowLimits::from)
+                .unwrap_or_default()
+        });
+        let runtime_context = RuntimeContext::with_resources(
+            rule,
+            self.webview_provider.unwrap_or_else(noop_provider),
+            self.http_client,
+        )?;
+        Ok(CrawlerRuntime::from_context(Arc::new(runtime_context)).with_limits(limits))
+    }
+}
@@ -0,0 +1,59 @@
+//! # 演练模式
+//!
+//! 不访问网络，用调用方提供的 HTML 样本代替流程的首个 HTTP 响应执行提取，
+//! 同时返回规则的静态校验结果。用于规则开发与规则测试。
+//!
+//! 流程中除首个请求外的网络访问（分页的后续页、详情预取等）均不会发生：
+//! 后续请求返回 `DryRunNetwork` 错误，详情预取被跳过。
+
+use crate::{
+    Result,
+    extractor::ValidationReport,
+    flow::{
+        content::{ContentRequest, ContentResponse},
+        detail::{DetailRequest, DetailResponse},
+        discovery::{DiscoveryRequest, DiscoveryResponse},
+        search::{SearchRequest, SearchResponse},
+        suggest::SuggestRequest,
+    },
+    model::SearchItem,
+};
+
+/// 演练的流程及其输入
+#[derive(Debug, Clone)]
+pub enum DryRunFlow {
+    /// 搜索
+    Search(SearchRequest),
+    /// 搜索建议
+    Suggest(SuggestRequest),
+    /// 发现页
+    Discovery(DiscoveryRequest),
+    /// 详情
+    Detail(DetailRequest),
+    /// 内容
+    Content(ContentRequest),
+}
+
+/// 演练的流程输出
+#[derive(Debug, Clone)]
+pub enum DryRunOutput {
+    /// 搜索结果
+    Search(SearchResponse),
+    /// 搜索建议
+    Suggest(Vec<SearchItem>),
+    /// 发现页结果
+    Discovery(DiscoveryResponse),
+    /// 详情
+    Detail(DetailResponse),
+    /// 内容
+    Content(ContentResponse),
+}
+
+/// 演练结果
+#[derive(Debug)]
+pub struct DryRunReport {
+    /// 规则静态校验结果（错误与警告）
+    pub validation: ValidationReport,
+    /// 流程执行结果
+    pub output: Result<DryRunOutput>,
+}
@@ -1,3 +1,9 @@
 //! # 爬虫运行时主入口模块
+pub mod builder;
+pub mod dry_run;
+pub mod normalize;
 pub mod runtime;
+pub use builder::CrawlerRuntimeBuilder;
+pub use dry_run::{DryRunFlow, DryRunOutput, DryRunReport};
+pub use normalize::CrawlerRuleExt;
 pub use runtime::CrawlerRuntime;
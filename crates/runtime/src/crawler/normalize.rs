@@ -0,0 +1,86 @@
+//! # 规则规范化
+//!
+//! 将全局配置下沉到各流程，生成无需再做合并判断的完整规则
+
+use crate::{context::FlowLimits, http::HttpConfigExt};
+use crawler_schema::{
+    config::{
+        DEFAULT_FOLLOW_REDIRECTS,
+        DEFAULT_MAX_REDIRECTS,
+        DEFAULT_TIMEOUT,
+        DEFAULT_USER_AGENT,
+        HttpConfig,
+        LimitsConfig,
+    },
+    core::CrawlerRule,
+};
+
+/// 规则规范化扩展 trait
+pub trait CrawlerRuleExt {
+    /// 生成规范化后的规则
+    ///
+    /// - 全局 HTTP 配置补齐默认值（User-Agent、超时、重定向）
+    /// - 每个流程的 `http` 均为"全局配置 + 流程覆盖"合并后的完整配置
+    /// - `limits` 中未填写的字段补齐 [`FlowLimits`] 的默认值，缺省的 `limits` 表同样补齐
+    /// - `meta.domain` 去除末尾的 `/`，避免拼接 URL 时出现 `//`
+    ///
+    /// 原始规则保持不变，序列化时应使用原始规则以保证往返一致。
+    fn normalize(&self) -> CrawlerRule;
+}
+
+impl CrawlerRuleExt for CrawlerRule {
+    fn normalize(&self) -> CrawlerRule {
+        let mut rule = self.clone();
+
+        let defaults = HttpConfig {
+            user_agent: Some(DEFAULT_USER_AGENT.to_string()),
+            timeout: Some(DEFAULT_TIMEOUT),
+            follow_redirects: Some(DEFAULT_FOLLOW_REDIRECTS),
+            max_redirects: Some(DEFAULT_MAX_REDIRECTS),
+            ..Default::default()
+        };
+        let global = match &self.http {
+            Some(http) => defaults.merge(http),
+            None => defaults,
+        };
+
+        let resolve = |flow: &Option<HttpConfig>| -> Option<HttpConfig> {
+            Some(match flow {
+                Some(http) => global.merge(http),
+                None => global.clone(),
+            })
+        };
+
+        rule.search.http = resolve(&self.search.http);
+        rule.detail.http = resolve(&self.detail.http);
+        if let Some(suggest) = rule.suggest.as_mut() {
+            suggest.http = resolve(&suggest.http);
+        }
+        if let Some(discovery) = rule.discovery.as_mut() {
+            discovery.http = resolve(&discovery.http);
+        }
+        if let Some(content) = rule.content.as_mut() {
+            content.http = resolve(&content.http);
+        }
+        rule.http = Some(global.clone());
+
+        let limits = self
+            .limits
+            .as_ref()
+            .map(FlowLimits::from)
+            .unwrap_or_default();
+        rule.limits = Some(LimitsConfig {
+            max_http_requests: Some(limits.max_http_requests),
+            timeout: limits.timeout.map(|timeout| timeout.as_secs()),
+            max_depth: Some(limits.max_depth),
+            max_array_len: Some(limits.max_array_len as u64),
+        });
+
+        let domain = rule.meta.domain.trim_end_matches('/');
+        if domain.len() != rule.meta.domain.len() {
+            rule.meta.domain = domain.to_string();
+        }
+
+        rule
+    }
+}
@@ -4,15 +4,35 @@
 
 use crate::{
     Result,
-    context::{FlowContext, RuntimeContext},
+    RuntimeError,
+    context::{FlowContext, FlowLimits, RuntimeContext},
+    crawler::{CrawlerRuntimeBuilder, DryRunFlow, DryRunOutput, DryRunReport},
+    extractor::{
+        RuleWalker,
+        StepVisitor,
+        ValidationReport,
+        selector::{ConditionExecutor, CssSelectorExecutor, XpathExecutor},
+    },
     flow::{
+        content::{ContentFlowExecutor, ContentRequest, ContentResponse},
         detail::{DetailFlowExecutor, DetailRequest, DetailResponse},
+        discovery::{DiscoveryFlowExecutor, DiscoveryRequest, DiscoveryResponse},
+        login::{LoginFlowExecutor, LoginRequest, LoginResponse},
         search::{SearchFlowExecutor, SearchRequest, SearchResponse},
+        suggest::{SuggestFlowExecutor, SuggestRequest},
     },
-    webview::{SharedWebViewProvider, noop_provider},
+    model::SearchItem,
+    script::ScriptExecutor,
+    webview::SharedWebViewProvider,
+};
+use crawler_schema::{
+    core::CrawlerRule,
+    extract::{ExtractStep, FieldExtractor, RegexStep, SelectorStep},
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
 };
-use crawler_schema::core::CrawlerRule;
-use std::sync::Arc;
 
 /// 爬虫运行时
 ///
@@ -22,6 +42,10 @@ use std::sync::Arc;
 pub struct CrawlerRuntime {
     /// 运行时上下文（共享资源）
     runtime_context: Arc<RuntimeContext>,
+    /// 预热结果（仅计算一次）
+    warm_up: Arc<OnceLock<ValidationReport>>,
+    /// 每次流程调用的资源限制
+    limits: FlowLimits,
 }
 
 impl CrawlerRuntime {
@@ -29,38 +53,229 @@ impl CrawlerRuntime {
     ///
     /// 如果规则包含需要 WebView 的配置（如登录、人机验证），
     /// 相关功能将不可用。推荐使用 `builder()` 方法注入 WebView 提供者。
+    ///
+    /// 运行时持有的是经过 [`normalize`](crate::crawler::CrawlerRuleExt::normalize) 规范化后的规则。
     pub fn new(rule: CrawlerRule, webview_provider: Option<SharedWebViewProvider>) -> Result<Self> {
-        let webview_provider = webview_provider.unwrap_or_else(noop_provider);
-        // 创建运行时上下文
-        let runtime_context = Arc::new(RuntimeContext::with_webview_provider(
-            rule,
-            webview_provider,
-        )?);
+        let mut builder = CrawlerRuntimeBuilder::new().rule(rule);
+        if let Some(provider) = webview_provider {
+            builder = builder.shared_webview_provider(provider);
+        }
+        builder.build()
+    }
+
+    /// 创建运行时构建器
+    pub fn builder() -> CrawlerRuntimeBuilder {
+        CrawlerRuntimeBuilder::new()
+    }
 
-        Ok(Self { runtime_context })
+    /// 从已创建的运行时上下文构建
+    pub(crate) fn from_context(runtime_context: Arc<RuntimeContext>) -> Self {
+        Self {
+            runtime_context,
+            warm_up: Arc::new(OnceLock::new()),
+            limits: FlowLimits::default(),
+        }
+    }
+
+    /// 设置每次流程调用的资源限制
+    pub(crate) fn with_limits(mut self, limits: FlowLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 创建一次流程调用的上下文
+    fn flow_context(&self) -> FlowContext {
+        FlowContext::new(self.runtime_context.clone()).with_limits(self.limits)
+    }
+
+    /// 预热运行时
+    ///
+    /// 提前完成首次请求前的准备工作，并汇总诊断信息：
+    /// - 编译规则中所有 CSS 选择器（含 XPath 转换结果）、正则、`validate` 正则 与条件步骤的
+    ///   `matches` 正则，写入运行时上下文的缓存
+    /// - 加载脚本代码并创建所需的脚本引擎
+    ///
+    /// 选择器与模板语法等规则校验在创建运行时上下文时已完成（存在错误时无法创建），
+    /// 其警告包含在返回的报告中；HTTP 客户端（含代理客户端）同样在创建时构建。
+    /// 可重复调用，仅首次调用执行实际工作
+    pub fn warm_up(&self) -> &ValidationReport {
+        self.warm_up.get_or_init(|| {
+            let runtime_context = &self.runtime_context;
+            let rule = runtime_context.rule();
+            let mut report = runtime_context.validation().clone();
+
+            let _ = RuleWalker::walk_rule(
+                rule,
+                &mut WarmUpVisitor {
+                    runtime_context,
+                    report: &mut report,
+                },
+            );
+
+            report
+        })
     }
 
     /// 搜索
     pub async fn search(&self, keyword: &str, page: u32) -> Result<SearchResponse> {
-        let request = SearchRequest {
+        self.search_with(SearchRequest {
             keyword: keyword.to_string(),
             page,
-        };
+            cursor: None,
+            sorts: HashMap::new(),
+            filters: HashMap::new(),
+            prefetch_detail: false,
+            prefetch_concurrency: None,
+        })
+        .await
+    }
+
+    /// 使用完整请求参数搜索（含排序与筛选选项）
+    pub async fn search_with(&self, request: SearchRequest) -> Result<SearchResponse> {
         let flow = &self.runtime_context.rule().search;
-        let mut flow_context = FlowContext::new(self.runtime_context.clone());
+        let mut flow_context = self.flow_context();
         SearchFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context).await
     }
 
+    /// 获取搜索建议
+    ///
+    /// 规则未配置 `suggest` 流程时返回 `MissingConfig` 错误
+    pub async fn suggest(&self, keyword: &str) -> Result<Vec<SearchItem>> {
+        let flow = self
+            .runtime_context
+            .rule()
+            .suggest
+            .as_ref()
+            .ok_or_else(|| RuntimeError::MissingConfig {
+                field: "suggest".to_string(),
+            })?;
+        let request = SuggestRequest {
+            keyword: keyword.to_string(),
+        };
+        let mut flow_context = self.flow_context();
+        SuggestFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context).await
+    }
+
+    /// 获取发现页（分类/列表页）
+    ///
+    /// 规则未配置 `discovery` 流程时返回 `MissingConfig` 错误
+    pub async fn discover(&self, request: DiscoveryRequest) -> Result<DiscoveryResponse> {
+        let flow = self
+            .runtime_context
+            .rule()
+            .discovery
+            .as_ref()
+            .ok_or_else(|| RuntimeError::MissingConfig {
+                field: "discovery".to_string(),
+            })?;
+        let mut flow_context = self.flow_context();
+        DiscoveryFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context)
+            .await
+    }
+
     /// 获取详情
     pub async fn detail(&self, url: &str) -> Result<DetailResponse> {
         let request = DetailRequest {
             url: url.to_string(),
         };
         let flow = &self.runtime_context.rule().detail;
-        let mut flow_context = FlowContext::new(self.runtime_context.clone());
+        let mut flow_context = self.flow_context();
         DetailFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context).await
     }
 
+    /// 获取内容（章节正文、图片列表、播放地址等）
+    ///
+    /// 规则未配置 `content` 流程时返回 `MissingConfig` 错误
+    pub async fn content(&self, url: &str) -> Result<ContentResponse> {
+        let flow = self
+            .runtime_context
+            .rule()
+            .content
+            .as_ref()
+            .ok_or_else(|| RuntimeError::MissingConfig {
+                field: "content".to_string(),
+            })?;
+        let request = ContentRequest {
+            url: url.to_string(),
+        };
+        let mut flow_context = self.flow_context();
+        ContentFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context).await
+    }
+
+    /// 登录
+    ///
+    /// 登录得到的 Cookie 与请求头保存在共享的 HTTP 客户端中，之后的请求自动携带。
+    /// 规则未配置 `login` 流程时返回 `MissingConfig` 错误
+    pub async fn login(&self, request: LoginRequest) -> Result<LoginResponse> {
+        let flow = self.runtime_context.rule().login.as_ref().ok_or_else(|| {
+            RuntimeError::MissingConfig {
+                field: "login".to_string(),
+            }
+        })?;
+        let mut flow_context = self.flow_context();
+        LoginFlowExecutor::execute(request, flow, &self.runtime_context, &mut flow_context).await
+    }
+
+    /// 演练：不访问网络，以 `fixture_html` 作为流程首个请求的响应执行提取
+    ///
+    /// 返回规则的静态校验结果与流程输出。未提供样本，或流程需要更多请求时，
+    /// 相应的请求返回 `DryRunNetwork` 错误
+    pub async fn dry_run(&self, flow: DryRunFlow, fixture_html: Option<&str>) -> DryRunReport {
+        let validation = self.warm_up().clone();
+        let mut flow_context = self
+            .flow_context()
+            .with_fixture(fixture_html.map(str::to_string));
+        let output = self.execute_dry_run(flow, &mut flow_context).await;
+        DryRunReport { validation, output }
+    }
+
+    /// 按演练的流程类型分派执行
+    async fn execute_dry_run(
+        &self,
+        flow: DryRunFlow,
+        flow_context: &mut FlowContext,
+    ) -> Result<DryRunOutput> {
+        let runtime_context = &self.runtime_context;
+        let rule = runtime_context.rule();
+        let missing = |field: &str| RuntimeError::MissingConfig {
+            field: field.to_string(),
+        };
+
+        match flow {
+            DryRunFlow::Search(request) => {
+                SearchFlowExecutor::execute(request, &rule.search, runtime_context, flow_context)
+                    .await
+                    .map(DryRunOutput::Search)
+            }
+            DryRunFlow::Suggest(request) => {
+                let flow = rule.suggest.as_ref().ok_or_else(|| missing("suggest"))?;
+                SuggestFlowExecutor::execute(request, flow, runtime_context, flow_context)
+                    .await
+                    .map(DryRunOutput::Suggest)
+            }
+            DryRunFlow::Discovery(request) => {
+                let flow = rule
+                    .discovery
+                    .as_ref()
+                    .ok_or_else(|| missing("discovery"))?;
+                DiscoveryFlowExecutor::execute(request, flow, runtime_context, flow_context)
+                    .await
+                    .map(DryRunOutput::Discovery)
+            }
+            DryRunFlow::Detail(request) => {
+                DetailFlowExecutor::execute(request, &rule.detail, runtime_context, flow_context)
+                    .await
+                    .map(DryRunOutput::Detail)
+            }
+            DryRunFlow::Content(request) => {
+                let flow = rule.content.as_ref().ok_or_else(|| missing("content"))?;
+                ContentFlowExecutor::execute(request, flow, runtime_context, flow_context)
+                    .await
+                    .map(DryRunOutput::Content)
+            }
+        }
+    }
+
     /// 获取运行时上下文
     pub fn runtime_ctx(&self) -> &Arc<RuntimeContext> {
         &self.runtime_context
@@ -71,3 +286,69 @@ impl CrawlerRuntime {
         todo!("实现资源释放逻辑");
     }
 }
+
+/// 预热时遍历提取步骤的访问者：编译选择器与正则写入缓存，加载脚本
+struct WarmUpVisitor<'a> {
+    runtime_context: &'a RuntimeContext,
+    report: &'a mut ValidationReport,
+}
+
+impl WarmUpVisitor<'_> {
+    /// 将步骤的预热错误记录到报告中
+    fn report_error(&mut self, field: String, error: RuntimeError) {
+        self.report.push_error(RuntimeError::InvalidConfigValue {
+            field,
+            reason: error.to_string(),
+        });
+    }
+
+    /// 编译 CSS 选择器（去除末尾伪元素）并写入缓存
+    fn prime_selector(&self, expr: &str) -> Result<()> {
+        let (expr, _) = CssSelectorExecutor::split_pseudo(expr)?;
+        self.runtime_context.css_selector(expr).map(drop)
+    }
+
+    /// 获取选择器表达式
+    fn selector_expr(selector: &SelectorStep) -> &str {
+        match selector {
+            SelectorStep::Simple(expr) | SelectorStep::WithOptions { expr, .. } => expr,
+        }
+    }
+}
+
+impl StepVisitor for WarmUpVisitor<'_> {
+    fn visit_step(&mut self, path: &str, step: &ExtractStep) -> Result<()> {
+        let runtime_context = self.runtime_context;
+        let primed = match step {
+            ExtractStep::Css(selector) => self.prime_selector(Self::selector_expr(selector)),
+            ExtractStep::Xpath(selector) => XpathExecutor::to_css(Self::selector_expr(selector))
+                .and_then(|css| self.prime_selector(&css)),
+            ExtractStep::Regex(
+                RegexStep::Simple(pattern) | RegexStep::WithOptions { pattern, .. },
+            ) => runtime_context.regex(pattern).map(drop),
+            ExtractStep::Condition(condition) => {
+                match ConditionExecutor::match_pattern(condition) {
+                    Some(pattern) => runtime_context.regex(&pattern).map(drop),
+                    None => Ok(()),
+                }
+            }
+            ExtractStep::Script(script) => ScriptExecutor::load_script_code(script).map(|_| {
+                runtime_context.script_engine(ScriptExecutor::language(script));
+            }),
+            _ => Ok(()),
+        };
+        if let Err(e) = primed {
+            self.report_error(path.to_string(), e);
+        }
+        Ok(())
+    }
+
+    fn visit_extractor(&mut self, path: &str, extractor: &FieldExtractor) -> Result<()> {
+        if let Some(pattern) = &extractor.validate
+            && let Err(e) = self.runtime_context.regex(pattern)
+        {
+            self.report_error(format!("{}.validate", path), e);
+        }
+        Ok(())
+    }
+}
@@ -17,6 +17,14 @@ pub enum RuntimeError {
     #[error("组件 '{component}' 未定义")]
     UndefinedComponent { component: String },
 
+    /// 组件已定义但未被引用
+    #[error("组件 '{component}' 已定义但未被引用")]
+    UnusedComponent { component: String },
+
+    /// 流程变量已写入但从未被读取
+    #[error("变量 '{name}' 在 {path} 写入但从未被读取")]
+    UnusedVariable { name: String, path: String },
+
     /// 流程未定义
     #[error("流程 '{flow}' 未定义")]
     UndefinedFlow { flow: String },
@@ -51,6 +59,10 @@ pub enum RuntimeError {
         limit_ms: u64,
     },
 
+    /// 资源使用超出限制
+    #[error("超出资源限制: {limit_type} (限制: {limit})")]
+    ResourceLimitExceeded { limit_type: String, limit: u64 },
+
     // --- HTTP 相关错误 ---
     /// HTTP 配置错误
     #[error("HTTP 配置错误: {0}")]
@@ -60,11 +72,19 @@ pub enum RuntimeError {
     #[error("HTTP 请求错误: {0}")]
     HttpRequest(String),
 
+    /// 演练模式下没有可用的响应样本
+    #[error("演练模式不发起网络请求，缺少响应样本: {url}")]
+    DryRunNetwork { url: String },
+
     // --- 数据提取错误 ---
     /// 数据提取错误
     #[error("数据提取错误: {0}")]
     Extraction(String),
 
+    /// 字段提取结果为空（且没有回退或默认值可用）
+    #[error("数据提取错误: 字段提取结果为空")]
+    EmptyValue,
+
     // --- 配置文件错误 ---
     /// 配置文件错误
     #[error("配置文件错误: {0}")]
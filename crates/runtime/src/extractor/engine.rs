@@ -12,6 +12,9 @@ use crate::{
     },
 };
 use crawler_schema::extract::{ExtractStep, FieldExtractor};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
 use std::sync::Arc;
 
 /// 提取引擎
@@ -25,69 +28,114 @@ impl ExtractEngine {
     ///
     /// 执行 FieldExtractor 定义的提取流程
     /// 所有回退尝试都使用同一个 input 引用，避免多次克隆
+    ///
+    /// 配置了 `validate` 时，不匹配的结果视为该步骤链失败
     pub fn extract_field(
         extractor: &FieldExtractor,
         input: &ExtractValueData,
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> Result<SharedValue> {
+        Self::extract(extractor, input, runtime_context, flow_context, None)
+    }
+
+    /// 提取字段并记录来源
+    ///
+    /// 与 [`extract_field`](Self::extract_field) 结果一致，额外返回每个步骤链的尝试结果，
+    /// 以及最终值来自主步骤链、第几个回退还是默认值。仅用于调试规则
+    pub fn extract_field_traced(
+        extractor: &FieldExtractor,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> (Result<SharedValue>, ExtractionTrace) {
+        let mut trace = ExtractionTrace::default();
+        let result = Self::extract(
+            extractor,
+            input,
+            runtime_context,
+            flow_context,
+            Some(&mut trace),
+        );
+        (result, trace)
+    }
+
+    /// 提取流程实现，`trace` 为 `None` 时不做任何记录
+    fn extract(
+        extractor: &FieldExtractor,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        mut trace: Option<&mut ExtractionTrace>,
+    ) -> Result<SharedValue> {
+        // 校验正则在规则加载时已检查语法，这里从运行时上下文的缓存中取用
+        let validate = extractor
+            .validate
+            .as_deref()
+            .map(|pattern| runtime_context.regex(pattern))
+            .transpose()?;
+
+        let run = |steps: &[ExtractStep]| -> Result<SharedValue> {
+            let value = Self::execute_steps(steps, input, runtime_context, flow_context)?;
+            if let Some(re) = &validate
+                && !value.is_empty()
+                && !Self::matches_pattern(&value, re)
+            {
+                return Err(RuntimeError::Extraction(format!(
+                    "Extracted value does not match validate pattern '{}'",
+                    re.as_str()
+                )));
+            }
+            Ok(value)
+        };
+
         // 执行主步骤链
-        match Self::execute_steps(&extractor.steps, input, runtime_context, flow_context) {
-            Ok(value) => {
-                // 检查是否为空
-                if value.is_empty() && !extractor.nullable {
-                    // 尝试回退（仍然使用 input 的引用，无克隆）
-                    if let Some(fallback) = &extractor.fallback {
-                        for fallback_steps in fallback {
-                            if let Ok(fallback_value) = Self::execute_steps(
-                                fallback_steps,
-                                input,
-                                runtime_context,
-                                flow_context,
-                            ) && !fallback_value.is_empty()
-                            {
-                                return Ok(fallback_value);
-                            }
-                        }
-                    }
-
-                    // 使用默认值
-                    if let Some(default) = &extractor.default {
-                        return Ok(Arc::new(ExtractValueData::from_json(default)));
-                    }
-
-                    // 如果不允许空值，返回错误
-                    return Err(RuntimeError::Extraction(
-                        "Field extraction returned empty value".to_string(),
-                    ));
-                }
-
-                Ok(value)
+        let result = run(&extractor.steps);
+        ExtractionTrace::record(&mut trace, FieldSource::Primary, &result);
+        let primary_error = match result {
+            // 非空值，或允许空值时直接返回
+            Ok(value) if !value.is_empty() || extractor.nullable => {
+                ExtractionTrace::resolve(&mut trace, FieldSource::Primary);
+                return Ok(value);
             }
-            Err(e) => {
-                // 尝试回退
-                if let Some(fallback) = &extractor.fallback {
-                    for fallback_steps in fallback {
-                        if let Ok(fallback_value) = Self::execute_steps(
-                            fallback_steps,
-                            input,
-                            runtime_context,
-                            flow_context,
-                        ) && !fallback_value.is_empty()
-                        {
-                            return Ok(fallback_value);
-                        }
-                    }
-                }
-
-                // 使用默认值
-                if let Some(default) = &extractor.default {
-                    return Ok(Arc::new(ExtractValueData::from_json(default)));
-                }
-
-                Err(e)
+            Ok(_) => None,
+            Err(e) => Some(e),
+        };
+
+        // 尝试回退（仍然使用 input 的引用，无克隆）
+        for (index, fallback_steps) in extractor.fallback.iter().flatten().enumerate() {
+            let source = FieldSource::Fallback(index);
+            let result = run(fallback_steps);
+            ExtractionTrace::record(&mut trace, source, &result);
+            if let Ok(fallback_value) = result
+                && !fallback_value.is_empty()
+            {
+                ExtractionTrace::resolve(&mut trace, source);
+                return Ok(fallback_value);
             }
         }
+
+        // 使用默认值
+        if let Some(default) = &extractor.default {
+            ExtractionTrace::resolve(&mut trace, FieldSource::Default);
+            return Ok(Arc::new(ExtractValueData::from_json(default)));
+        }
+
+        // 主步骤链出错时返回其错误，否则报告空值
+        Err(primary_error.unwrap_or(RuntimeError::EmptyValue))
+    }
+
+    /// 检查提取结果是否匹配校验正则（数组要求每个元素都匹配）
+    fn matches_pattern(value: &ExtractValueData, re: &Regex) -> bool {
+        match value {
+            ExtractValueData::String(s) | ExtractValueData::Html(s) => re.is_match(s),
+            ExtractValueData::Array(items) => items.iter().all(|v| Self::matches_pattern(v, re)),
+            ExtractValueData::Json(v) => match v.as_ref() {
+                Value::String(s) => re.is_match(s),
+                other => re.is_match(&other.to_string()),
+            },
+            ExtractValueData::Null => false,
+        }
     }
 
     /// 执行步骤链
@@ -107,3 +155,69 @@ impl ExtractEngine {
         Ok(current)
     }
 }
+
+/// 字段值来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldSource {
+    /// 主步骤链
+    Primary,
+    /// 回退步骤链（`fallback` 中的下标）
+    Fallback(usize),
+    /// 默认值
+    Default,
+}
+
+/// 单个步骤链的执行结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptOutcome {
+    /// 得到非空值
+    Value,
+    /// 得到空值
+    Empty,
+    /// 执行出错或未通过校验
+    Failed(String),
+}
+
+/// 步骤链尝试记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChainAttempt {
+    /// 步骤链
+    pub source: FieldSource,
+    /// 执行结果
+    pub outcome: AttemptOutcome,
+}
+
+/// 字段提取追踪
+///
+/// 由 [`ExtractEngine::extract_field_traced`] 返回，按执行顺序记录每个步骤链的尝试
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ExtractionTrace {
+    /// 最终值的来源，提取失败时为 `None`
+    pub source: Option<FieldSource>,
+    /// 已执行的步骤链（默认值不计入）
+    pub attempts: Vec<ChainAttempt>,
+}
+
+impl ExtractionTrace {
+    /// 记录一次步骤链执行（未开启追踪时为空操作）
+    fn record(trace: &mut Option<&mut Self>, source: FieldSource, result: &Result<SharedValue>) {
+        let Some(trace) = trace else {
+            return;
+        };
+        let outcome = match result {
+            Ok(value) if value.is_empty() => AttemptOutcome::Empty,
+            Ok(_) => AttemptOutcome::Value,
+            Err(e) => AttemptOutcome::Failed(e.to_string()),
+        };
+        trace.attempts.push(ChainAttempt { source, outcome });
+    }
+
+    /// 记录最终值的来源（未开启追踪时为空操作）
+    fn resolve(trace: &mut Option<&mut Self>, source: FieldSource) {
+        if let Some(trace) = trace {
+            trace.source = Some(source);
+        }
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
     context::{FlowContext, RuntimeContext},
     extractor::value::{ExtractValueData, SharedValue},
 };
-use crawler_schema::extract::ExtractStep;
+use crawler_schema::extract::{AttrStep, ExtractStep};
 
 /// 步骤执行器工厂
 ///
@@ -16,11 +16,26 @@ pub struct StepExecutorFactory;
 
 impl StepExecutorFactory {
     /// 直接执行步骤
+    ///
+    /// 执行前后检查流程的资源限制（见 [`FlowLimits`](crate::context::FlowLimits)）
     pub fn execute(
         step: &ExtractStep,
         input: &ExtractValueData,
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        flow_context.check_step_limits()?;
+        let output = Self::dispatch(step, input, runtime_context, flow_context)?;
+        flow_context.check_step_output(&output)?;
+        Ok(output)
+    }
+
+    /// 分派到具体的步骤执行器
+    fn dispatch(
+        step: &ExtractStep,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
     ) -> Result<SharedValue> {
         match step {
             ExtractStep::Css(selector) => {
@@ -39,6 +54,14 @@ impl StepExecutorFactory {
                     flow_context,
                 )
             }
+            ExtractStep::JsonPointer(pointer) => {
+                crate::extractor::selector::json::JsonPointerExecutor::execute(
+                    pointer,
+                    input,
+                    runtime_context,
+                    flow_context,
+                )
+            }
             ExtractStep::Regex(regex) => {
                 crate::extractor::selector::regex::RegexSelectorExecutor::execute(
                     regex,
@@ -55,12 +78,22 @@ impl StepExecutorFactory {
                     flow_context,
                 )
             }
-            ExtractStep::Attr(attr) => crate::extractor::selector::attr::AttrExecutor::execute(
-                attr,
-                input,
-                runtime_context,
-                flow_context,
-            ),
+            ExtractStep::Attr(attr) => match attr {
+                AttrStep::Single(name) => crate::extractor::selector::attr::AttrExecutor::execute(
+                    name,
+                    input,
+                    runtime_context,
+                    flow_context,
+                ),
+                AttrStep::Multiple(names) => {
+                    crate::extractor::selector::attr::AttrExecutor::execute_multiple(
+                        names,
+                        input,
+                        runtime_context,
+                        flow_context,
+                    )
+                }
+            },
             ExtractStep::Index(index) => crate::extractor::selector::index::IndexExecutor::execute(
                 index,
                 input,
@@ -86,14 +119,24 @@ impl StepExecutorFactory {
                     flow_context,
                 )
             }
-            ExtractStep::Xpath(_selector) => {
-                // XPath 需要 JS 环境，暂不支持
-                Err(crate::error::RuntimeError::Extraction(
-                    "XPath not supported in this context".into(),
-                ))
+            ExtractStep::Coalesce(inputs) => {
+                crate::extractor::selector::coalesce::CoalesceExecutor::execute(
+                    inputs,
+                    input,
+                    runtime_context,
+                    flow_context,
+                )
             }
-            ExtractStep::Map(steps) => crate::extractor::selector::map::MapExecutor::execute(
-                steps,
+            ExtractStep::Xpath(selector) => {
+                crate::extractor::selector::xpath::XpathExecutor::execute(
+                    selector,
+                    input,
+                    runtime_context,
+                    flow_context,
+                )
+            }
+            ExtractStep::Map(map) => crate::extractor::selector::map::MapExecutor::execute(
+                map,
                 input,
                 runtime_context,
                 flow_context,
@@ -106,6 +149,14 @@ impl StepExecutorFactory {
                     flow_context,
                 )
             }
+            ExtractStep::Merge(fields) => {
+                crate::extractor::selector::merge::MergeExecutor::execute(
+                    fields,
+                    input,
+                    runtime_context,
+                    flow_context,
+                )
+            }
         }
     }
 }
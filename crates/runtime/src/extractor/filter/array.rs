@@ -1,5 +1,13 @@
 //! # 数组处理过滤器
 
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::{SharedValue, filter::Filter, value::ExtractValueData},
+};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
 // TODO: 实现数组相关过滤器
 // - first
 // - last
@@ -8,4 +16,105 @@
 // - unique
 // - sort
 // - flatten
-// - length
+
+/// Length 过滤器
+///
+/// 字符串按字符计数，数组按元素计数，JSON 对象按键计数，空值为 0
+pub struct LengthFilter;
+
+impl Filter for LengthFilter {
+    fn apply(&self, input: &SharedValue, _args: &[Value]) -> Result<SharedValue> {
+        let length = match input.as_ref() {
+            ExtractValueData::String(s) | ExtractValueData::Html(s) => s.chars().count(),
+            ExtractValueData::Array(arr) => arr.len(),
+            ExtractValueData::Null => 0,
+            ExtractValueData::Json(json) => match json.as_ref() {
+                Value::String(s) => s.chars().count(),
+                Value::Array(arr) => arr.len(),
+                Value::Object(obj) => obj.len(),
+                Value::Null => 0,
+                other => {
+                    return Err(RuntimeError::Extraction(format!(
+                        "length filter does not support value: {}",
+                        other
+                    )));
+                }
+            },
+        };
+        Ok(Arc::new(ExtractValueData::Json(Arc::new(Value::from(
+            length,
+        )))))
+    }
+}
+
+/// Zip 过滤器
+///
+/// 将输入数组与另一个数组按位置配对。
+/// 参数: [other, key_a?, key_b?, strict?]
+///
+/// - `other`：另一个数组；在提取步骤中写变量名，由过滤器执行器解析为变量值
+/// - `key_a`、`key_b`：提供时输出 `{ key_a: a, key_b: b }` 对象，否则输出 `[a, b]`
+/// - `strict`：为 `true`（或字符串 `"strict"`）时长度不一致报错，默认截断为较短的长度
+pub struct ZipFilter;
+
+impl Filter for ZipFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["other", "key_a", "key_b", "strict"]
+    }
+
+    fn var_params(&self) -> &'static [usize] {
+        &[0]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let left = input.as_array_slice().ok_or_else(|| {
+            RuntimeError::Extraction("zip filter requires array input".to_string())
+        })?;
+        let right = match args.first() {
+            Some(Value::Array(arr)) => arr,
+            Some(other) => {
+                return Err(RuntimeError::Extraction(format!(
+                    "zip filter requires an array argument, got: {}",
+                    other
+                )));
+            }
+            None => {
+                return Err(RuntimeError::Extraction(
+                    "zip filter requires an array argument".to_string(),
+                ));
+            }
+        };
+
+        let options: Vec<&str> = args[1..].iter().filter_map(Value::as_str).collect();
+        let strict = options.contains(&"strict") || args.get(3) == Some(&Value::Bool(true));
+        let keys: Vec<&str> = options.into_iter().filter(|s| *s != "strict").collect();
+
+        if strict && left.len() != right.len() {
+            return Err(RuntimeError::Extraction(format!(
+                "zip filter length mismatch: {} vs {}",
+                left.len(),
+                right.len()
+            )));
+        }
+
+        let pairs: Vec<SharedValue> = left
+            .iter()
+            .zip(right)
+            .map(|(a, b)| {
+                let a = a.to_owned_json();
+                let pair = match keys.as_slice() {
+                    [key_a, key_b] => {
+                        let mut object = Map::with_capacity(2);
+                        object.insert(key_a.to_string(), a);
+                        object.insert(key_b.to_string(), b.clone());
+                        Value::Object(object)
+                    }
+                    _ => Value::Array(vec![a, b.clone()]),
+                };
+                Arc::new(ExtractValueData::Json(Arc::new(pair)))
+            })
+            .collect();
+
+        Ok(Arc::new(ExtractValueData::Array(Arc::new(pairs))))
+    }
+}
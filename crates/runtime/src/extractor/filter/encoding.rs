@@ -1,8 +1,84 @@
 //! # 编码处理过滤器
 
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::{SharedValue, filter::Filter, value::ExtractValueData},
+};
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::Value;
+use std::sync::Arc;
+
 // TODO: 实现编码相关过滤器
 // - base64_encode
 // - base64_decode
 // - html_encode
-// - html_decode
-// - md5
+
+/// HtmlDecode 过滤器
+/// 解码 HTML 实体（命名实体与 `&#39;`、`&#x27;` 等数字实体）
+pub struct HtmlDecodeFilter;
+
+impl Filter for HtmlDecodeFilter {
+    fn apply(&self, input: &SharedValue, _args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("html_decode filter requires string input".to_string())
+        })?;
+        let decoded = html_escape::decode_html_entities(s);
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            decoded.into_owned().into_boxed_str(),
+        ))))
+    }
+}
+
+/// Hash 过滤器
+/// 参数: [algorithm, encoding?]
+///
+/// - `algorithm`：`md5`、`sha1`、`sha256`
+/// - `encoding`：`hex`（默认，小写）或 `base64`
+pub struct HashFilter;
+
+impl Filter for HashFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["algorithm", "encoding"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        use sha1::Sha1;
+        use sha2::{Digest, Sha256};
+
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("hash filter requires string input".to_string())
+        })?;
+        let algorithm = args.first().and_then(|v| v.as_str()).ok_or_else(|| {
+            RuntimeError::Extraction("hash filter requires an algorithm argument".to_string())
+        })?;
+        let encoding = args.get(1).and_then(|v| v.as_str()).unwrap_or("hex");
+
+        let digest: Vec<u8> = match algorithm.to_ascii_lowercase().as_str() {
+            "md5" => md5::compute(s.as_bytes()).0.to_vec(),
+            "sha1" => Sha1::digest(s.as_bytes()).to_vec(),
+            "sha256" => Sha256::digest(s.as_bytes()).to_vec(),
+            other => {
+                return Err(RuntimeError::Extraction(format!(
+                    "hash filter: unsupported algorithm '{}' (expected md5, sha1 or sha256)",
+                    other
+                )));
+            }
+        };
+
+        let encoded = match encoding {
+            "hex" => digest.iter().map(|b| format!("{:02x}", b)).collect(),
+            "base64" => general_purpose::STANDARD.encode(&digest),
+            other => {
+                return Err(RuntimeError::Extraction(format!(
+                    "hash filter: unsupported encoding '{}' (expected hex or base64)",
+                    other
+                )));
+            }
+        };
+
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            encoded.into_boxed_str(),
+        ))))
+    }
+}
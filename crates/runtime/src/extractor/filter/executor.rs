@@ -9,6 +9,9 @@ use crawler_schema::extract::FilterStep;
 use serde_json::Value;
 use std::sync::Arc;
 
+/// 流程变量：最终页面 URL（重定向之后），由流程执行器在收到响应后写入
+pub const FINAL_URL_VAR: &str = "_final_url";
+
 /// 过滤器执行器
 pub struct FilterExecutor;
 
@@ -39,27 +42,81 @@ impl FilterExecutor {
         filters
     }
 
+    /// 过滤器参数中引用的流程变量路径（见
+    /// [`Filter::var_params`](crate::extractor::filter::registry::Filter::var_params)）
+    pub fn variable_args(filter: &FilterStep) -> Vec<String> {
+        let calls = match filter {
+            FilterStep::Pipeline(pipeline) => Self::parse_pipeline(pipeline),
+            FilterStep::List(filters) => filters
+                .iter()
+                .map(|filter| (filter.name.clone(), filter.args.clone().unwrap_or_default()))
+                .collect(),
+        };
+        let registry = global_registry();
+        calls
+            .into_iter()
+            .flat_map(|(name, args)| {
+                let positions = registry.get(&name).map_or(&[][..], |f| f.var_params());
+                positions
+                    .iter()
+                    .filter_map(|&i| match args.get(i) {
+                        Some(Value::String(path)) => Some(path.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
     /// 执行过滤器
     pub fn execute(
         filter: &FilterStep,
         input: &ExtractValueData,
-        _runtime_context: &RuntimeContext,
-        _flow_context: &FlowContext,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
     ) -> Result<SharedValue> {
         let registry = global_registry();
+        // absolute_url 未提供参数时，使用重定向后的页面 URL 作为基准
+        let default_base = || -> Vec<Value> {
+            flow_context
+                .resolve(FINAL_URL_VAR)
+                .cloned()
+                .or_else(|| runtime_context.globals().get("base_url").cloned())
+                .into_iter()
+                .collect()
+        };
+        // 变量路径参数（如 zip 的第一个参数 `tags`、`item.tags`）解析为其中保存的值
+        let resolve_vars = |name: &str, args: &mut Vec<Value>| -> Result<()> {
+            let positions = registry.get(name).map_or(&[][..], |f| f.var_params());
+            for &i in positions {
+                if let Some(Value::String(var)) = args.get(i) {
+                    args[i] = flow_context.resolve_path(var)?.clone();
+                }
+            }
+            Ok(())
+        };
         let mut current = Arc::new(input.clone());
 
         match filter {
             FilterStep::Pipeline(pipeline) => {
                 let filters = Self::parse_pipeline(pipeline);
-                for (name, args) in filters {
+                for (name, mut args) in filters {
+                    if name == "absolute_url" && args.is_empty() {
+                        args = default_base();
+                    }
+                    resolve_vars(&name, &mut args)?;
                     current = registry.apply(&name, current, &args)?;
                 }
             }
             FilterStep::List(filters) => {
                 for filter_config in filters {
-                    let args = filter_config.args.as_deref().unwrap_or(&[]);
-                    current = registry.apply(&filter_config.name, current, args)?;
+                    let mut args = match filter_config.args.as_deref() {
+                        Some(args) if !args.is_empty() => args.to_vec(),
+                        _ if filter_config.name == "absolute_url" => default_base(),
+                        _ => Vec::new(),
+                    };
+                    resolve_vars(&filter_config.name, &mut args)?;
+                    current = registry.apply(&filter_config.name, current, &args)?;
                 }
             }
         }
@@ -0,0 +1,183 @@
+//! # 本地化过滤器
+//!
+//! 处理中文站点常见的文本格式，如中文数字
+
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::{SharedValue, filter::Filter, value::ExtractValueData},
+};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// CnNum 过滤器
+/// 将字符串中的中文数字转换为阿拉伯数字，如 "第三十二章" → "第32章"
+/// 参数: [only_first]（可选，为 true 时仅转换第一段数字）
+pub struct CnNumFilter;
+
+impl Filter for CnNumFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["only_first"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("cn_num filter requires string input".to_string())
+        })?;
+        let only_first = args.first().and_then(|v| v.as_bool()).unwrap_or(false);
+
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            convert_cn_numerals(s, only_first).into_boxed_str(),
+        ))))
+    }
+}
+
+/// 数字后出现时表示数量或序号的量词（"三十二章"、"二〇二四年"）
+const QUANTITY_SUFFIXES: &[char] = &[
+    '章', '节', '回', '卷', '集', '季', '话', '篇', '册', '页', '期', '年', '月', '日', '号', '届',
+    '楼', '层', '级', '幕', '岁',
+];
+
+/// 替换字符串中的中文数字段
+///
+/// 只转换处于数字语境中的片段，避免 "一个"、"万一"、"十全十美" 等普通词语被改写：
+/// - 前面是 "第"（"第三十二章"）
+/// - 后面是量词（"三十二章"、"二〇二四年"，见 [`QUANTITY_SUFFIXES`]）
+/// - 片段自成一个词，前后不与其他汉字相连（"卷 三十二"、整个字符串就是 "一百零五"）
+///
+/// 不符合中文数字写法的片段（如仅由单位组成的 "万"、单位在前的 "万一"）保持原样
+pub fn convert_cn_numerals(s: &str, only_first: bool) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut result = String::with_capacity(s.len());
+    let mut converted = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        if cn_digit(chars[i]).is_none() && cn_unit(chars[i]).is_none() {
+            result.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() && (cn_digit(chars[i]).is_some() || cn_unit(chars[i]).is_some()) {
+            i += 1;
+        }
+        let run: String = chars[start..i].iter().collect();
+        let prev = start.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i).copied();
+
+        let numeric_context = prev == Some('第')
+            || next.is_some_and(|c| QUANTITY_SUFFIXES.contains(&c))
+            || (!prev.is_some_and(is_cjk) && !next.is_some_and(is_cjk));
+        let parsed = if numeric_context && !(only_first && converted) {
+            parse_cn_number(&run)
+        } else {
+            None
+        };
+        match parsed {
+            Some(n) => {
+                result.push_str(&n.to_string());
+                converted = true;
+            }
+            None => result.push_str(&run),
+        }
+    }
+
+    result
+}
+
+/// 是否为汉字（CJK 统一表意文字）
+fn is_cjk(c: char) -> bool {
+    matches!(c, '\u{4e00}'..='\u{9fff}' | '\u{3400}'..='\u{4dbf}')
+}
+
+/// 解析一段中文数字，如 "一百零五" → 105、"二零二四" → 2024
+fn parse_cn_number(run: &str) -> Option<u64> {
+    let chars: Vec<char> = run.chars().collect();
+
+    // 必须包含数字，或以 "十" 开头（"十二"）
+    if !chars.iter().any(|c| cn_digit(*c).is_some()) && chars.first() != Some(&'十') {
+        return None;
+    }
+
+    // 无单位时按逐位读法处理（"二零二四"）
+    if chars.iter().all(|c| cn_digit(*c).is_some()) {
+        return chars.iter().try_fold(0u64, |acc, c| {
+            acc.checked_mul(10)?.checked_add(cn_digit(*c)?)
+        });
+    }
+
+    let mut total = 0u64;
+    let mut section = 0u64;
+    let mut number = 0u64;
+    let mut prev: Option<char> = None;
+
+    for (i, c) in chars.into_iter().enumerate() {
+        if let Some(d) = cn_digit(c) {
+            number = d;
+            prev = Some(c);
+            continue;
+        }
+        let unit = cn_unit(c)?;
+        // 单位须跟在数字之后（开头的 "十" 除外）；"万"、"亿" 也可跟在单位之后（"十万"）
+        let after_digit = prev.is_some_and(|p| cn_digit(p).is_some());
+        let valid = match unit {
+            10 if i == 0 => true,
+            10 | 100 | 1000 => after_digit,
+            _ => prev.is_some(),
+        };
+        if !valid {
+            return None;
+        }
+        prev = Some(c);
+        match unit {
+            10 | 100 | 1000 => {
+                // "十二" 中省略的 "一"
+                let n = if number == 0 && unit == 10 { 1 } else { number };
+                section = section.checked_add(n.checked_mul(unit)?)?;
+            }
+            10_000 => {
+                total = total.checked_add(section.checked_add(number)?.checked_mul(unit)?)?;
+                section = 0;
+            }
+            _ => {
+                total = total
+                    .checked_add(section)?
+                    .checked_add(number)?
+                    .checked_mul(unit)?;
+                section = 0;
+            }
+        }
+        number = 0;
+    }
+
+    total.checked_add(section)?.checked_add(number)
+}
+
+fn cn_digit(c: char) -> Option<u64> {
+    Some(match c {
+        '零' | '〇' => 0,
+        '一' => 1,
+        '二' | '两' => 2,
+        '三' => 3,
+        '四' => 4,
+        '五' => 5,
+        '六' => 6,
+        '七' => 7,
+        '八' => 8,
+        '九' => 9,
+        _ => return None,
+    })
+}
+
+fn cn_unit(c: char) -> Option<u64> {
+    Some(match c {
+        '十' => 10,
+        '百' => 100,
+        '千' => 1000,
+        '万' => 10_000,
+        '亿' => 100_000_000,
+        _ => return None,
+    })
+}
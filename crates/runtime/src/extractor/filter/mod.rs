@@ -6,6 +6,9 @@ pub mod array;
 pub mod convert;
 pub mod encoding;
 pub mod executor;
+pub mod locale;
+pub mod number;
+pub mod predicate;
 pub mod registry;
 pub mod string;
 pub mod url;
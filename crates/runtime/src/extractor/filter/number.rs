@@ -0,0 +1,160 @@
+//! # 数值过滤器
+//!
+//! 输入与参数可以是 JSON 数字或可解析为数字的字符串（忽略首尾空白）。
+//! 结果为 `Json(Number)`：值为整数时输出整数，否则输出浮点数。
+
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::{SharedValue, filter::Filter, value::ExtractValueData},
+};
+use serde_json::{Number, Value};
+use std::sync::Arc;
+
+/// 可精确表示为整数的最大绝对值（2^53）
+const MAX_EXACT_INT: f64 = 9_007_199_254_740_992.0;
+
+/// 将 JSON 值解析为数字
+fn parse_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// 读取数值输入
+fn input_number(name: &str, input: &SharedValue) -> Result<f64> {
+    let parsed = match input.as_ref() {
+        ExtractValueData::Json(v) => parse_number(v),
+        other => other.as_str().and_then(|s| s.trim().parse().ok()),
+    };
+    parsed
+        .ok_or_else(|| RuntimeError::Extraction(format!("{} filter requires numeric input", name)))
+}
+
+/// 读取第 `index` 个数值参数
+fn arg_number(name: &str, args: &[Value], index: usize) -> Result<f64> {
+    args.get(index).and_then(parse_number).ok_or_else(|| {
+        RuntimeError::Extraction(format!("{} filter requires a numeric operand", name))
+    })
+}
+
+/// 包装数值结果，整数值输出为整数
+fn number_value(name: &str, value: f64) -> Result<SharedValue> {
+    let number = if value.fract() == 0.0 && value.abs() <= MAX_EXACT_INT {
+        Number::from(value as i64)
+    } else {
+        Number::from_f64(value).ok_or_else(|| {
+            RuntimeError::Extraction(format!("{} filter produced a non-finite number", name))
+        })?
+    };
+    Ok(Arc::new(ExtractValueData::Json(Arc::new(Value::Number(
+        number,
+    )))))
+}
+
+/// 读取输入与操作数（加减乘除共用）
+fn operands(name: &str, input: &SharedValue, args: &[Value]) -> Result<(f64, f64)> {
+    Ok((input_number(name, input)?, arg_number(name, args, 0)?))
+}
+
+/// Add 过滤器
+/// 参数: [operand]
+pub struct AddFilter;
+
+impl Filter for AddFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["operand"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (a, b) = operands("add", input, args)?;
+        number_value("add", a + b)
+    }
+}
+
+/// Sub 过滤器
+/// 参数: [operand]
+pub struct SubFilter;
+
+impl Filter for SubFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["operand"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (a, b) = operands("sub", input, args)?;
+        number_value("sub", a - b)
+    }
+}
+
+/// Mul 过滤器
+/// 参数: [operand]
+pub struct MulFilter;
+
+impl Filter for MulFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["operand"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (a, b) = operands("mul", input, args)?;
+        number_value("mul", a * b)
+    }
+}
+
+/// Div 过滤器
+/// 参数: [divisor]，除数为 0 时报错
+pub struct DivFilter;
+
+impl Filter for DivFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["operand"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (dividend, divisor) = operands("div", input, args)?;
+        if divisor == 0.0 {
+            return Err(RuntimeError::Extraction(
+                "div filter: division by zero".to_string(),
+            ));
+        }
+        number_value("div", dividend / divisor)
+    }
+}
+
+/// Round 过滤器
+/// 参数: [precision?]，保留的小数位数（默认 0）
+pub struct RoundFilter;
+
+impl Filter for RoundFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["precision"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let value = input_number("round", input)?;
+        let precision = match args.first() {
+            Some(arg) => parse_number(arg)
+                .filter(|p| *p >= 0.0 && p.fract() == 0.0)
+                .ok_or_else(|| {
+                    RuntimeError::Extraction(
+                        "round filter: 'precision' must be a non-negative integer".to_string(),
+                    )
+                })? as i32,
+            None => 0,
+        };
+        let factor = 10f64.powi(precision);
+        number_value("round", (value * factor).round() / factor)
+    }
+}
+
+/// Abs 过滤器
+pub struct AbsFilter;
+
+impl Filter for AbsFilter {
+    fn apply(&self, input: &SharedValue, _args: &[Value]) -> Result<SharedValue> {
+        number_value("abs", input_number("abs", input)?.abs())
+    }
+}
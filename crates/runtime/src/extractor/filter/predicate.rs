@@ -0,0 +1,101 @@
+//! # 判断过滤器
+//!
+//! 返回 `Json(Bool)`，供 `condition` 步骤与模板条件使用
+
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::{SharedValue, filter::Filter, value::ExtractValueData},
+};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// 包装布尔结果
+fn bool_value(value: bool) -> SharedValue {
+    Arc::new(ExtractValueData::Json(Arc::new(Value::Bool(value))))
+}
+
+/// 读取字符串输入与单个参数
+fn string_and_pattern<'a>(
+    name: &str,
+    input: &'a SharedValue,
+    args: &'a [Value],
+) -> Result<(&'a str, &'a str)> {
+    let s = input.as_str().ok_or_else(|| {
+        RuntimeError::Extraction(format!("{} filter requires string input", name))
+    })?;
+    let pattern = args.first().and_then(|v| v.as_str()).ok_or_else(|| {
+        RuntimeError::Extraction(format!("{} filter requires 1 string argument", name))
+    })?;
+    Ok((s, pattern))
+}
+
+/// Contains 过滤器
+/// 参数: [needle]
+///
+/// 字符串输入判断是否包含子串；数组输入判断是否有元素与参数相等（按 JSON 值比较）
+pub struct ContainsFilter;
+
+impl Filter for ContainsFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["needle"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let needle = args.first().ok_or_else(|| {
+            RuntimeError::Extraction("contains filter requires 1 argument".to_string())
+        })?;
+
+        let found = match input.as_ref() {
+            ExtractValueData::Array(items) => {
+                items.iter().any(|item| &item.to_owned_json() == needle)
+            }
+            ExtractValueData::Json(json) => match json.as_ref() {
+                Value::Array(items) => items.contains(needle),
+                Value::String(s) => needle.as_str().is_some_and(|n| s.contains(n)),
+                _ => {
+                    return Err(RuntimeError::Extraction(
+                        "contains filter requires string or array input".to_string(),
+                    ));
+                }
+            },
+            ExtractValueData::Null => false,
+            other => {
+                let s = other.as_str().unwrap_or_default();
+                needle.as_str().is_some_and(|n| s.contains(n))
+            }
+        };
+
+        Ok(bool_value(found))
+    }
+}
+
+/// StartsWith 过滤器
+/// 参数: [prefix]
+pub struct StartsWithFilter;
+
+impl Filter for StartsWithFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["prefix"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (s, prefix) = string_and_pattern("starts_with", input, args)?;
+        Ok(bool_value(s.starts_with(prefix)))
+    }
+}
+
+/// EndsWith 过滤器
+/// 参数: [suffix]
+pub struct EndsWithFilter;
+
+impl Filter for EndsWithFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["suffix"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (s, suffix) = string_and_pattern("ends_with", input, args)?;
+        Ok(bool_value(s.ends_with(suffix)))
+    }
+}
@@ -10,6 +10,22 @@ use std::{collections::HashMap, sync::Arc};
 pub trait Filter: Send + Sync {
     /// 应用过滤器
     fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue>;
+
+    /// 按位置顺序排列的参数名称
+    ///
+    /// 模板中以命名参数调用过滤器时（如 `{{ s | truncate(max_chars=10) }}`），
+    /// 按此顺序转换为位置参数；无参数的过滤器返回空列表
+    fn params(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// 以变量路径传入的参数位置
+    ///
+    /// 这些位置上的字符串参数（如 `tags`、`item.tags`）在调用前由过滤器执行器
+    /// 解析为流程变量的值；规则校验也据此统计变量引用
+    fn var_params(&self) -> &'static [usize] {
+        &[]
+    }
 }
 
 /// 过滤器注册表（全局单例）
@@ -40,6 +56,11 @@ impl FilterRegistry {
         self.filters.get(name).cloned()
     }
 
+    /// 已注册的过滤器名称
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.filters.keys().map(String::as_str)
+    }
+
     /// 应用过滤器
     ///
     /// 接受输入值的所有权，内部使用引用传递给过滤器
@@ -53,23 +74,66 @@ impl FilterRegistry {
 
     /// 注册所有内置过滤器
     fn register_builtin_filters(&mut self) {
-        use crate::extractor::filter::{convert, string, url};
+        use crate::extractor::filter::{
+            array,
+            convert,
+            encoding,
+            locale,
+            number,
+            predicate,
+            string,
+            url,
+        };
 
         // 字符串过滤器
         self.register("trim", string::TrimFilter);
         self.register("lower", string::LowerFilter);
         self.register("upper", string::UpperFilter);
+        self.register("capitalize", string::CapitalizeFilter);
+        self.register("title_case", string::TitleCaseFilter);
         self.register("replace", string::ReplaceFilter);
         self.register("regex_replace", string::RegexReplaceFilter);
+        self.register("match_all", string::MatchAllFilter);
         self.register("split", string::SplitFilter);
         self.register("join", string::JoinFilter);
         self.register("strip_html", string::StripHtmlFilter);
+        self.register("normalize_whitespace", string::NormalizeWhitespaceFilter);
+        self.register("paragraphs", string::ParagraphsFilter);
         self.register("substring", string::SubstringFilter);
+        self.register("truncate", string::TruncateFilter);
+        self.register("trim_prefix", string::TrimPrefixFilter);
+        self.register("trim_suffix", string::TrimSuffixFilter);
+        self.register("prepend", string::PrependFilter);
+        self.register("append", string::AppendFilter);
+
+        // 数组过滤器
+        self.register("length", array::LengthFilter);
+        self.register("zip", array::ZipFilter);
 
         // 类型转换过滤器
         self.register("to_int", convert::ToIntFilter);
         self.register("to_string", convert::ToStringFilter);
 
+        // 数值过滤器
+        self.register("add", number::AddFilter);
+        self.register("sub", number::SubFilter);
+        self.register("mul", number::MulFilter);
+        self.register("div", number::DivFilter);
+        self.register("round", number::RoundFilter);
+        self.register("abs", number::AbsFilter);
+
+        // 判断过滤器
+        self.register("contains", predicate::ContainsFilter);
+        self.register("starts_with", predicate::StartsWithFilter);
+        self.register("ends_with", predicate::EndsWithFilter);
+
+        // 编码过滤器
+        self.register("html_decode", encoding::HtmlDecodeFilter);
+        self.register("hash", encoding::HashFilter);
+
+        // 本地化过滤器
+        self.register("cn_num", locale::CnNumFilter);
+
         // URL 过滤器
         self.register("absolute_url", url::AbsoluteUrlFilter);
         self.register("url_encode", url::UrlEncodeFilter);
@@ -5,8 +5,17 @@ use crate::{
     error::RuntimeError,
     extractor::{SharedValue, filter::Filter, value::ExtractValueData},
 };
+use regex::Regex;
 use serde_json::Value;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// 段落分隔标签：`<br>`、`<p>`、`<div>` 的开始与结束标签
+static PARAGRAPH_BREAK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)<br\s*/?>|</?(?:p|div)(?:\s[^>]*)?>").expect("valid regex"));
+
+/// HTML 标签
+static HTML_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]+>").expect("valid regex"));
 
 /// Trim 过滤器
 pub struct TrimFilter;
@@ -50,11 +59,64 @@ impl Filter for UpperFilter {
     }
 }
 
+/// Capitalize 过滤器
+///
+/// 首字母大写，其余字符保持不变
+pub struct CapitalizeFilter;
+
+impl Filter for CapitalizeFilter {
+    fn apply(&self, input: &SharedValue, _args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("capitalize filter requires string input".to_string())
+        })?;
+        let mut chars = s.chars();
+        let capitalized = match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        };
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            capitalized.into_boxed_str(),
+        ))))
+    }
+}
+
+/// TitleCase 过滤器
+///
+/// 按 Unicode 单词边界切分，每个单词首字母大写、其余字母小写；
+/// 空白、标点与不区分大小写的文字（如中文）保持不变
+pub struct TitleCaseFilter;
+
+impl Filter for TitleCaseFilter {
+    fn apply(&self, input: &SharedValue, _args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("title_case filter requires string input".to_string())
+        })?;
+        let mut title = String::with_capacity(s.len());
+        for word in s.split_word_bounds() {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) if first.is_alphanumeric() => {
+                    title.extend(first.to_uppercase());
+                    title.extend(chars.flat_map(char::to_lowercase));
+                }
+                _ => title.push_str(word),
+            }
+        }
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            title.into_boxed_str(),
+        ))))
+    }
+}
+
 /// Replace 过滤器
 /// 参数: [from, to]
 pub struct ReplaceFilter;
 
 impl Filter for ReplaceFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["from", "to"]
+    }
+
     fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
         let s = input.as_str().ok_or_else(|| {
             RuntimeError::Extraction("replace filter requires string input".to_string())
@@ -84,6 +146,10 @@ impl Filter for ReplaceFilter {
 pub struct RegexReplaceFilter;
 
 impl Filter for RegexReplaceFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["pattern", "replacement"]
+    }
+
     fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
         let s = input.as_str().ok_or_else(|| {
             RuntimeError::Extraction("regex_replace filter requires string input".to_string())
@@ -111,11 +177,75 @@ impl Filter for RegexReplaceFilter {
     }
 }
 
+/// MatchAll 过滤器
+/// 参数: [pattern, group?]
+///
+/// 返回所有匹配组成的数组；指定 `group`（序号或名称）时取每个匹配中该捕获组的内容，
+/// 捕获组未参与匹配的项被跳过。无匹配时返回空数组
+pub struct MatchAllFilter;
+
+impl Filter for MatchAllFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["pattern", "group"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("match_all filter requires string input".to_string())
+        })?;
+
+        let pattern = args.first().and_then(|v| v.as_str()).ok_or_else(|| {
+            RuntimeError::Extraction("match_all filter requires a 'pattern' argument".to_string())
+        })?;
+        let re = regex::Regex::new(pattern)
+            .map_err(|e| RuntimeError::Extraction(format!("Invalid regex pattern: {}", e)))?;
+
+        let group = match args.get(1) {
+            Some(Value::Number(n)) => n.as_u64().map(|i| i.to_string()),
+            Some(Value::String(g)) => Some(g.clone()),
+            _ => None,
+        };
+        if let Some(group) = &group {
+            let exists = match group.parse::<usize>() {
+                Ok(index) => index < re.captures_len(),
+                Err(_) => re.capture_names().flatten().any(|name| name == group),
+            };
+            if !exists {
+                return Err(RuntimeError::Extraction(format!(
+                    "match_all: pattern has no capture group '{}'",
+                    group
+                )));
+            }
+        }
+
+        let to_value = |m: regex::Match<'_>| -> SharedValue {
+            Arc::new(ExtractValueData::String(Arc::from(m.as_str())))
+        };
+        let matches: Vec<SharedValue> = match &group {
+            None => re.find_iter(s).map(to_value).collect(),
+            Some(group) => re
+                .captures_iter(s)
+                .filter_map(|caps| match group.parse::<usize>() {
+                    Ok(index) => caps.get(index),
+                    Err(_) => caps.name(group),
+                })
+                .map(to_value)
+                .collect(),
+        };
+
+        Ok(Arc::new(ExtractValueData::Array(Arc::new(matches))))
+    }
+}
+
 /// Split 过滤器
 /// 参数: [separator]
 pub struct SplitFilter;
 
 impl Filter for SplitFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["separator"]
+    }
+
     fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
         let s = input.as_str().ok_or_else(|| {
             RuntimeError::Extraction("split filter requires string input".to_string())
@@ -141,6 +271,10 @@ impl Filter for SplitFilter {
 pub struct JoinFilter;
 
 impl Filter for JoinFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["separator"]
+    }
+
     fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
         let arr = input.as_array_slice().ok_or_else(|| {
             RuntimeError::Extraction("join filter requires array input".to_string())
@@ -170,8 +304,7 @@ impl Filter for StripHtmlFilter {
         })?;
 
         // 使用正则移除 HTML 标签
-        let re = regex::Regex::new(r"<[^>]+>").unwrap();
-        let result = re.replace_all(s, "").to_string();
+        let result = HTML_TAG.replace_all(s, "").to_string();
 
         Ok(Arc::new(ExtractValueData::String(Arc::from(
             result.into_boxed_str(),
@@ -179,11 +312,120 @@ impl Filter for StripHtmlFilter {
     }
 }
 
+/// NormalizeWhitespace 过滤器
+/// 参数: [preserve_paragraphs?, convert_nbsp?]
+///
+/// 将连续的 Unicode 空白折叠为单个空格并去除首尾空白。
+/// `preserve_paragraphs` 为 `true` 时，包含两个及以上换行的空白（段落分隔）折叠为 `\n\n`；
+/// `convert_nbsp`（默认 `true`）为 `false` 时保留不换行空格 `\u{00A0}`，不视为空白
+pub struct NormalizeWhitespaceFilter;
+
+impl Filter for NormalizeWhitespaceFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["preserve_paragraphs", "convert_nbsp"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction(
+                "normalize_whitespace filter requires string input".to_string(),
+            )
+        })?;
+        let preserve_paragraphs = bool_arg(args, 0, false);
+        let convert_nbsp = bool_arg(args, 1, true);
+
+        let mut result = String::with_capacity(s.len());
+        // 当前空白段中的换行数，`None` 表示不在空白段中
+        let mut pending: Option<usize> = None;
+        for c in s.chars() {
+            if c.is_whitespace() && (convert_nbsp || c != '\u{00A0}') {
+                *pending.get_or_insert(0) += usize::from(c == '\n');
+                continue;
+            }
+            // 首部空白直接丢弃
+            if let Some(newlines) = pending.take()
+                && !result.is_empty()
+            {
+                result.push_str(if preserve_paragraphs && newlines >= 2 {
+                    "\n\n"
+                } else {
+                    " "
+                });
+            }
+            result.push(c);
+        }
+
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            result.into_boxed_str(),
+        ))))
+    }
+}
+
+/// Paragraphs 过滤器
+/// 参数: [min_chars?]
+///
+/// 按换行与 `<br>`、`<p>`、`<div>` 标签拆分为段落数组，移除其余 HTML 标签，
+/// 逐段去除首尾空白并丢弃空段。指定 `min_chars` 时同时丢弃字符数少于该值的段落
+/// （如插入的广告短句）
+pub struct ParagraphsFilter;
+
+impl Filter for ParagraphsFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["min_chars"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("paragraphs filter requires string input".to_string())
+        })?;
+        let min_chars = args
+            .first()
+            .map(|arg| {
+                let parsed = match arg {
+                    Value::Number(n) => n.as_u64(),
+                    Value::String(s) => s.trim().parse().ok(),
+                    _ => None,
+                };
+                parsed.ok_or_else(|| {
+                    RuntimeError::Extraction(
+                        "paragraphs filter: 'min_chars' must be a non-negative integer".to_string(),
+                    )
+                })
+            })
+            .transpose()?
+            .unwrap_or(1) as usize;
+
+        let text = PARAGRAPH_BREAK.replace_all(s, "\n");
+        let text = HTML_TAG.replace_all(&text, "");
+        let paragraphs: Vec<SharedValue> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && line.chars().count() >= min_chars)
+            .map(|line| Arc::new(ExtractValueData::String(Arc::from(line))))
+            .collect();
+
+        Ok(Arc::new(ExtractValueData::Array(Arc::new(paragraphs))))
+    }
+}
+
+/// 读取布尔参数，接受布尔值或字符串 `"true"` / `"false"`
+fn bool_arg(args: &[Value], index: usize, default: bool) -> bool {
+    match args.get(index) {
+        Some(Value::Bool(b)) => *b,
+        Some(Value::String(s)) => s.trim().eq_ignore_ascii_case("true"),
+        _ => default,
+    }
+}
+
 /// Substring 过滤器
 /// 参数: [start, length?]
 pub struct SubstringFilter;
 
 impl Filter for SubstringFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["start", "length"]
+    }
+
     fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
         let s = input.as_str().ok_or_else(|| {
             RuntimeError::Extraction("substring filter requires string input".to_string())
@@ -204,3 +446,140 @@ impl Filter for SubstringFilter {
         ))))
     }
 }
+
+/// Truncate 过滤器
+/// 参数: [max_chars, suffix?, word_boundary?]
+///
+/// 按字符数截断到 `max_chars`，仅在确实截断时追加 `suffix`（默认 `…`，不计入长度）。
+/// `word_boundary` 为 `true` 时回退到最近的空白处，避免切断单词；
+/// 截断位置之前没有空白时仍按字符截断
+pub struct TruncateFilter;
+
+impl Filter for TruncateFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["max_chars", "suffix", "word_boundary"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let s = input.as_str().ok_or_else(|| {
+            RuntimeError::Extraction("truncate filter requires string input".to_string())
+        })?;
+
+        let max_chars = args
+            .first()
+            .and_then(|v| match v {
+                Value::Number(n) => n.as_u64(),
+                Value::String(s) => s.trim().parse().ok(),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                RuntimeError::Extraction(
+                    "truncate filter requires a non-negative 'max_chars' argument".to_string(),
+                )
+            })? as usize;
+        let suffix = args.get(1).and_then(|v| v.as_str()).unwrap_or("…");
+        let word_boundary = match args.get(2) {
+            Some(Value::Bool(b)) => *b,
+            Some(Value::String(s)) => s.eq_ignore_ascii_case("true"),
+            _ => false,
+        };
+
+        let Some((cut, next)) = s.char_indices().nth(max_chars) else {
+            return Ok(input.clone());
+        };
+        let mut kept = &s[..cut];
+        if word_boundary
+            && !next.is_whitespace()
+            && let Some(space) = kept.rfind(char::is_whitespace)
+        {
+            kept = &kept[..space];
+        }
+
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            format!("{}{}", kept.trim_end(), suffix).into_boxed_str(),
+        ))))
+    }
+}
+
+/// 读取字符串输入与单个字符串参数（前缀/后缀类过滤器共用）
+fn string_and_affix<'a>(
+    name: &str,
+    input: &'a SharedValue,
+    args: &'a [Value],
+) -> Result<(&'a str, &'a str)> {
+    let s = input.as_str().ok_or_else(|| {
+        RuntimeError::Extraction(format!("{} filter requires string input", name))
+    })?;
+    let affix = args.first().and_then(|v| v.as_str()).ok_or_else(|| {
+        RuntimeError::Extraction(format!("{} filter requires 1 string argument", name))
+    })?;
+    Ok((s, affix))
+}
+
+/// TrimPrefix 过滤器
+/// 参数: [prefix]，输入以其开头时移除
+pub struct TrimPrefixFilter;
+
+impl Filter for TrimPrefixFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["prefix"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (s, prefix) = string_and_affix("trim_prefix", input, args)?;
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            s.strip_prefix(prefix).unwrap_or(s),
+        ))))
+    }
+}
+
+/// TrimSuffix 过滤器
+/// 参数: [suffix]，输入以其结尾时移除
+pub struct TrimSuffixFilter;
+
+impl Filter for TrimSuffixFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["suffix"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (s, suffix) = string_and_affix("trim_suffix", input, args)?;
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            s.strip_suffix(suffix).unwrap_or(s),
+        ))))
+    }
+}
+
+/// Prepend 过滤器
+/// 参数: [prefix]
+pub struct PrependFilter;
+
+impl Filter for PrependFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["prefix"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (s, prefix) = string_and_affix("prepend", input, args)?;
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            format!("{}{}", prefix, s).into_boxed_str(),
+        ))))
+    }
+}
+
+/// Append 过滤器
+/// 参数: [suffix]
+pub struct AppendFilter;
+
+impl Filter for AppendFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["suffix"]
+    }
+
+    fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
+        let (s, suffix) = string_and_affix("append", input, args)?;
+        Ok(Arc::new(ExtractValueData::String(Arc::from(
+            format!("{}{}", s, suffix).into_boxed_str(),
+        ))))
+    }
+}
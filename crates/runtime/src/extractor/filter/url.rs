@@ -7,6 +7,7 @@ use crate::{
 };
 use serde_json::Value;
 use std::sync::Arc;
+use url::Url;
 
 /// AbsoluteUrl 过滤器
 /// 将相对 URL 转换为绝对 URL
@@ -14,6 +15,10 @@ use std::sync::Arc;
 pub struct AbsoluteUrlFilter;
 
 impl Filter for AbsoluteUrlFilter {
+    fn params(&self) -> &'static [&'static str] {
+        &["base_url"]
+    }
+
     fn apply(&self, input: &SharedValue, args: &[Value]) -> Result<SharedValue> {
         let url = input.as_str().ok_or_else(|| {
             RuntimeError::Extraction("absolute_url filter requires string input".to_string())
@@ -31,32 +36,43 @@ impl Filter for AbsoluteUrlFilter {
             RuntimeError::Extraction("absolute_url filter requires base_url argument".to_string())
         })?;
 
-        // 拼接 URL
-        let absolute = if url.starts_with('/') {
-            // 绝对路径
-            let base = base_url.trim_end_matches('/');
-            // 提取 base 的 origin (scheme + host)
-            if let Some(idx) = base.find("://") {
-                if let Some(path_start) = base[idx + 3..].find('/') {
-                    format!("{}{}", &base[..idx + 3 + path_start], url)
-                } else {
-                    format!("{}{}", base, url)
-                }
-            } else {
-                format!("{}{}", base, url)
-            }
-        } else {
-            // 相对路径
-            let base = base_url.trim_end_matches('/');
-            format!("{}/{}", base, url)
-        };
-
         Ok(Arc::new(ExtractValueData::String(Arc::from(
-            absolute.into_boxed_str(),
+            resolve_url(base_url, url).into_boxed_str(),
         ))))
     }
 }
 
+/// 按 RFC 3986 将相对 URL 解析为绝对 URL
+///
+/// `base` 应为页面的最终 URL（重定向之后），
+/// 这样 `/a` 重定向到 `/b/` 后，`c.html` 会解析为 `/b/c.html`；
+/// `../`、`?query`、`#fragment` 与协议相对 URL（`//cdn.example.com/a.jpg`）
+/// 均由 `Url::join` 处理。
+/// `base` 无法解析时退化为简单拼接（`url` 本身为绝对 URL 时原样返回），
+/// 协议相对 URL 沿用 `base` 的协议（缺省为 https）。
+pub fn resolve_url(base: &str, url: &str) -> String {
+    if let Ok(joined) = Url::parse(base).and_then(|b| b.join(url)) {
+        return joined.to_string();
+    }
+
+    // 已是绝对 URL 时无需 `base`
+    if Url::parse(url).is_ok() {
+        return url.to_string();
+    }
+
+    if let Some(rest) = url.strip_prefix("//") {
+        let scheme = base.split_once("://").map_or("https", |(scheme, _)| scheme);
+        return format!("{}://{}", scheme, rest);
+    }
+
+    let base = base.trim_end_matches('/');
+    if url.starts_with(['/', '?', '#']) {
+        format!("{}{}", base, url)
+    } else {
+        format!("{}/{}", base, url)
+    }
+}
+
 /// UrlEncode 过滤器
 pub struct UrlEncodeFilter;
 
@@ -6,8 +6,12 @@ pub mod engine;
 pub mod executor;
 pub mod filter;
 pub mod selector;
+pub mod validate;
 pub mod value;
+pub mod visit;
 
-pub use engine::ExtractEngine;
+pub use engine::{AttemptOutcome, ChainAttempt, ExtractEngine, ExtractionTrace, FieldSource};
 pub use executor::StepExecutorFactory;
+pub use validate::{Diagnostic, SelectorValidator, Severity, ValidationReport};
 pub use value::{ExtractValueData, SharedValue};
+pub use visit::{RuleWalker, StepVisitor};
@@ -4,9 +4,12 @@ use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
     error::RuntimeError,
-    extractor::value::{ExtractValueData, SharedValue},
+    extractor::{
+        selector::css::CssSelectorExecutor,
+        value::{ExtractValueData, SharedValue},
+    },
 };
-use scraper::Html;
+use serde_json::{Map, Value};
 use std::sync::Arc;
 
 /// 属性提取器
@@ -14,8 +17,10 @@ use std::sync::Arc;
 /// 从 HTML 元素中提取属性或文本内容
 /// 支持的属性名：
 /// - `text` - 提取文本内容
+/// - `own_text` - 仅提取元素自身的直接子文本节点
 /// - `html` - 提取内部 HTML
 /// - `outer_html` - 提取外部 HTML（包含自身标签）
+/// - `first(a,b,...)` - 依次尝试多个属性，返回第一个非空值（如懒加载图片）
 /// - 其他 - 提取指定属性值（如 href, src, class 等）
 pub struct AttrExecutor;
 
@@ -58,14 +63,76 @@ impl AttrExecutor {
         }
     }
 
-    fn extract_from_html(html: &str, attr_name: &str) -> Result<SharedValue> {
-        let document = Html::parse_fragment(html);
+    /// 一次提取多个属性
+    ///
+    /// 返回 `{ 属性名: 值 }` 的 JSON 对象，缺失的属性为 null；
+    /// 输入为数组时对每个元素分别提取
+    pub fn execute_multiple(
+        attr_names: &[String],
+        input: &ExtractValueData,
+        _runtime_context: &RuntimeContext,
+        _flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        match input {
+            ExtractValueData::Html(html) | ExtractValueData::String(html) => {
+                Self::extract_multiple_from_html(html, attr_names)
+            }
+            ExtractValueData::Array(arr) => {
+                let results: Vec<SharedValue> = arr
+                    .iter()
+                    .filter_map(|item| match item.as_ref() {
+                        ExtractValueData::Html(h) | ExtractValueData::String(h) => {
+                            Self::extract_multiple_from_html(h, attr_names).ok()
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Arc::new(ExtractValueData::Array(Arc::new(results))))
+            }
+            _ => Err(RuntimeError::Extraction(
+                "Attr executor requires HTML input".to_string(),
+            )),
+        }
+    }
+
+    fn extract_multiple_from_html(html: &str, attr_names: &[String]) -> Result<SharedValue> {
+        let mut object = Map::new();
+        for name in attr_names {
+            let value = Self::extract_from_html(html, name)?;
+            object.insert(name.clone(), value.to_owned_json());
+        }
+        Ok(Arc::new(ExtractValueData::Json(Arc::new(Value::Object(
+            object,
+        )))))
+    }
 
-        // 获取根元素（第一个非文本元素）
-        let root = document
-            .root_element()
-            .first_child()
-            .and_then(scraper::ElementRef::wrap);
+    /// 从单个 HTML 片段中提取属性或文本
+    pub(crate) fn extract_from_html(html: &str, attr_name: &str) -> Result<SharedValue> {
+        // 回退链：first(data-src,data-original,src)
+        if let Some(candidates) = Self::parse_first(attr_name) {
+            for candidate in candidates {
+                let value = Self::extract_from_html(html, candidate)?;
+                if !value.is_empty() {
+                    return Ok(value);
+                }
+            }
+            return Ok(Arc::new(ExtractValueData::Null));
+        }
+
+        let document = CssSelectorExecutor::parse_fragment(html);
+
+        // 获取根元素（第一个非文本元素，表格片段跳过补全的外层结构）
+        let root = match CssSelectorExecutor::table_tag(html) {
+            Some(tag) => document
+                .root_element()
+                .descendants()
+                .filter_map(scraper::ElementRef::wrap)
+                .find(|el| el.value().name() == tag),
+            None => document
+                .root_element()
+                .first_child()
+                .and_then(scraper::ElementRef::wrap),
+        };
 
         let result = match attr_name {
             "text" => {
@@ -83,6 +150,22 @@ impl AttrExecutor {
                     ExtractValueData::String(Arc::from(text.into_boxed_str()))
                 }
             }
+            "own_text" => {
+                // 仅提取根元素的直接子文本节点（XPath 的 `/text()`）
+                let text: String = root
+                    .map(|el| {
+                        el.children()
+                            .filter_map(|node| node.value().as_text().map(|t| t.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let text = text.trim();
+                if text.is_empty() {
+                    ExtractValueData::Null
+                } else {
+                    ExtractValueData::String(Arc::from(text))
+                }
+            }
             "html" | "inner_html" => {
                 // 提取内部 HTML
                 if let Some(el) = root {
@@ -116,4 +199,16 @@ impl AttrExecutor {
 
         Ok(Arc::new(result))
     }
+
+    /// 解析 `first(a,b,...)` 回退链，返回候选属性名
+    fn parse_first(attr_name: &str) -> Option<Vec<&str>> {
+        let inner = attr_name.trim().strip_prefix("first(")?.strip_suffix(')')?;
+        Some(
+            inner
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .collect(),
+        )
+    }
 }
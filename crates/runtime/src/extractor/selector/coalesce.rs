@@ -0,0 +1,38 @@
+//! # 多变量回退执行器
+
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    extractor::value::{ExtractValueData, SharedValue},
+    template::TemplateExt,
+};
+use crawler_schema::template::Template;
+use std::sync::Arc;
+
+/// 多变量回退执行器
+pub struct CoalesceExecutor;
+
+impl CoalesceExecutor {
+    /// 依次渲染模板，返回第一个非空结果，全部为空时返回 `Null`
+    ///
+    /// 渲染失败（如引用了不存在的变量）的模板视为空
+    pub fn execute(
+        inputs: &[Template],
+        _input: &ExtractValueData,
+        _runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        for template in inputs {
+            match template.render(flow_context) {
+                Ok(rendered) if !rendered.trim().is_empty() => {
+                    return Ok(Arc::new(ExtractValueData::String(Arc::from(
+                        rendered.trim(),
+                    ))));
+                }
+                Ok(_) => {}
+                Err(e) => tracing::debug!("coalesce 模板 '{}' 渲染失败: {}", template, e),
+            }
+        }
+        Ok(Arc::new(ExtractValueData::Null))
+    }
+}
@@ -1,45 +1,88 @@
 //! # 组件引用执行器
 //!
-//! 处理 `use_component` 步骤，引用预定义的可复用组件。
+//! 处理 `use_component` 步骤，引用 `components` 中预定义的可复用组件。
 //!
-//! 组件执行需要在运行时解析组件定义并执行其提取逻辑。
-//! 当前实现为占位符，完整实现需要访问全局组件注册表。
+//! # 参数绑定
+//!
+//! 组件在流程上下文的子作用域（见 `FlowContext::child`）中执行，依次写入：
+//! 1. 组件定义的默认 `inputs`
+//! 2. 调用时的 `args`（覆盖同名默认值；字符串按模板在调用方上下文中渲染）
+//!
+//! 组件内部写入的变量不会泄漏到调用方。
+//!
+//! # 嵌套与循环
+//!
+//! 组件可以再引用其他组件。规则校验只检查组件是否已定义，不分析引用图，
+//! 因此循环引用（`a → b → a`）在执行时检测：当前调用链由流程上下文记录
+//! （见 `FlowContext::component_scope`，不占用变量名），再次进入链上已有的组件时返回
+//! `CircularReference`；嵌套层数受 `FlowLimits::max_depth` 限制，超出时返回
+//! `ResourceLimitExceeded`。
 
 use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
-    extractor::value::{ExtractValueData, SharedValue},
+    error::RuntimeError,
+    extractor::{
+        engine::ExtractEngine,
+        value::{ExtractValueData, SharedValue},
+    },
+    template::TemplateExt,
 };
-use crawler_schema::flow::ComponentRef;
-use std::sync::Arc;
+use crawler_schema::{flow::ComponentRef, template::Template};
+use serde_json::Value;
+use std::collections::HashMap;
 
 /// 组件引用执行器
 pub struct ComponentExecutor;
 
 impl ComponentExecutor {
-    /// 获取组件名称
-    fn component_name(component_ref: &ComponentRef) -> &str {
+    /// 获取组件名称与调用参数
+    fn parts(component_ref: &ComponentRef) -> (&str, Option<&HashMap<String, Value>>) {
         match component_ref {
-            ComponentRef::Simple(name) => name,
-            ComponentRef::WithArgs { name, .. } => name,
+            ComponentRef::Simple(name) => (name, None),
+            ComponentRef::WithArgs { name, args } => (name, args.as_ref()),
         }
     }
 
     /// 执行组件引用
+    ///
+    /// 以当前输入执行组件的 `extractor`（含回退与默认值），返回其结果
     pub fn execute(
         component_ref: &ComponentRef,
         input: &ExtractValueData,
-        _runtime_context: &RuntimeContext,
-        _flow_context: &FlowContext,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
     ) -> Result<SharedValue> {
-        // TODO: 完整实现需要：
-        // 1. 从上下文获取全局组件注册表
-        // 2. 根据名称查找组件定义
-        // 3. 合并参数（组件默认 inputs + 调用时的 args）
-        // 4. 执行组件的 extractor 步骤
-        //
-        // 当前返回输入值作为占位
-        let _ = Self::component_name(component_ref); // 避免 dead_code 警告
-        Ok(Arc::new(input.clone()))
+        let (name, args) = Self::parts(component_ref);
+        let component = runtime_context
+            .rule()
+            .components
+            .as_ref()
+            .and_then(|components| components.get(name))
+            .ok_or_else(|| RuntimeError::UndefinedComponent {
+                component: name.to_string(),
+            })?;
+
+        // 绑定参数：默认 inputs，再由调用参数覆盖
+        let mut component_context = flow_context.component_scope(name)?;
+        if let Some(inputs) = &component.inputs {
+            for (key, value) in inputs {
+                component_context.set(key.clone(), value.clone());
+            }
+        }
+        for (key, value) in args.into_iter().flatten() {
+            let value = match value {
+                Value::String(s) => Value::String(Template::from(s.clone()).render(flow_context)?),
+                other => other.clone(),
+            };
+            component_context.set(key.clone(), value);
+        }
+
+        ExtractEngine::extract_field(
+            &component.extractor,
+            input,
+            runtime_context,
+            &component_context,
+        )
     }
 }
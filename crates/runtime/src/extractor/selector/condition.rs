@@ -10,7 +10,8 @@ use crate::{
         value::{ExtractValueData, SharedValue},
     },
 };
-use crawler_schema::extract::{ConditionStep, ExtractStep};
+use crawler_schema::extract::{CompareOp, ConditionStep, ExtractStep};
+use serde_json::Value;
 use std::sync::Arc;
 
 /// 条件执行器
@@ -24,7 +25,7 @@ impl ConditionExecutor {
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> Result<SharedValue> {
-        if Self::evaluate_condition(&condition.when, input, runtime_context, flow_context) {
+        if Self::evaluate_condition(condition, input, runtime_context, flow_context)? {
             // 条件为真，执行 then 步骤
             Self::execute_steps(&condition.then, input, runtime_context, flow_context)
         } else if let Some(otherwise) = &condition.otherwise {
@@ -54,16 +55,102 @@ impl ConditionExecutor {
 
     /// 判断条件是否为真
     ///
-    /// 执行 `when` 步骤，如果结果非空/非 null/非 false，则为真
+    /// 执行 `when` 步骤：
+    /// - 未设置 `op`：结果非空/非 null/非 false 则为真
+    /// - 设置了 `op`：将结果与 `value` 比较
+    ///
+    /// `when` 步骤执行失败视为条件为假
     fn evaluate_condition(
-        steps: &[ExtractStep],
+        condition: &ConditionStep,
         input: &ExtractValueData,
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
-    ) -> bool {
-        match Self::execute_steps(steps, input, runtime_context, flow_context) {
-            Ok(result) => result.is_truthy(),
-            Err(_) => false,
+    ) -> Result<bool> {
+        let result =
+            match Self::execute_steps(&condition.when, input, runtime_context, flow_context) {
+                Ok(result) => result,
+                Err(_) => return Ok(false),
+            };
+
+        match condition.op {
+            None => Ok(result.is_truthy()),
+            Some(op) => {
+                let expected = condition.value.as_ref().unwrap_or(&Value::Null);
+                Self::compare(op, &result.to_owned_json(), expected, runtime_context)
+            }
+        }
+    }
+
+    /// `matches` 比较使用的正则表达式（`value` 的文本形式），其他比较返回 `None`
+    ///
+    /// 供规则校验与预热在加载阶段编译
+    pub fn match_pattern(condition: &ConditionStep) -> Option<String> {
+        (condition.op == Some(CompareOp::Matches))
+            .then(|| Self::as_text(condition.value.as_ref().unwrap_or(&Value::Null)))
+    }
+
+    /// 执行比较运算
+    ///
+    /// `matches` 的正则从运行时上下文的缓存中获取，不会在每次求值时重新编译
+    fn compare(
+        op: CompareOp,
+        actual: &Value,
+        expected: &Value,
+        runtime_context: &RuntimeContext,
+    ) -> Result<bool> {
+        match op {
+            CompareOp::Eq => Ok(Self::loose_eq(actual, expected)),
+            CompareOp::Ne => Ok(!Self::loose_eq(actual, expected)),
+            CompareOp::Gt | CompareOp::Lt | CompareOp::Gte | CompareOp::Lte => {
+                let (Some(a), Some(b)) = (Self::as_number(actual), Self::as_number(expected))
+                else {
+                    return Ok(false);
+                };
+                Ok(match op {
+                    CompareOp::Gt => a > b,
+                    CompareOp::Lt => a < b,
+                    CompareOp::Gte => a >= b,
+                    _ => a <= b,
+                })
+            }
+            CompareOp::Contains => Ok(match actual {
+                Value::Array(items) => items.iter().any(|v| Self::loose_eq(v, expected)),
+                Value::String(s) => s.contains(Self::as_text(expected).as_str()),
+                _ => false,
+            }),
+            CompareOp::Matches => {
+                let re = runtime_context.regex(&Self::as_text(expected))?;
+                Ok(re.is_match(&Self::as_text(actual)))
+            }
+        }
+    }
+
+    /// 宽松相等：数值按数值比较，字符串与其他标量按文本比较
+    fn loose_eq(a: &Value, b: &Value) -> bool {
+        if a == b {
+            return true;
+        }
+        match (Self::as_number(a), Self::as_number(b)) {
+            (Some(x), Some(y)) => x == y,
+            _ => !a.is_array() && !a.is_object() && Self::as_text(a) == Self::as_text(b),
+        }
+    }
+
+    /// 解析为数值（字符串会尝试解析）
+    fn as_number(v: &Value) -> Option<f64> {
+        match v {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// 转换为文本
+    fn as_text(v: &Value) -> String {
+        match v {
+            Value::String(s) => s.clone(),
+            Value::Null => String::new(),
+            other => other.to_string(),
         }
     }
 }
@@ -4,21 +4,33 @@ use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
     error::RuntimeError,
-    extractor::value::{ExtractValueData, SharedValue},
+    extractor::{
+        selector::attr::AttrExecutor,
+        value::{ExtractValueData, SharedValue},
+    },
 };
 use crawler_schema::extract::SelectorStep;
-use scraper::{Html, Selector};
+use scraper::Html;
 use std::sync::Arc;
 
 /// CSS 选择器执行器
+///
+/// 支持在选择器末尾使用伪元素直接提取内容（Scrapy 风格）：
+/// - `::text` - 提取文本内容
+/// - `::own_text` - 仅提取元素自身的直接子文本节点
+/// - `::html` - 提取内部 HTML
+/// - `::attr(name)` - 提取指定属性值，如 `a::attr(href)`
 pub struct CssSelectorExecutor;
 
 impl CssSelectorExecutor {
     /// 执行 CSS 选择器
+    ///
+    /// `Html` 输入按片段解析，选择器只在该片段内查找；
+    /// 数组输入（如 `map` 之前选中的元素列表）对每个元素分别查找
     pub fn execute(
         selector: &SelectorStep,
         input: &ExtractValueData,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         _flow_context: &FlowContext,
     ) -> Result<SharedValue> {
         // 获取 HTML 字符串
@@ -30,7 +42,7 @@ impl CssSelectorExecutor {
                     .iter()
                     .filter_map(|item| match item.as_ref() {
                         ExtractValueData::Html(h) | ExtractValueData::String(h) => {
-                            Self::execute_on_html(h, selector).ok()
+                            Self::execute_on_html(h, selector, runtime_context).ok()
                         }
                         _ => None,
                     })
@@ -45,7 +57,7 @@ impl CssSelectorExecutor {
             }
         };
 
-        let results = Self::execute_on_html(html, selector)?;
+        let results = Self::execute_on_html(html, selector, runtime_context)?;
         if results.is_empty() {
             Ok(Arc::new(ExtractValueData::Null))
         } else if results.len() == 1 && !Self::is_select_all(selector) {
@@ -55,44 +67,122 @@ impl CssSelectorExecutor {
         }
     }
 
-    /// 在 HTML 上执行选择器
-    fn execute_on_html(html: &str, selector: &SelectorStep) -> Result<Vec<SharedValue>> {
-        let document = Html::parse_fragment(html);
+    /// 在 HTML 上执行选择器，编译后的选择器缓存在运行时上下文中
+    fn execute_on_html(
+        html: &str,
+        selector: &SelectorStep,
+        runtime_context: &RuntimeContext,
+    ) -> Result<Vec<SharedValue>> {
+        let document = Self::parse_fragment(html);
 
         let (selector_str, select_all) = match selector {
             SelectorStep::Simple(s) => (s.as_str(), false),
             SelectorStep::WithOptions { expr, all } => (expr.as_str(), *all),
         };
 
-        let css_selector = Selector::parse(selector_str).map_err(|e| {
-            RuntimeError::Extraction(format!("Invalid CSS selector '{}': {:?}", selector_str, e))
-        })?;
+        let (selector_str, pseudo) = Self::split_pseudo(selector_str)?;
+
+        let css_selector = runtime_context.css_selector(selector_str)?;
 
         let elements = document.select(&css_selector);
+        let limit = if select_all { usize::MAX } else { 1 };
 
-        let results: Vec<SharedValue> = if select_all {
-            elements
-                .map(|el| {
-                    Arc::new(ExtractValueData::Html(Arc::from(
-                        el.html().into_boxed_str(),
-                    )))
-                })
-                .collect()
-        } else {
-            // 只取第一个匹配
-            elements
-                .take(1)
-                .map(|el| {
-                    Arc::new(ExtractValueData::Html(Arc::from(
-                        el.html().into_boxed_str(),
-                    )))
-                })
-                .collect()
-        };
+        let mut results = Vec::new();
+        for el in elements {
+            if results.len() >= limit {
+                break;
+            }
+            let value = match pseudo {
+                Some(attr_name) => AttrExecutor::extract_from_html(&el.html(), attr_name)?,
+                None => Arc::new(ExtractValueData::Html(Arc::from(
+                    el.html().into_boxed_str(),
+                ))),
+            };
+            // 伪元素提取结果为空时跳过（如元素缺少该属性）
+            if !value.is_empty() {
+                results.push(value);
+            }
+        }
 
         Ok(results)
     }
 
+    /// 解析 HTML 片段
+    ///
+    /// 输入通常是前一步选中元素的外部 HTML，之后的选择器只在该片段内查找，
+    /// 不会匹配到兄弟元素。表格行、单元格等元素脱离 `<table>` 时会被 HTML
+    /// 解析器丢弃，此时补全外层表格结构再解析
+    pub(crate) fn parse_fragment(html: &str) -> Html {
+        match Self::table_tag(html).as_deref() {
+            Some("td" | "th") => Html::parse_fragment(&format!("<table><tr>{}</tr></table>", html)),
+            Some(_) => Html::parse_fragment(&format!("<table>{}</table>", html)),
+            None => Html::parse_fragment(html),
+        }
+    }
+
+    /// 片段以表格内部元素开头时返回其标签名（小写）
+    pub(crate) fn table_tag(html: &str) -> Option<String> {
+        let rest = html.trim_start().strip_prefix('<')?;
+        let tag = rest
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        matches!(
+            tag.as_str(),
+            "tr" | "td" | "th" | "thead" | "tbody" | "tfoot" | "caption" | "colgroup"
+        )
+        .then_some(tag)
+    }
+
+    /// 拆分选择器末尾的伪元素
+    ///
+    /// 返回 `(选择器, 提取属性名)`，属性名 `text`/`html` 与 `attr` 步骤含义一致
+    pub(crate) fn split_pseudo(selector: &str) -> Result<(&str, Option<&str>)> {
+        let trimmed = selector.trim_end();
+        let Some(pos) = trimmed.rfind("::") else {
+            return Ok((selector, None));
+        };
+        let (base, pseudo) = (&trimmed[..pos], &trimmed[pos + 2..]);
+
+        let attr_name = match pseudo {
+            "text" => "text",
+            "own_text" => "own_text",
+            "html" => "html",
+            _ => match pseudo
+                .strip_prefix("attr(")
+                .and_then(|rest| rest.strip_suffix(')'))
+            {
+                Some(name) if Self::is_valid_attr_name(name.trim()) => name.trim(),
+                Some(_) | None if pseudo.starts_with("attr") => {
+                    return Err(RuntimeError::Extraction(format!(
+                        "Invalid pseudo-element '::{}' in CSS selector '{}'",
+                        pseudo, selector
+                    )));
+                }
+                // 其他伪元素交由选择器解析器处理
+                _ => return Ok((selector, None)),
+            },
+        };
+
+        if base.trim().is_empty() {
+            return Err(RuntimeError::Extraction(format!(
+                "CSS selector '{}' has no element selector before '::{}'",
+                selector, pseudo
+            )));
+        }
+
+        Ok((base, Some(attr_name)))
+    }
+
+    /// 属性名是否合法
+    fn is_valid_attr_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':'))
+    }
+
     /// 是否选择所有匹配
     fn is_select_all(selector: &SelectorStep) -> bool {
         match selector {
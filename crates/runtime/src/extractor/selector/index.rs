@@ -14,6 +14,8 @@ pub struct IndexExecutor;
 
 impl IndexExecutor {
     /// 执行索引/切片
+    ///
+    /// 索引越界时返回 `Null`
     pub fn execute(
         index: &IndexStep,
         input: &ExtractValueData,
@@ -22,20 +24,15 @@ impl IndexExecutor {
     ) -> Result<SharedValue> {
         match input {
             ExtractValueData::Array(arr) => match index {
-                IndexStep::Single(idx) => {
-                    let index_pos = if *idx < 0 {
-                        (arr.len() as i32 + idx) as usize
-                    } else {
-                        *idx as usize
-                    };
-
-                    if index_pos < arr.len() {
-                        Ok(arr[index_pos].clone())
-                    } else {
-                        Err(RuntimeError::Extraction("Index out of bounds".to_string()))
-                    }
-                }
+                IndexStep::Single(idx) => Ok(Self::element(arr, *idx as i64)),
+                IndexStep::Slice(keyword) if keyword.trim() == "first" => Ok(Self::element(arr, 0)),
+                IndexStep::Slice(keyword) if keyword.trim() == "last" => Ok(Self::element(arr, -1)),
                 IndexStep::Slice(slice_str) => {
+                    // 字符串形式的单个索引，如 "-1"
+                    if let Ok(idx) = slice_str.trim().parse::<i64>() {
+                        return Ok(Self::element(arr, idx));
+                    }
+
                     // 解析切片：start:end 或 start:end:step
                     let parts: Vec<&str> = slice_str.split(':').collect();
                     let start = parts
@@ -61,4 +58,17 @@ impl IndexExecutor {
             )),
         }
     }
+
+    /// 按索引取元素，负数从末尾计数，越界时返回 `Null`
+    fn element(arr: &[SharedValue], index: i64) -> SharedValue {
+        let position = if index < 0 {
+            arr.len().checked_sub(index.unsigned_abs() as usize)
+        } else {
+            Some(index as usize)
+        };
+        position
+            .and_then(|pos| arr.get(pos))
+            .cloned()
+            .unwrap_or_else(|| Arc::new(ExtractValueData::Null))
+    }
 }
@@ -15,7 +15,14 @@ use std::sync::Arc;
 pub struct JsonSelectorExecutor;
 
 impl JsonSelectorExecutor {
+    /// 表达式是否为 JSON Pointer（以 `/` 开头），JSONPath 必须以 `$` 或 `@` 开头
+    pub fn is_pointer(expr: &str) -> bool {
+        expr.starts_with('/')
+    }
+
     /// 执行 JSON 选择器
+    ///
+    /// 以 `/` 开头的表达式按 JSON Pointer 查询（`Value::pointer`），其余按 JSONPath 查询
     pub fn execute(
         selector: &SelectorStep,
         input: &ExtractValueData,
@@ -49,6 +56,15 @@ impl JsonSelectorExecutor {
             SelectorStep::WithOptions { expr, all } => (expr.as_str(), *all),
         };
 
+        // JSON Pointer：固定路径，最多一个结果
+        if Self::is_pointer(jsonpath_str) {
+            return Ok(Arc::new(
+                json.pointer(jsonpath_str)
+                    .map(ExtractValueData::from_json)
+                    .unwrap_or(ExtractValueData::Null),
+            ));
+        }
+
         // 使用 JsonPath trait 的 query 方法
         let results = json.query(jsonpath_str).map_err(|e| {
             RuntimeError::Extraction(format!("Invalid JSONPath '{}': {}", jsonpath_str, e))
@@ -68,3 +84,55 @@ impl JsonSelectorExecutor {
         }
     }
 }
+
+/// JSON Pointer 执行器
+pub struct JsonPointerExecutor;
+
+impl JsonPointerExecutor {
+    /// 执行 JSON Pointer 查询
+    pub fn execute(
+        pointer: &str,
+        input: &ExtractValueData,
+        _runtime_context: &RuntimeContext,
+        _flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        Self::validate(pointer)?;
+
+        let parsed;
+        let json: &Value = match input {
+            ExtractValueData::Json(v) => v,
+            ExtractValueData::String(s) => {
+                parsed = serde_json::from_str::<Value>(s).map_err(|e| {
+                    RuntimeError::Extraction(format!("Failed to parse JSON: {}", e))
+                })?;
+                &parsed
+            }
+            ExtractValueData::Array(_) => {
+                parsed = input.to_owned_json();
+                &parsed
+            }
+            _ => {
+                return Err(RuntimeError::Extraction(
+                    "JSON pointer requires JSON input".to_string(),
+                ));
+            }
+        };
+
+        match json.pointer(pointer) {
+            Some(v) => Ok(Arc::new(ExtractValueData::from_json(v))),
+            None => Ok(Arc::new(ExtractValueData::Null)),
+        }
+    }
+
+    /// 校验 JSON Pointer 语法（必须为空或以 `/` 开头）
+    pub fn validate(pointer: &str) -> Result<()> {
+        if pointer.is_empty() || pointer.starts_with('/') {
+            Ok(())
+        } else {
+            Err(RuntimeError::Extraction(format!(
+                "Invalid JSON pointer '{}': must be empty or start with '/'",
+                pointer
+            )))
+        }
+    }
+}
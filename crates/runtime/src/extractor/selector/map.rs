@@ -1,6 +1,6 @@
 //! # 映射执行器
 //!
-//! 对数组每个元素应用步骤
+//! 对数组每个元素（或 JSON 对象的每个条目）应用步骤，执行时在流程上下文中提供 `loop` 变量
 
 use crate::{
     Result,
@@ -11,38 +11,135 @@ use crate::{
         value::{ExtractValueData, SharedValue},
     },
 };
-use crawler_schema::extract::ExtractStep;
+use crawler_schema::extract::{ExtractStep, MapCollect, MapErrorMode, MapStep};
+use serde_json::{Value, json};
 use std::sync::Arc;
 
+/// 循环信息变量名
+pub const LOOP_VAR: &str = "loop";
+
 /// 映射执行器
 pub struct MapExecutor;
 
 impl MapExecutor {
     /// 执行映射
+    ///
+    /// 每个元素在独立的子作用域中执行，子作用域中写入 `loop` 变量
+    /// （`index`、`index0`、`first`、`last`、`length`），配置 `index_as` 时
+    /// 同时将从 1 开始的序号写入该变量。元素执行完毕后，其子作用域中
+    /// [`export`](FlowContext::export) 的变量写回映射所在的作用域，对之后的元素可见
+    ///
+    /// 输入为 JSON 对象时按键顺序遍历条目，条目的值作为步骤输入，
+    /// `loop` 额外包含 `key`，配置 `key_as` 时同时将键写入该变量；
+    /// `collect` 为 `object` 时以原键重建对象，否则返回数组
     pub fn execute(
-        steps: &[ExtractStep],
+        map: &MapStep,
         input: &ExtractValueData,
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> Result<SharedValue> {
+        let mut scope = flow_context.clone();
         match input {
             ExtractValueData::Array(arr) => {
-                let results: Vec<SharedValue> = arr
-                    .iter()
-                    .filter_map(|item| {
-                        Self::execute_steps(steps, item, runtime_context, flow_context).ok()
-                    })
-                    .collect();
+                let length = arr.len();
+                let mut results = Vec::with_capacity(length);
+                for (i, item) in arr.iter().enumerate() {
+                    let item_context = Self::item_context(map, &scope, i, length, None);
+                    let result =
+                        Self::execute_steps(map.steps(), item, runtime_context, &item_context);
+                    scope.merge_exports(&item_context);
+                    if let Some(result) = Self::handle_error(map, result, &i.to_string())? {
+                        results.push(result);
+                    }
+                }
 
                 Ok(Arc::new(ExtractValueData::Array(Arc::new(results))))
             }
-            _ => {
-                // 非数组输入，直接应用步骤
-                Err(RuntimeError::Extraction(
-                    "Map step requires array input".to_string(),
-                ))
+            ExtractValueData::Json(json) if json.is_object() => {
+                let entries: Vec<(&String, &Value)> =
+                    json.as_object().into_iter().flatten().collect();
+                let length = entries.len();
+                let mut results = Vec::with_capacity(length);
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    let item_context = Self::item_context(map, &scope, i, length, Some(key));
+                    let item = ExtractValueData::from_json(value);
+                    let result =
+                        Self::execute_steps(map.steps(), &item, runtime_context, &item_context);
+                    scope.merge_exports(&item_context);
+                    if let Some(result) = Self::handle_error(map, result, key)? {
+                        results.push((key.clone(), result));
+                    }
+                }
+
+                let output = match map.collect() {
+                    MapCollect::Array => ExtractValueData::Array(Arc::new(
+                        results.into_iter().map(|(_, result)| result).collect(),
+                    )),
+                    MapCollect::Object => ExtractValueData::Json(Arc::new(Value::Object(
+                        results
+                            .into_iter()
+                            .map(|(key, result)| (key, result.to_owned_json()))
+                            .collect(),
+                    ))),
+                };
+                Ok(Arc::new(output))
+            }
+            _ => Err(RuntimeError::Extraction(
+                "Map step requires array or JSON object input".to_string(),
+            )),
+        }
+    }
+
+    /// 按 `on_error` 处理单个元素的结果，返回 `None` 表示丢弃该元素
+    fn handle_error(
+        map: &MapStep,
+        result: Result<SharedValue>,
+        position: &str,
+    ) -> Result<Option<SharedValue>> {
+        match (result, map.on_error()) {
+            (Ok(value), _) => Ok(Some(value)),
+            (Err(e), MapErrorMode::Skip) => {
+                tracing::debug!("映射元素 {} 失败，已跳过: {}", position, e);
+                Ok(None)
+            }
+            (Err(e), MapErrorMode::Null) => {
+                tracing::debug!("映射元素 {} 失败，以 null 占位: {}", position, e);
+                Ok(Some(Arc::new(ExtractValueData::Null)))
             }
+            (Err(e), MapErrorMode::Fail) => Err(RuntimeError::Extraction(format!(
+                "Map element {} failed: {}",
+                position, e
+            ))),
+        }
+    }
+
+    /// 构建单个元素的子作用域，写入 `loop` 与序号、键变量
+    fn item_context(
+        map: &MapStep,
+        flow_context: &FlowContext,
+        i: usize,
+        length: usize,
+        key: Option<&str>,
+    ) -> FlowContext {
+        let mut item_context = flow_context.child();
+        let mut loop_info = json!({
+            "index": i + 1,
+            "index0": i,
+            "first": i == 0,
+            "last": i + 1 == length,
+            "length": length,
+        });
+        if let Some(key) = key {
+            loop_info["key"] = json!(key);
+            if let Some(name) = map.key_as() {
+                item_context.set(name, json!(key));
+            }
+        }
+        item_context.set(LOOP_VAR, loop_info);
+        if let Some(name) = map.index_as() {
+            item_context.set(name, json!(i + 1));
         }
+        item_context
     }
 
     /// 对单个值执行所有步骤
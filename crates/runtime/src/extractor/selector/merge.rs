@@ -0,0 +1,62 @@
+//! # 合并执行器
+//!
+//! 对同一输入执行多组步骤，将各组结果合并为一个 JSON 对象
+
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    extractor::{
+        StepExecutorFactory,
+        value::{ExtractValueData, SharedValue},
+    },
+};
+use crawler_schema::extract::ExtractStep;
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// 合并执行器
+pub struct MergeExecutor;
+
+impl MergeExecutor {
+    /// 执行合并
+    ///
+    /// 每个键的步骤都以同一输入开始执行；结果为空或执行失败时该键为 `null`，
+    /// 不会中断其他键的提取
+    pub fn execute(
+        fields: &BTreeMap<String, Vec<ExtractStep>>,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        let mut object = Map::with_capacity(fields.len());
+
+        for (key, steps) in fields {
+            let value = Self::execute_steps(steps, input, runtime_context, flow_context)
+                .ok()
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_owned_json())
+                .unwrap_or(Value::Null);
+            object.insert(key.clone(), value);
+        }
+
+        Ok(Arc::new(ExtractValueData::Json(Arc::new(Value::Object(
+            object,
+        )))))
+    }
+
+    /// 对输入依次执行所有步骤
+    fn execute_steps(
+        steps: &[ExtractStep],
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        let mut current = Arc::new(input.clone());
+
+        for step in steps {
+            current = StepExecutorFactory::execute(step, &current, runtime_context, flow_context)?;
+        }
+
+        Ok(current)
+    }
+}
@@ -3,6 +3,7 @@
 //! 实现各种选择器：CSS, JSON, XPath, Regex
 
 pub mod attr;
+pub mod coalesce;
 pub mod component;
 pub mod condition;
 pub mod const_value;
@@ -10,13 +11,18 @@ pub mod css;
 pub mod index;
 pub mod json;
 pub mod map;
+pub mod merge;
 pub mod noop;
 pub mod regex;
 pub mod set_var;
+pub mod xpath;
 
+pub use coalesce::CoalesceExecutor;
 pub use component::ComponentExecutor;
 pub use condition::ConditionExecutor;
 pub use css::CssSelectorExecutor;
-pub use json::JsonSelectorExecutor;
+pub use json::{JsonPointerExecutor, JsonSelectorExecutor};
 pub use map::MapExecutor;
+pub use merge::MergeExecutor;
 pub use regex::RegexSelectorExecutor;
+pub use xpath::XpathExecutor;
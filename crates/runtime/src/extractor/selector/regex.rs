@@ -7,6 +7,7 @@ use crate::{
     extractor::value::{ExtractValueData, SharedValue},
 };
 use crawler_schema::extract::RegexStep;
+use serde_json::{Map, Value};
 use std::sync::Arc;
 
 /// 正则表达式选择器执行器
@@ -14,61 +15,113 @@ pub struct RegexSelectorExecutor;
 
 impl RegexSelectorExecutor {
     /// 执行正则匹配
+    ///
+    /// 输入为数组时对每个元素分别匹配，返回结果数组（未匹配的元素被丢弃）
     pub fn execute(
         regex: &RegexStep,
         input: &ExtractValueData,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         _flow_context: &FlowContext,
     ) -> Result<SharedValue> {
-        // 获取字符串
-        let text = input
-            .as_str()
-            .ok_or_else(|| RuntimeError::Extraction("Regex requires string input".to_string()))?;
-
         // 解析正则配置
-        let (pattern, group, global) = match regex {
-            RegexStep::Simple(p) => (p.as_str(), 1, false),
+        let (pattern, group, global, named) = match regex {
+            RegexStep::Simple(p) => (p.as_str(), 1, false, false),
             RegexStep::WithOptions {
                 pattern,
                 group,
                 global,
-            } => (pattern.as_str(), *group, *global),
+                named,
+            } => (pattern.as_str(), *group, *global, *named),
         };
 
-        // 编译正则表达式
-        let re = regex::Regex::new(pattern)
-            .map_err(|e| RuntimeError::Extraction(format!("Invalid regex pattern: {}", e)))?;
+        // 编译正则表达式（按模式缓存）
+        let re = runtime_context.regex(pattern)?;
+        if named && re.capture_names().flatten().next().is_none() {
+            return Err(RuntimeError::Extraction(format!(
+                "Regex pattern '{}' has no named groups",
+                pattern
+            )));
+        }
+        let capture = Capture { group, named };
+
+        // 数组输入：逐元素匹配
+        if let ExtractValueData::Array(items) = input {
+            let mut results = Vec::with_capacity(items.len());
+            for item in items.iter() {
+                let text = item.as_str().ok_or_else(|| {
+                    RuntimeError::Extraction("Regex requires string array elements".to_string())
+                })?;
+                let matched = Self::match_text(&re, text, capture, global);
+                if !matches!(matched.as_ref(), ExtractValueData::Null) {
+                    results.push(matched);
+                }
+            }
+            return Ok(Arc::new(ExtractValueData::Array(Arc::new(results))));
+        }
+
+        // 获取字符串
+        let text = input
+            .as_str()
+            .ok_or_else(|| RuntimeError::Extraction("Regex requires string input".to_string()))?;
+
+        Ok(Self::match_text(&re, text, capture, global))
+    }
 
+    /// 对单个字符串执行匹配，未匹配时返回 Null
+    fn match_text(re: &regex::Regex, text: &str, capture: Capture, global: bool) -> SharedValue {
         if global {
             // 全局匹配
             let matches: Vec<SharedValue> = re
                 .captures_iter(text)
-                .filter_map(|cap| {
-                    cap.get(group).map(|m| {
-                        Arc::new(ExtractValueData::String(Arc::from(
-                            m.as_str().to_string().into_boxed_str(),
-                        )))
-                    })
-                })
+                .filter_map(|cap| capture.extract(re, &cap))
                 .collect();
 
             if matches.is_empty() {
-                Ok(Arc::new(ExtractValueData::Null))
+                Arc::new(ExtractValueData::Null)
             } else {
-                Ok(Arc::new(ExtractValueData::Array(Arc::new(matches))))
+                Arc::new(ExtractValueData::Array(Arc::new(matches)))
             }
         } else {
             // 单次匹配
-            let result = re.captures(text).and_then(|cap| cap.get(group)).map(|m| {
-                Arc::new(ExtractValueData::String(Arc::from(
-                    m.as_str().to_string().into_boxed_str(),
-                )))
-            });
+            re.captures(text)
+                .and_then(|cap| capture.extract(re, &cap))
+                .unwrap_or_else(|| Arc::new(ExtractValueData::Null))
+        }
+    }
+}
 
-            match result {
-                Some(s) => Ok(s),
-                None => Ok(Arc::new(ExtractValueData::Null)),
-            }
+/// 单次匹配的取值方式
+#[derive(Clone, Copy)]
+struct Capture {
+    /// 捕获组索引
+    group: usize,
+    /// 是否返回命名捕获组对象
+    named: bool,
+}
+
+impl Capture {
+    /// 从一次匹配中取值，指定的捕获组未参与匹配时返回 `None`
+    fn extract(self, re: &regex::Regex, cap: &regex::Captures) -> Option<SharedValue> {
+        if self.named {
+            let object: Map<String, Value> = re
+                .capture_names()
+                .flatten()
+                .map(|name| {
+                    let value = cap
+                        .name(name)
+                        .map_or(Value::Null, |m| Value::String(m.as_str().to_string()));
+                    (name.to_string(), value)
+                })
+                .collect();
+            return Some(Arc::new(ExtractValueData::Json(Arc::new(Value::Object(
+                object,
+            )))));
         }
+
+        cap.get(self.group).map(|m| {
+            Arc::new(ExtractValueData::String(Arc::from(
+                m.as_str().to_string().into_boxed_str(),
+            )))
+        })
     }
 }
@@ -0,0 +1,357 @@
+//! # XPath 选择器执行器
+//!
+//! 将常用的 XPath 子集转换为等价的 CSS 选择器后执行，无需额外的 XML/JS 引擎。
+//!
+//! # 支持的语法
+//!
+//! | XPath | 对应 CSS |
+//! |-------|---------|
+//! | `//div` | `div`（任意层级） |
+//! | `/a` | `> a`（直接子元素） |
+//! | `*` | `*` |
+//! | `[@id]` | `[id]` |
+//! | `[@class='x']` | `[class="x"]` |
+//! | `[contains(@class,'x')]` | `[class*="x"]` |
+//! | `[starts-with(@href,'x')]` | `[href^="x"]` |
+//! | `[2]` / `[last()]` | `:nth-of-type(2)` / `:last-of-type` |
+//! | `/text()` | `::own_text`（直接子文本节点） |
+//! | `//text()` | `::text`（全部后代文本） |
+//! | `/@href` | `::attr(href)` |
+//!
+//! 轴（`ancestor::`、`.`、`..` 等）、联合（`|`）、其他函数、布尔与比较运算、
+//! 跟在其他谓词之后的位置谓词等无法等价转换的语法不受支持，转换时返回明确的错误，
+//! 规则加载阶段的选择器校验即会报告，而不会按近似的 CSS 执行。
+
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    error::RuntimeError,
+    extractor::{
+        selector::css::CssSelectorExecutor,
+        value::{ExtractValueData, SharedValue},
+    },
+};
+use crawler_schema::extract::SelectorStep;
+
+/// XPath 选择器执行器
+pub struct XpathExecutor;
+
+impl XpathExecutor {
+    /// 执行 XPath 选择器
+    pub fn execute(
+        selector: &SelectorStep,
+        input: &ExtractValueData,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<SharedValue> {
+        let css_selector = match selector {
+            SelectorStep::Simple(expr) => SelectorStep::Simple(Self::to_css(expr)?),
+            SelectorStep::WithOptions { expr, all } => SelectorStep::WithOptions {
+                expr: Self::to_css(expr)?,
+                all: *all,
+            },
+        };
+        CssSelectorExecutor::execute(&css_selector, input, runtime_context, flow_context)
+    }
+
+    /// 将 XPath 表达式转换为 CSS 选择器（可带 `::text`/`::attr()` 伪元素）
+    pub fn to_css(xpath: &str) -> Result<String> {
+        let steps = Self::split_steps(xpath.trim())?;
+        let last = steps.len() - 1;
+
+        let mut css = String::new();
+        let mut pseudo = None;
+
+        for (i, (descendant, step)) in steps.iter().enumerate() {
+            if step == "text()" || step.starts_with('@') {
+                if i != last {
+                    return Err(Self::error(xpath, "text() 与 @attr 只能出现在末尾"));
+                }
+                pseudo = Some(match step.as_str() {
+                    "text()" if *descendant => "::text".to_string(),
+                    "text()" => "::own_text".to_string(),
+                    _ if *descendant => {
+                        return Err(Self::error(xpath, "不支持后代属性 `//@attr`"));
+                    }
+                    _ => format!("::attr({})", Self::attr_name(xpath, step)?),
+                });
+                break;
+            }
+
+            if !css.is_empty() {
+                css.push_str(if *descendant { " " } else { " > " });
+            }
+            css.push_str(&Self::step_to_css(xpath, step)?);
+        }
+
+        if css.is_empty() {
+            return Err(Self::error(xpath, "缺少元素选择步骤"));
+        }
+        if let Some(pseudo) = pseudo {
+            css.push_str(&pseudo);
+        }
+        Ok(css)
+    }
+
+    /// 按 `/` 与 `//` 拆分步骤，返回 `(是否为后代轴, 步骤)`
+    ///
+    /// 第一个步骤总是相对当前片段按后代匹配
+    fn split_steps(xpath: &str) -> Result<Vec<(bool, String)>> {
+        let mut steps = Vec::new();
+        let mut current = String::new();
+        let mut descendant = true;
+        let mut depth = 0usize;
+        let mut quote: Option<char> = None;
+        let mut chars = xpath.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match (c, quote) {
+                (q, Some(open)) if q == open => {
+                    quote = None;
+                    current.push(c);
+                }
+                (_, Some(_)) => current.push(c),
+                ('\'' | '"', None) => {
+                    quote = Some(c);
+                    current.push(c);
+                }
+                ('[', None) => {
+                    depth += 1;
+                    current.push(c);
+                }
+                (']', None) => {
+                    depth = depth.saturating_sub(1);
+                    current.push(c);
+                }
+                ('/', None) if depth == 0 => {
+                    if !current.is_empty() {
+                        steps.push((descendant, std::mem::take(&mut current)));
+                    }
+                    descendant = chars.peek() == Some(&'/');
+                    if descendant {
+                        chars.next();
+                    }
+                }
+                _ => current.push(c),
+            }
+        }
+
+        if quote.is_some() || depth != 0 {
+            return Err(Self::error(xpath, "引号或方括号未闭合"));
+        }
+        if !current.is_empty() {
+            steps.push((descendant, current));
+        }
+        if steps.is_empty() {
+            return Err(Self::error(xpath, "表达式为空"));
+        }
+        if let Some(first) = steps.first_mut() {
+            first.0 = true;
+        }
+        Ok(steps)
+    }
+
+    /// 转换单个步骤（节点名 + 谓词）
+    fn step_to_css(xpath: &str, step: &str) -> Result<String> {
+        let (name, mut rest) = match step.find('[') {
+            Some(pos) => (&step[..pos], &step[pos..]),
+            None => (step, ""),
+        };
+        let name = name.trim();
+        if name.contains("::") || name == "." || name == ".." {
+            return Err(Self::error(xpath, &format!("不支持 XPath 轴 '{}'", name)));
+        }
+        if name.contains('|') {
+            return Err(Self::error(xpath, "不支持联合表达式 `|`"));
+        }
+        if name.contains('(') {
+            return Err(Self::error(xpath, &format!("不支持函数表达式 '{}'", name)));
+        }
+        if name != "*"
+            && (name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_')))
+        {
+            return Err(Self::error(xpath, &format!("无效的节点名 '{}'", name)));
+        }
+
+        let mut css = name.to_string();
+        let mut filtered = false;
+        while !rest.is_empty() {
+            let end =
+                Self::predicate_end(rest).ok_or_else(|| Self::error(xpath, "谓词方括号未闭合"))?;
+            let predicate = rest[1..end].trim();
+            let positional = predicate == "last()" || predicate.parse::<u32>().is_ok();
+            // `div[@a][2]` 是过滤后的第 2 个，CSS 的 `:nth-of-type` 无法表达
+            if positional && filtered {
+                return Err(Self::error(
+                    xpath,
+                    &format!("位置谓词 '[{}]' 必须写在其他谓词之前", predicate),
+                ));
+            }
+            filtered |= !positional;
+            css.push_str(&Self::predicate_to_css(xpath, name, predicate)?);
+            rest = rest[end + 1..].trim_start();
+            if !rest.is_empty() && !rest.starts_with('[') {
+                return Err(Self::error(xpath, &format!("无法解析的步骤 '{}'", step)));
+            }
+        }
+        Ok(css)
+    }
+
+    /// 查找与开头 `[` 匹配的 `]` 位置（忽略引号内内容）
+    fn predicate_end(s: &str) -> Option<usize> {
+        let mut quote: Option<char> = None;
+        for (i, c) in s.char_indices().skip(1) {
+            match (c, quote) {
+                (q, Some(open)) if q == open => quote = None,
+                (_, Some(_)) => {}
+                ('\'' | '"', None) => quote = Some(c),
+                (']', None) => return Some(i),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// 转换单个谓词
+    fn predicate_to_css(xpath: &str, name: &str, predicate: &str) -> Result<String> {
+        // 位置谓词
+        if let Ok(n) = predicate.parse::<u32>() {
+            return Ok(if name == "*" {
+                format!(":nth-child({})", n)
+            } else {
+                format!(":nth-of-type({})", n)
+            });
+        }
+        if predicate == "last()" {
+            return Ok(if name == "*" {
+                ":last-child".to_string()
+            } else {
+                ":last-of-type".to_string()
+            });
+        }
+
+        if let Some(reason) = Self::unsupported_predicate(predicate) {
+            return Err(Self::error(
+                xpath,
+                &format!("不支持的谓词 '[{}]': {}", predicate, reason),
+            ));
+        }
+
+        // 函数谓词：contains(@a,'v') / starts-with(@a,'v')
+        for (func, op) in [("contains", "*="), ("starts-with", "^=")] {
+            if let Some(args) = predicate
+                .strip_prefix(func)
+                .map(str::trim_start)
+                .and_then(|s| s.strip_prefix('('))
+                .and_then(|s| s.strip_suffix(')'))
+            {
+                let (attr, value) = args
+                    .split_once(',')
+                    .ok_or_else(|| Self::error(xpath, &format!("{}() 需要两个参数", func)))?;
+                if !attr.trim().starts_with('@') {
+                    return Err(Self::error(
+                        xpath,
+                        &format!(
+                            "{}() 的第一个参数只支持 @属性，不支持 '{}'",
+                            func,
+                            attr.trim()
+                        ),
+                    ));
+                }
+                let attr = Self::attr_name(xpath, attr)?;
+                let value = Self::literal(xpath, value)?;
+                return Ok(format!("[{}{}\"{}\"]", attr, op, value));
+            }
+        }
+
+        // 属性谓词：@a 或 @a='v'
+        if predicate.starts_with('@') {
+            return match predicate.split_once('=') {
+                Some((attr, value)) => Ok(format!(
+                    "[{}=\"{}\"]",
+                    Self::attr_name(xpath, attr)?,
+                    Self::literal(xpath, value)?
+                )),
+                None => Ok(format!("[{}]", Self::attr_name(xpath, predicate)?)),
+            };
+        }
+
+        Err(Self::error(
+            xpath,
+            &format!(
+                "不支持的谓词 '[{}]': 仅支持位置、last()、@属性、contains() 与 starts-with()",
+                predicate
+            ),
+        ))
+    }
+
+    /// 检查谓词中无法转换为 CSS 的语法（忽略引号内内容），返回原因
+    fn unsupported_predicate(predicate: &str) -> Option<&'static str> {
+        let mut code = String::new();
+        let mut quote: Option<char> = None;
+        for c in predicate.chars() {
+            match (c, quote) {
+                (q, Some(open)) if q == open => quote = None,
+                (_, Some(_)) => {}
+                ('\'' | '"', None) => {
+                    quote = Some(c);
+                    code.push_str("''");
+                }
+                _ => code.push(c),
+            }
+        }
+
+        let words = code.split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_')));
+        if words.clone().any(|w| matches!(w, "and" | "or")) {
+            return Some("不支持布尔运算");
+        }
+        if code.contains("!=") || code.contains('<') || code.contains('>') {
+            return Some("仅支持 `=` 比较");
+        }
+        if code.contains("text()") || code.contains("node()") || code.contains('.') {
+            return Some("谓词中只能引用 @属性，不支持 text() 等节点");
+        }
+        let calls = code.matches('(').count();
+        let allowed = ["contains(", "starts-with(", "last()"]
+            .iter()
+            .filter(|f| code.trim_start().starts_with(*f))
+            .count();
+        if calls > allowed {
+            return Some("仅支持 contains()、starts-with() 与 last() 函数");
+        }
+        None
+    }
+
+    /// 解析 `@name`
+    fn attr_name<'a>(xpath: &str, s: &'a str) -> Result<&'a str> {
+        let name = s
+            .trim()
+            .strip_prefix('@')
+            .ok_or_else(|| Self::error(xpath, &format!("期望属性引用，实际为 '{}'", s.trim())))?;
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':'))
+        {
+            return Err(Self::error(xpath, &format!("无效的属性名 '{}'", name)));
+        }
+        Ok(name)
+    }
+
+    /// 解析带引号的字符串字面量
+    fn literal<'a>(xpath: &str, s: &'a str) -> Result<&'a str> {
+        let s = s.trim();
+        s.strip_prefix('\'')
+            .and_then(|v| v.strip_suffix('\''))
+            .or_else(|| s.strip_prefix('"').and_then(|v| v.strip_suffix('"')))
+            .filter(|v| !v.contains('"'))
+            .ok_or_else(|| Self::error(xpath, &format!("无效的字符串字面量 {}", s)))
+    }
+
+    fn error(xpath: &str, reason: &str) -> RuntimeError {
+        RuntimeError::Extraction(format!("Unsupported XPath '{}': {}", xpath, reason))
+    }
+}
@@ -0,0 +1,811 @@
+//! # 选择器语法校验
+//!
+//! 在规则加载时预编译所有 CSS 选择器和 JSONPath 表达式，
+//! 使语法错误在加载阶段而非提取阶段暴露；并检查字段与媒体类型是否匹配、组件是否被引用。
+
+use crate::{
+    Result,
+    error::RuntimeError,
+    extractor::{
+        filter::executor::FilterExecutor,
+        selector::{
+            condition::ConditionExecutor,
+            css::CssSelectorExecutor,
+            json::{JsonPointerExecutor, JsonSelectorExecutor},
+            xpath::XpathExecutor,
+        },
+        visit::{RuleWalker, StepVisitor},
+    },
+    flow::suggest::SuggestFlowExecutor,
+    http::HttpClient,
+    template::TemplateExt,
+};
+use crawler_schema::{
+    config::HttpConfig,
+    core::CrawlerRule,
+    extract::{ExtractStep, FieldExtractor, SelectorStep, VarContext},
+    fields::{ContentFields, DetailFields},
+    flow::ComponentRef,
+    script::{ScriptEngine, ScriptSource},
+    template::Template,
+};
+use encoding_rs::Encoding;
+use jsonpath_rust::JsonPath;
+use regex::Regex;
+use scraper::Selector;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// 常用编码标签，用于拼写错误时给出建议
+const COMMON_ENCODINGS: &[&str] = &[
+    "utf-8",
+    "gbk",
+    "gb2312",
+    "gb18030",
+    "big5",
+    "shift_jis",
+    "euc-jp",
+    "euc-kr",
+    "iso-8859-1",
+    "windows-1252",
+];
+
+/// 诊断级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// 错误：规则无法加载
+    Error,
+    /// 警告：仅作提示，不影响加载
+    Warning,
+}
+
+/// 校验诊断信息
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// 诊断级别
+    pub severity: Severity,
+    /// 具体错误
+    pub error: RuntimeError,
+}
+
+/// 校验报告
+///
+/// 同时收集错误与警告，`into_result` 仅在存在错误时返回 `Err`
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    /// 添加错误
+    pub fn push_error(&mut self, error: RuntimeError) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            error,
+        });
+    }
+
+    /// 添加警告
+    pub fn push_warning(&mut self, error: RuntimeError) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            error,
+        });
+    }
+
+    /// 全部诊断信息（错误与警告）
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// 所有错误
+    pub fn errors(&self) -> impl Iterator<Item = &RuntimeError> {
+        self.by_severity(Severity::Error)
+    }
+
+    /// 所有警告
+    pub fn warnings(&self) -> impl Iterator<Item = &RuntimeError> {
+        self.by_severity(Severity::Warning)
+    }
+
+    /// 是否存在错误
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// 转换为 Result，存在错误时返回第一个错误
+    pub fn into_result(self) -> Result<()> {
+        match self
+            .diagnostics
+            .into_iter()
+            .find(|d| d.severity == Severity::Error)
+        {
+            Some(d) => Err(d.error),
+            None => Ok(()),
+        }
+    }
+
+    fn by_severity(&self, severity: Severity) -> impl Iterator<Item = &RuntimeError> {
+        self.diagnostics
+            .iter()
+            .filter(move |d| d.severity == severity)
+            .map(|d| &d.error)
+    }
+}
+
+/// 选择器校验器
+pub struct SelectorValidator;
+
+impl SelectorValidator {
+    /// 校验规则中所有提取器的选择器语法与结果校验正则
+    ///
+    /// 返回第一个语法错误，错误中的 `field` 为步骤路径（如 `search.fields.title.steps[0]`）
+    pub fn validate_rule(rule: &CrawlerRule) -> Result<()> {
+        RuleWalker::walk_rule(rule, &mut FailFastVisitor)
+    }
+
+    /// 完整校验规则，收集所有错误与警告
+    ///
+    /// - 错误：选择器语法错误、模板语法错误或未知的模板过滤器、字段定义与 `meta.media_type`
+    ///   不匹配、无法识别的 `meta.encoding`、无效的 `resolve` 地址、搜索建议的请求未引用
+    ///   `keyword`、 脚本引用了同一步骤序列中稍后才定义的变量
+    /// - 警告：未被引用的组件、写入后从未读取的流程变量
+    pub fn check_rule(rule: &CrawlerRule) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        if let Err(e) = Self::validate_media_type(rule) {
+            report.push_error(e);
+        }
+
+        if let Err(e) = Self::validate_encoding(rule) {
+            report.push_error(e);
+        }
+
+        for error in Self::validate_templates(rule) {
+            report.push_error(error);
+        }
+
+        for error in Self::validate_resolve(rule) {
+            report.push_error(error);
+        }
+
+        if let Some(suggest) = &rule.suggest
+            && let Err(e) = SuggestFlowExecutor::validate(suggest)
+        {
+            report.push_error(e);
+        }
+
+        let _ = RuleWalker::walk_rule(rule, &mut ReportingVisitor(&mut report));
+
+        for error in Self::use_before_definition(rule) {
+            report.push_error(error);
+        }
+
+        for component in Self::unused_components(rule) {
+            report.push_warning(RuntimeError::UnusedComponent { component });
+        }
+
+        for (name, path) in Self::unused_variables(rule) {
+            report.push_warning(RuntimeError::UnusedVariable { name, path });
+        }
+
+        report
+    }
+
+    /// 收集规则中需要渲染的模板（URL、请求体、请求头），返回 `(字段路径, 模板)`
+    pub fn rule_templates(rule: &CrawlerRule) -> Vec<(String, &Template)> {
+        let mut templates = vec![
+            ("search.url".to_string(), &rule.search.url),
+            ("detail.url".to_string(), &rule.detail.url),
+        ];
+        if let Some(suggest) = &rule.suggest {
+            templates.push(("suggest.url".to_string(), &suggest.url));
+        }
+        if let Some(discovery) = &rule.discovery {
+            templates.push(("discovery.url".to_string(), &discovery.url));
+        }
+        if let Some(content) = &rule.content {
+            templates.push(("content.url".to_string(), &content.url));
+        }
+
+        for (prefix, http) in Self::http_configs(rule) {
+            let Some(request) = http.and_then(|h| h.request.as_ref()) else {
+                continue;
+            };
+            if let Some(body) = &request.body {
+                templates.push((format!("{}.request.body", prefix), body));
+            }
+            for (key, value) in request.headers.iter().flatten() {
+                templates.push((format!("{}.request.headers.{}", prefix, key), value));
+            }
+            for (key, value) in request.form.iter().flatten() {
+                templates.push((format!("{}.request.form.{}", prefix, key), value));
+            }
+        }
+
+        templates
+    }
+
+    /// 规则中的全局与各流程 HTTP 配置，返回 `(字段路径, 配置)`
+    fn http_configs(rule: &CrawlerRule) -> [(&'static str, Option<&HttpConfig>); 6] {
+        [
+            ("http", rule.http.as_ref()),
+            ("search.http", rule.search.http.as_ref()),
+            (
+                "suggest.http",
+                rule.suggest.as_ref().and_then(|f| f.http.as_ref()),
+            ),
+            (
+                "discovery.http",
+                rule.discovery.as_ref().and_then(|f| f.http.as_ref()),
+            ),
+            ("detail.http", rule.detail.http.as_ref()),
+            (
+                "content.http",
+                rule.content.as_ref().and_then(|f| f.http.as_ref()),
+            ),
+        ]
+    }
+
+    /// 校验各 HTTP 配置中 `resolve` 的地址（IP 或 `IP:端口`）
+    pub fn validate_resolve(rule: &CrawlerRule) -> Vec<RuntimeError> {
+        Self::http_configs(rule)
+            .into_iter()
+            .filter_map(|(prefix, http)| Some((prefix, http?.resolve.as_ref()?)))
+            .flat_map(|(prefix, resolve)| {
+                resolve.iter().filter_map(move |(host, addr)| {
+                    HttpClient::resolve_addr(addr).err().map(|reason| {
+                        RuntimeError::InvalidConfigValue {
+                            field: format!("{}.resolve.{}", prefix, host),
+                            reason,
+                        }
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// 校验规则中所有模板的语法与过滤器名称（含 `coalesce` 步骤中的模板）
+    pub fn validate_templates(rule: &CrawlerRule) -> Vec<RuntimeError> {
+        Self::rule_templates(rule)
+            .into_iter()
+            .filter_map(|(field, template)| {
+                template
+                    .validate()
+                    .err()
+                    .map(|e| Self::template_error(field, e))
+            })
+            .collect()
+    }
+
+    /// 将模板校验错误包装为带字段路径的配置错误
+    fn template_error(field: String, error: RuntimeError) -> RuntimeError {
+        RuntimeError::InvalidConfigValue {
+            field,
+            reason: error.to_string(),
+        }
+    }
+
+    /// 查找已定义但从未被 `use_component` 引用的组件
+    ///
+    /// 结果仅作为提示，不影响规则加载
+    pub fn unused_components(rule: &CrawlerRule) -> Vec<String> {
+        let Some(components) = &rule.components else {
+            return Vec::new();
+        };
+
+        let mut referenced = HashSet::new();
+        let _ = RuleWalker::walk_rule(rule, &mut |_: &str, step: &ExtractStep| {
+            if let ExtractStep::UseComponent(component) = step {
+                let name = match component {
+                    ComponentRef::Simple(name) => name,
+                    ComponentRef::WithArgs { name, .. } => name,
+                };
+                referenced.insert(name.clone());
+            }
+            Ok(())
+        });
+
+        let mut unused: Vec<String> = components
+            .keys()
+            .filter(|name| !referenced.contains(*name))
+            .cloned()
+            .collect();
+        unused.sort();
+        unused
+    }
+
+    /// 查找通过 `set_var` 写入流程上下文、但规则中其他位置从未引用的变量
+    ///
+    /// 返回 `(变量名, 写入步骤路径)`。写入 `runtime` 上下文的变量会跨流程保留、
+    /// 也可能由调用方读取，因此不在检查范围内。
+    /// 引用来自规则中所有模板解析出的变量、内联脚本中的标识符以及
+    /// 以变量路径为参数的过滤器（如 `zip`）。
+    pub fn unused_variables(rule: &CrawlerRule) -> Vec<(String, String)> {
+        let mut writes: Vec<(String, String)> = Vec::new();
+        let _ = RuleWalker::walk_rule(rule, &mut |path: &str, step: &ExtractStep| {
+            if let ExtractStep::SetVar(set_var) = step
+                && matches!(set_var.context, VarContext::Flow)
+            {
+                writes.push((set_var.name.clone(), path.to_string()));
+            }
+            Ok(())
+        });
+        if writes.is_empty() {
+            return Vec::new();
+        }
+
+        let referenced = Self::referenced_variables(rule);
+        let mut unused: Vec<(String, String)> = writes
+            .into_iter()
+            .filter(|(name, _)| !referenced.contains(name))
+            .collect();
+        unused.sort();
+        unused
+    }
+
+    /// 规则中读取的变量名（路径首段）
+    fn referenced_variables(rule: &CrawlerRule) -> HashSet<String> {
+        let mut references = HashSet::new();
+        if let Ok(value) = serde_json::to_value(rule) {
+            collect_value_references(&value, &mut references);
+        }
+        let _ = RuleWalker::walk_rule(rule, &mut |_: &str, step: &ExtractStep| {
+            if let ExtractStep::Filter(filter) = step {
+                for path in FilterExecutor::variable_args(filter) {
+                    let root = path.split(['.', '[']).next().unwrap_or(&path);
+                    references.insert(root.trim().to_string());
+                }
+            }
+            Ok(())
+        });
+        references
+    }
+
+    /// 查找在同一步骤序列中先引用、后定义的变量
+    ///
+    /// 步骤按顺序执行，若脚本或 `coalesce` 模板引用的变量由同一序列中**之后**的 `set_var` 写入，
+    /// 执行到该步骤时变量尚不存在，属于步骤顺序错误。完全未在序列中定义的变量
+    /// 视为外部变量（流程注入或其他字段写入），不在检查范围内。
+    ///
+    /// 返回 `InvalidConfigValue`，`field` 为引用步骤的路径，原因中包含定义步骤的索引。
+    pub fn use_before_definition(rule: &CrawlerRule) -> Vec<RuntimeError> {
+        let mut visitor = DefinitionOrderVisitor(Vec::new());
+        let _ = RuleWalker::walk_rule(rule, &mut visitor);
+        visitor.0
+    }
+
+    /// 检查单个步骤序列（并递归检查 `map`、`condition` 的子序列）
+    fn check_definition_order(path: &str, steps: &[ExtractStep], errors: &mut Vec<RuntimeError>) {
+        // 变量名 → 首次定义的步骤索引
+        let mut definitions: HashMap<&str, usize> = HashMap::new();
+        for (i, step) in steps.iter().enumerate() {
+            if let ExtractStep::SetVar(set_var) = step {
+                definitions.entry(set_var.name.as_str()).or_insert(i);
+            }
+        }
+
+        for (i, step) in steps.iter().enumerate() {
+            let step_path = format!("{}[{}]", path, i);
+
+            let mut references = HashSet::new();
+            Self::collect_var_references(step, &mut references);
+            for (name, defined_at) in &definitions {
+                if *defined_at > i && references.contains(*name) {
+                    errors.push(RuntimeError::InvalidConfigValue {
+                        field: step_path.clone(),
+                        reason: format!(
+                            "变量 '{}' 在步骤 [{}] 中被引用，但直到步骤 [{}] 才定义",
+                            name, i, defined_at
+                        ),
+                    });
+                }
+            }
+
+            match step {
+                ExtractStep::Map(map) => {
+                    Self::check_definition_order(
+                        &format!("{}.map", step_path),
+                        map.steps(),
+                        errors,
+                    );
+                }
+                ExtractStep::Condition(condition) => {
+                    let branches = [
+                        ("when", Some(&condition.when)),
+                        ("then", Some(&condition.then)),
+                        ("otherwise", condition.otherwise.as_ref()),
+                    ];
+                    for (branch, sub_steps) in branches {
+                        if let Some(sub_steps) = sub_steps {
+                            Self::check_definition_order(
+                                &format!("{}.{}", step_path, branch),
+                                sub_steps,
+                                errors,
+                            );
+                        }
+                    }
+                }
+                ExtractStep::Merge(fields) => {
+                    for (key, sub_steps) in fields {
+                        Self::check_definition_order(
+                            &format!("{}.merge.{}", step_path, key),
+                            sub_steps,
+                            errors,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 收集步骤（含嵌套子步骤）引用的变量名
+    ///
+    /// `coalesce` 模板取解析后的变量引用；内联脚本不是模板，按标识符词法扫描
+    fn collect_var_references(step: &ExtractStep, references: &mut HashSet<String>) {
+        match step {
+            ExtractStep::Script(script) => {
+                if let ScriptSource::Code(code) = script.source() {
+                    references.extend(script_identifiers(code, script.engine.unwrap_or_default()));
+                }
+            }
+            ExtractStep::Coalesce(inputs) => {
+                for template in inputs {
+                    // 语法错误由模板校验报告
+                    if let Ok(variables) = template.variables() {
+                        references.extend(variables);
+                    }
+                }
+            }
+            ExtractStep::Map(map) => {
+                for sub_step in map.steps() {
+                    Self::collect_var_references(sub_step, references);
+                }
+            }
+            ExtractStep::Condition(condition) => {
+                for sub_step in condition
+                    .when
+                    .iter()
+                    .chain(&condition.then)
+                    .chain(condition.otherwise.iter().flatten())
+                {
+                    Self::collect_var_references(sub_step, references);
+                }
+            }
+            ExtractStep::Merge(fields) => {
+                for sub_step in fields.values().flatten() {
+                    Self::collect_var_references(sub_step, references);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 校验详情页/内容页字段与 `meta.media_type` 是否一致
+    ///
+    /// 字段形状错误时 serde 的报错难以理解，这里给出期望的字段形状
+    pub fn validate_media_type(rule: &CrawlerRule) -> Result<()> {
+        let expected = rule.meta.media_type;
+
+        let actual = rule.detail.fields.media_type();
+        if actual != expected {
+            return Err(RuntimeError::InvalidConfigValue {
+                field: "detail.fields".to_string(),
+                reason: format!(
+                    "规则媒体类型为{}，但详情字段按{}定义；期望: {}",
+                    expected.display_name(),
+                    actual.display_name(),
+                    DetailFields::expected_shape(expected)
+                ),
+            });
+        }
+
+        if let Some(content) = &rule.content {
+            let actual = content.fields.media_type();
+            if actual != expected {
+                return Err(RuntimeError::InvalidConfigValue {
+                    field: "content.fields".to_string(),
+                    reason: format!(
+                        "规则媒体类型为{}，但内容字段按{}定义；期望: {}",
+                        expected.display_name(),
+                        actual.display_name(),
+                        ContentFields::expected_shape(expected)
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 校验 `meta.encoding` 是否为可识别的编码标签
+    ///
+    /// 拼写接近常用编码时在错误中给出建议，如 `gb2313` → `gb2312`
+    pub fn validate_encoding(rule: &CrawlerRule) -> Result<()> {
+        let Some(label) = rule.meta.encoding.as_deref() else {
+            return Ok(());
+        };
+        if Encoding::for_label(label.trim().as_bytes()).is_some() {
+            return Ok(());
+        }
+
+        let input = label.trim().to_ascii_lowercase();
+        let suggestion = COMMON_ENCODINGS
+            .iter()
+            .map(|candidate| (edit_distance(&input, candidate), *candidate))
+            .filter(|(distance, _)| *distance <= 2)
+            .min_by_key(|(distance, _)| *distance);
+
+        Err(RuntimeError::InvalidConfigValue {
+            field: "meta.encoding".to_string(),
+            reason: match suggestion {
+                Some((_, candidate)) => {
+                    format!("无法识别的编码 '{}'，是否为 '{}'？", label, candidate)
+                }
+                None => format!("无法识别的编码 '{}'", label),
+            },
+        })
+    }
+
+    /// 校验字段提取器的 `validate` 正则
+    fn validate_extractor(path: &str, extractor: &FieldExtractor) -> Result<()> {
+        if let Some(pattern) = &extractor.validate {
+            Regex::new(pattern).map_err(|e| RuntimeError::InvalidConfigValue {
+                field: format!("{}.validate", path),
+                reason: format!("无效的正则 '{}': {}", pattern, e),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// 校验单个步骤的选择器语法
+    fn validate_step(path: &str, step: &ExtractStep) -> Result<()> {
+        match step {
+            ExtractStep::Css(selector) => {
+                let (expr, _) = CssSelectorExecutor::split_pseudo(Self::selector_expr(selector))
+                    .map_err(|e| RuntimeError::InvalidConfigValue {
+                        field: path.to_string(),
+                        reason: e.to_string(),
+                    })?;
+                Selector::parse(expr).map_err(|e| RuntimeError::InvalidConfigValue {
+                    field: path.to_string(),
+                    reason: format!("无效的 CSS 选择器 '{}': {:?}", expr, e),
+                })?;
+            }
+            ExtractStep::Xpath(selector) => {
+                let css = XpathExecutor::to_css(Self::selector_expr(selector)).map_err(|e| {
+                    RuntimeError::InvalidConfigValue {
+                        field: path.to_string(),
+                        reason: e.to_string(),
+                    }
+                })?;
+                Self::validate_step(path, &ExtractStep::Css(SelectorStep::Simple(css)))?;
+            }
+            ExtractStep::JsonPointer(pointer) => {
+                JsonPointerExecutor::validate(pointer).map_err(|e| {
+                    RuntimeError::InvalidConfigValue {
+                        field: path.to_string(),
+                        reason: e.to_string(),
+                    }
+                })?;
+            }
+            ExtractStep::Json(selector) => {
+                let expr = Self::selector_expr(selector);
+                // JSON Pointer 只需以 `/` 开头，无需额外校验
+                if !JsonSelectorExecutor::is_pointer(expr) {
+                    Value::Null
+                        .query(expr)
+                        .map_err(|e| RuntimeError::InvalidConfigValue {
+                            field: path.to_string(),
+                            reason: format!("无效的 JSONPath '{}': {}", expr, e),
+                        })?;
+                }
+            }
+            ExtractStep::Coalesce(inputs) => {
+                for (i, template) in inputs.iter().enumerate() {
+                    template.validate().map_err(|e| {
+                        Self::template_error(format!("{}.coalesce[{}]", path, i), e)
+                    })?;
+                }
+            }
+            ExtractStep::Condition(condition) => {
+                if let Some(pattern) = ConditionExecutor::match_pattern(condition) {
+                    Regex::new(&pattern).map_err(|e| RuntimeError::InvalidConfigValue {
+                        field: format!("{}.value", path),
+                        reason: format!("无效的正则 '{}': {}", pattern, e),
+                    })?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// 获取选择器表达式
+    fn selector_expr(selector: &SelectorStep) -> &str {
+        match selector {
+            SelectorStep::Simple(s) => s.as_str(),
+            SelectorStep::WithOptions { expr, .. } => expr.as_str(),
+        }
+    }
+}
+
+/// 遇到第一个错误即停止的校验访问者
+struct FailFastVisitor;
+
+impl StepVisitor for FailFastVisitor {
+    fn visit_step(&mut self, path: &str, step: &ExtractStep) -> Result<()> {
+        SelectorValidator::validate_step(path, step)
+    }
+
+    fn visit_extractor(&mut self, path: &str, extractor: &FieldExtractor) -> Result<()> {
+        SelectorValidator::validate_extractor(path, extractor)
+    }
+}
+
+/// 将所有错误收集到报告中的校验访问者
+struct ReportingVisitor<'a>(&'a mut ValidationReport);
+
+impl StepVisitor for ReportingVisitor<'_> {
+    fn visit_step(&mut self, path: &str, step: &ExtractStep) -> Result<()> {
+        if let Err(e) = SelectorValidator::validate_step(path, step) {
+            self.0.push_error(e);
+        }
+        Ok(())
+    }
+
+    fn visit_extractor(&mut self, path: &str, extractor: &FieldExtractor) -> Result<()> {
+        if let Err(e) = SelectorValidator::validate_extractor(path, extractor) {
+            self.0.push_error(e);
+        }
+        Ok(())
+    }
+}
+
+/// 检查步骤序列中变量定义顺序的访问者
+struct DefinitionOrderVisitor(Vec<RuntimeError>);
+
+impl StepVisitor for DefinitionOrderVisitor {
+    fn visit_step(&mut self, _path: &str, _step: &ExtractStep) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_extractor(&mut self, path: &str, extractor: &FieldExtractor) -> Result<()> {
+        SelectorValidator::check_definition_order(
+            &format!("{}.steps", path),
+            &extractor.steps,
+            &mut self.0,
+        );
+        for (i, steps) in extractor.fallback.iter().flatten().enumerate() {
+            SelectorValidator::check_definition_order(
+                &format!("{}.fallback[{}]", path, i),
+                steps,
+                &mut self.0,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// 收集序列化规则中的变量引用
+///
+/// 内联脚本（含 `code` 的对象）按标识符扫描，其余字符串按模板解析
+fn collect_value_references(value: &Value, references: &mut HashSet<String>) {
+    match value {
+        Value::String(text) if text.contains("{{") || text.contains("{%") => {
+            // 语法错误由模板校验报告
+            if let Ok(variables) = Template::new(text.as_str()).variables() {
+                references.extend(variables);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_value_references(item, references);
+            }
+        }
+        Value::Object(map) => {
+            if let Some(Value::String(code)) = map.get("code") {
+                let engine = map
+                    .get("engine")
+                    .and_then(|engine| serde_json::from_value(engine.clone()).ok())
+                    .unwrap_or_default();
+                references.extend(script_identifiers(code, engine));
+            }
+            for (key, item) in map {
+                if key != "code" {
+                    collect_value_references(item, references);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 脚本中作为变量读取的标识符
+///
+/// 跳过字符串字面量、注释与属性访问（`obj.name` 中的 `name`），
+/// 避免把字符串内容或对象字段误认为变量引用
+fn script_identifiers(code: &str, engine: ScriptEngine) -> HashSet<String> {
+    let line_comment = match engine {
+        ScriptEngine::JavaScript | ScriptEngine::Rhai => "//",
+        ScriptEngine::Lua => "--",
+        ScriptEngine::Python => "#",
+    };
+    let block_comment = match engine {
+        ScriptEngine::JavaScript | ScriptEngine::Rhai => Some(("/*", "*/")),
+        ScriptEngine::Lua => Some(("--[[", "]]")),
+        ScriptEngine::Python => None,
+    };
+
+    let mut identifiers = HashSet::new();
+    let mut rest = code;
+    let mut after_dot = false;
+    while let Some(c) = rest.chars().next() {
+        if let Some((open, close)) = block_comment
+            && rest.starts_with(open)
+        {
+            rest = rest[open.len()..]
+                .find(close)
+                .map_or("", |end| &rest[open.len() + end + close.len()..]);
+            continue;
+        }
+        if rest.starts_with(line_comment) {
+            rest = rest.find('\n').map_or("", |end| &rest[end..]);
+            continue;
+        }
+        if matches!(c, '"' | '\'' | '`') {
+            let mut escaped = false;
+            let end = rest[1..].char_indices().find_map(|(i, ch)| {
+                let closed = !escaped && ch == c;
+                escaped = !escaped && ch == '\\';
+                closed.then_some(i + 2)
+            });
+            rest = end.map_or("", |end| &rest[end..]);
+            after_dot = false;
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' || c == '$' {
+            let end = rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_' || ch == '$'))
+                .unwrap_or(rest.len());
+            if !after_dot {
+                identifiers.insert(rest[..end].to_string());
+            }
+            rest = &rest[end..];
+            after_dot = false;
+            continue;
+        }
+        if c.is_ascii_digit() {
+            // 数字字面量（含 `1.5`、`0x1f`）整体跳过
+            let end = rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '.' || ch == '_'))
+                .unwrap_or(rest.len());
+            rest = &rest[end..];
+            after_dot = false;
+            continue;
+        }
+        if !c.is_whitespace() {
+            after_dot = c == '.';
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    identifiers
+}
+
+/// 编辑距离（Levenshtein）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+
+    prev[b.len()]
+}
@@ -4,7 +4,13 @@
 
 use serde::{Deserialize, Serialize, ser::SerializeSeq};
 use serde_json::Value;
-use std::sync::Arc;
+use std::{fmt::Write, sync::Arc};
+
+/// `describe_shape` 展开的最大嵌套层数
+const SHAPE_MAX_DEPTH: usize = 2;
+
+/// `describe_shape` 列出的最大对象键数
+const SHAPE_MAX_KEYS: usize = 8;
 
 /// 共享的提取值（使用 Arc 实现廉价克隆）
 pub type SharedValue = Arc<ExtractValueData>;
@@ -116,6 +122,142 @@ impl ExtractValueData {
             },
         }
     }
+
+    /// 合并两个提取结果
+    ///
+    /// 用于组合多次请求的部分提取结果：
+    /// - 对象 + 对象：键并集，同名键以 `other` 为准
+    /// - 数组 + 数组：按顺序拼接
+    /// - 任一侧为 Null：返回另一侧
+    /// - 其他组合：返回错误
+    pub fn merge(&self, other: &Self) -> crate::Result<Self> {
+        match (self, other) {
+            (Self::Null, _) => Ok(other.clone()),
+            (_, Self::Null) => Ok(self.clone()),
+            (Self::Array(a), Self::Array(b)) => {
+                let mut items = Vec::with_capacity(a.len() + b.len());
+                items.extend(a.iter().cloned());
+                items.extend(b.iter().cloned());
+                Ok(Self::Array(Arc::new(items)))
+            }
+            (Self::Json(a), Self::Json(b)) => match (a.as_ref(), b.as_ref()) {
+                (Value::Object(a), Value::Object(b)) => {
+                    let mut merged = a.clone();
+                    for (k, v) in b {
+                        merged.insert(k.clone(), v.clone());
+                    }
+                    Ok(Self::Json(Arc::new(Value::Object(merged))))
+                }
+                (Value::Array(a), Value::Array(b)) => {
+                    let merged: Vec<Value> = a.iter().chain(b.iter()).cloned().collect();
+                    Ok(Self::Json(Arc::new(Value::Array(merged))))
+                }
+                _ => Err(crate::error::RuntimeError::Extraction(format!(
+                    "无法合并 {} 与 {}",
+                    self.kind_name(),
+                    other.kind_name()
+                ))),
+            },
+            (Self::Array(_), Self::Json(_)) | (Self::Json(_), Self::Array(_)) => {
+                let a = self.to_owned_json();
+                let b = other.to_owned_json();
+                Self::from(a).merge(&Self::from(b))
+            }
+            _ => Err(crate::error::RuntimeError::Extraction(format!(
+                "无法合并 {} 与 {}",
+                self.kind_name(),
+                other.kind_name()
+            ))),
+        }
+    }
+
+    /// 生成值的结构摘要（用于调试）
+    ///
+    /// 只描述类型与规模，不输出具体内容，如 `Array[12] of Object{title,url}`、
+    /// `String(42)`。数组按首个元素描述元素结构，最多展开两层，更深处以 `…` 表示。
+    pub fn describe_shape(&self) -> String {
+        let mut out = String::new();
+        self.write_shape(&mut out, 0);
+        out
+    }
+
+    fn write_shape(&self, out: &mut String, depth: usize) {
+        match self {
+            Self::String(s) => {
+                let _ = write!(out, "String({})", s.len());
+            }
+            Self::Html(h) => {
+                let _ = write!(out, "Html({})", h.len());
+            }
+            Self::Null => out.push_str("Null"),
+            Self::Json(v) => write_json_shape(v, out, depth),
+            Self::Array(items) => {
+                let _ = write!(out, "Array[{}]", items.len());
+                if let Some(first) = items.first() {
+                    out.push_str(" of ");
+                    if depth >= SHAPE_MAX_DEPTH {
+                        out.push('…');
+                    } else {
+                        first.write_shape(out, depth + 1);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 获取类型名称（用于错误信息）
+    fn kind_name(&self) -> &'static str {
+        match self {
+            Self::String(_) => "string",
+            Self::Html(_) => "html",
+            Self::Array(_) => "array",
+            Self::Null => "null",
+            Self::Json(v) => match v.as_ref() {
+                Value::Object(_) => "object",
+                Value::Array(_) => "array",
+                Value::String(_) => "string",
+                Value::Number(_) => "number",
+                Value::Bool(_) => "bool",
+                Value::Null => "null",
+            },
+        }
+    }
+}
+
+/// 写入 JSON 值的结构摘要
+fn write_json_shape(value: &Value, out: &mut String, depth: usize) {
+    match value {
+        Value::Null => out.push_str("Null"),
+        Value::Bool(_) => out.push_str("Bool"),
+        Value::Number(_) => out.push_str("Number"),
+        Value::String(s) => {
+            let _ = write!(out, "String({})", s.len());
+        }
+        Value::Array(items) => {
+            let _ = write!(out, "Array[{}]", items.len());
+            if let Some(first) = items.first() {
+                out.push_str(" of ");
+                if depth >= SHAPE_MAX_DEPTH {
+                    out.push('…');
+                } else {
+                    write_json_shape(first, out, depth + 1);
+                }
+            }
+        }
+        Value::Object(map) => {
+            out.push_str("Object{");
+            for (i, key) in map.keys().take(SHAPE_MAX_KEYS).enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(key);
+            }
+            if map.len() > SHAPE_MAX_KEYS {
+                let _ = write!(out, ",…+{}", map.len() - SHAPE_MAX_KEYS);
+            }
+            out.push('}');
+        }
+    }
 }
 
 impl From<String> for ExtractValueData {
@@ -0,0 +1,348 @@
+//! # 提取步骤遍历
+//!
+//! 集中处理规则中所有提取步骤的递归遍历（回退、`map`、`condition` 子步骤），
+//! 供校验、分析等工具复用，避免新增嵌套步骤时各处遍历逻辑不一致。
+
+use crate::Result;
+use crawler_schema::{
+    core::CrawlerRule,
+    extract::{ExtractStep, FieldExtractor},
+    fields::{ContentFields, DetailFields, FieldRule, ItemFields},
+};
+
+/// 提取步骤访问者
+///
+/// 遍历时对每个步骤（包括嵌套子步骤）调用一次 `visit_step`，
+/// `path` 为步骤路径（如 `search.fields.title.steps[0]`）。返回错误将中止遍历。
+///
+/// 闭包 `FnMut(&str, &ExtractStep) -> Result<()>` 自动实现该 trait（仅访问步骤）。
+pub trait StepVisitor {
+    /// 访问单个步骤
+    fn visit_step(&mut self, path: &str, step: &ExtractStep) -> Result<()>;
+
+    /// 访问字段提取器（在其步骤之前调用），`path` 为字段路径（如 `search.fields.title`）
+    fn visit_extractor(&mut self, _path: &str, _extractor: &FieldExtractor) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<F> StepVisitor for F
+where
+    F: FnMut(&str, &ExtractStep) -> Result<()>,
+{
+    fn visit_step(&mut self, path: &str, step: &ExtractStep) -> Result<()> {
+        self(path, step)
+    }
+}
+
+/// 规则步骤遍历器
+pub struct RuleWalker;
+
+impl RuleWalker {
+    /// 按 `(步骤路径, 步骤)` 遍历规则中的所有提取步骤（含回退、Map、Condition 子步骤）
+    pub fn walk_rule<V: StepVisitor + ?Sized>(rule: &CrawlerRule, visitor: &mut V) -> Result<()> {
+        Self::walk_extractor(visitor, "search.list", &rule.search.list)?;
+        Self::walk_item_fields(visitor, "search.fields", &rule.search.fields)?;
+
+        if let Some(suggest) = &rule.suggest {
+            Self::walk_extractor(visitor, "suggest.list", &suggest.list)?;
+            Self::walk_field(visitor, "suggest.fields.title", &suggest.fields.title)?;
+            Self::walk_field(visitor, "suggest.fields.url", &suggest.fields.url)?;
+        }
+
+        if let Some(discovery) = &rule.discovery {
+            Self::walk_extractor(visitor, "discovery.list", &discovery.list)?;
+            Self::walk_item_fields(visitor, "discovery.fields", &discovery.fields)?;
+        }
+
+        Self::walk_detail_fields(visitor, "detail.fields", &rule.detail.fields)?;
+
+        if let Some(content) = &rule.content {
+            Self::walk_content_fields(visitor, "content.fields", &content.fields)?;
+        }
+
+        if let Some(components) = &rule.components {
+            for (name, component) in components {
+                Self::walk_extractor(
+                    visitor,
+                    &format!("components.{}", name),
+                    &component.extractor,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 遍历单个字段提取器（主步骤和所有回退步骤）
+    pub fn walk_extractor<V: StepVisitor + ?Sized>(
+        visitor: &mut V,
+        path: &str,
+        extractor: &FieldExtractor,
+    ) -> Result<()> {
+        visitor.visit_extractor(path, extractor)?;
+        Self::walk_steps(visitor, &format!("{}.steps", path), &extractor.steps)?;
+        if let Some(fallback) = &extractor.fallback {
+            for (i, steps) in fallback.iter().enumerate() {
+                Self::walk_steps(visitor, &format!("{}.fallback[{}]", path, i), steps)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 递归遍历步骤列表
+    fn walk_steps<V: StepVisitor + ?Sized>(
+        visitor: &mut V,
+        path: &str,
+        steps: &[ExtractStep],
+    ) -> Result<()> {
+        for (i, step) in steps.iter().enumerate() {
+            let step_path = format!("{}[{}]", path, i);
+            visitor.visit_step(&step_path, step)?;
+            match step {
+                ExtractStep::Map(map) => {
+                    Self::walk_steps(visitor, &format!("{}.map", step_path), map.steps())?;
+                }
+                ExtractStep::Condition(condition) => {
+                    Self::walk_steps(visitor, &format!("{}.when", step_path), &condition.when)?;
+                    Self::walk_steps(visitor, &format!("{}.then", step_path), &condition.then)?;
+                    if let Some(otherwise) = &condition.otherwise {
+                        Self::walk_steps(visitor, &format!("{}.otherwise", step_path), otherwise)?;
+                    }
+                }
+                ExtractStep::Merge(fields) => {
+                    for (key, sub_steps) in fields {
+                        Self::walk_steps(
+                            visitor,
+                            &format!("{}.merge.{}", step_path, key),
+                            sub_steps,
+                        )?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn walk_field<V: StepVisitor + ?Sized>(
+        visitor: &mut V,
+        path: &str,
+        rule: &FieldRule,
+    ) -> Result<()> {
+        Self::walk_extractor(visitor, path, &rule.extractor)
+    }
+
+    fn walk_optional<V: StepVisitor + ?Sized>(
+        visitor: &mut V,
+        path: &str,
+        rule: &Option<FieldRule>,
+    ) -> Result<()> {
+        match rule {
+            Some(rule) => Self::walk_field(visitor, path, rule),
+            None => Ok(()),
+        }
+    }
+
+    /// 按 `(字段名, 规则)` 列表批量遍历可选字段
+    fn walk_optionals<V: StepVisitor + ?Sized>(
+        visitor: &mut V,
+        path: &str,
+        fields: &[(&str, &Option<FieldRule>)],
+    ) -> Result<()> {
+        for (name, rule) in fields {
+            Self::walk_optional(visitor, &format!("{}.{}", path, name), rule)?;
+        }
+        Ok(())
+    }
+
+    fn walk_item_fields<V: StepVisitor + ?Sized>(
+        visitor: &mut V,
+        path: &str,
+        fields: &ItemFields,
+    ) -> Result<()> {
+        Self::walk_field(visitor, &format!("{}.title", path), &fields.title)?;
+        Self::walk_field(visitor, &format!("{}.url", path), &fields.url)?;
+        Self::walk_optionals(
+            visitor,
+            path,
+            &[
+                ("cover", &fields.cover),
+                ("summary", &fields.summary),
+                ("author", &fields.author),
+                ("latest", &fields.latest),
+                ("score", &fields.score),
+                ("status", &fields.status),
+                ("category", &fields.category),
+                ("extra", &fields.extra),
+            ],
+        )
+    }
+
+    fn walk_detail_fields<V: StepVisitor + ?Sized>(
+        visitor: &mut V,
+        path: &str,
+        fields: &DetailFields,
+    ) -> Result<()> {
+        match fields {
+            DetailFields::Book(fields) => {
+                Self::walk_field(visitor, &format!("{}.title", path), &fields.title)?;
+                Self::walk_field(visitor, &format!("{}.author", path), &fields.author)?;
+                Self::walk_optionals(
+                    visitor,
+                    path,
+                    &[
+                        ("cover", &fields.cover),
+                        ("intro", &fields.intro),
+                        ("category", &fields.category),
+                        ("tags", &fields.tags),
+                        ("status", &fields.status),
+                        ("last_chapter", &fields.last_chapter),
+                        ("update_time", &fields.update_time),
+                        ("word_count", &fields.word_count),
+                        ("toc_url", &fields.toc_url),
+                    ],
+                )?;
+                if let Some(chapters) = &fields.chapters {
+                    let p = format!("{}.chapters", path);
+                    Self::walk_field(visitor, &format!("{}.list", p), &chapters.list)?;
+                    Self::walk_field(visitor, &format!("{}.title", p), &chapters.title)?;
+                    Self::walk_field(visitor, &format!("{}.url", p), &chapters.url)?;
+                }
+            }
+            DetailFields::Manga(fields) => {
+                Self::walk_field(visitor, &format!("{}.title", path), &fields.title)?;
+                Self::walk_optionals(
+                    visitor,
+                    path,
+                    &[
+                        ("author", &fields.author),
+                        ("cover", &fields.cover),
+                        ("intro", &fields.intro),
+                        ("category", &fields.category),
+                        ("tags", &fields.tags),
+                        ("status", &fields.status),
+                        ("last_chapter", &fields.last_chapter),
+                        ("update_time", &fields.update_time),
+                    ],
+                )?;
+                if let Some(chapters) = &fields.chapters {
+                    let p = format!("{}.chapters", path);
+                    Self::walk_field(visitor, &format!("{}.list", p), &chapters.list)?;
+                    Self::walk_field(visitor, &format!("{}.title", p), &chapters.title)?;
+                    Self::walk_field(visitor, &format!("{}.url", p), &chapters.url)?;
+                }
+            }
+            DetailFields::Video(fields) => {
+                Self::walk_field(visitor, &format!("{}.title", path), &fields.title)?;
+                Self::walk_optionals(
+                    visitor,
+                    path,
+                    &[
+                        ("cover", &fields.cover),
+                        ("intro", &fields.intro),
+                        ("director", &fields.director),
+                        ("actors", &fields.actors),
+                        ("category", &fields.category),
+                        ("tags", &fields.tags),
+                        ("region", &fields.region),
+                        ("year", &fields.year),
+                        ("score", &fields.score),
+                        ("language", &fields.language),
+                        ("update_info", &fields.update_info),
+                        ("duration", &fields.duration),
+                    ],
+                )?;
+                if let Some(lines) = &fields.play_lines {
+                    let p = format!("{}.play_lines", path);
+                    Self::walk_field(visitor, &format!("{}.lines", p), &lines.lines)?;
+                    Self::walk_field(visitor, &format!("{}.line_name", p), &lines.line_name)?;
+                    let ep = format!("{}.episodes", p);
+                    Self::walk_field(visitor, &format!("{}.list", ep), &lines.episodes.list)?;
+                    Self::walk_field(visitor, &format!("{}.name", ep), &lines.episodes.name)?;
+                    Self::walk_field(visitor, &format!("{}.url", ep), &lines.episodes.url)?;
+                }
+            }
+            DetailFields::Audio(fields) => {
+                Self::walk_field(visitor, &format!("{}.title", path), &fields.title)?;
+                Self::walk_optionals(
+                    visitor,
+                    path,
+                    &[
+                        ("artist", &fields.artist),
+                        ("cover", &fields.cover),
+                        ("intro", &fields.intro),
+                        ("album", &fields.album),
+                        ("category", &fields.category),
+                        ("tags", &fields.tags),
+                        ("update_time", &fields.update_time),
+                        ("play_count", &fields.play_count),
+                    ],
+                )?;
+                if let Some(tracks) = &fields.tracks {
+                    let p = format!("{}.tracks", path);
+                    Self::walk_field(visitor, &format!("{}.list", p), &tracks.list)?;
+                    Self::walk_field(visitor, &format!("{}.name", p), &tracks.name)?;
+                    Self::walk_field(visitor, &format!("{}.url", p), &tracks.url)?;
+                    Self::walk_optional(visitor, &format!("{}.duration", p), &tracks.duration)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn walk_content_fields<V: StepVisitor + ?Sized>(
+        visitor: &mut V,
+        path: &str,
+        fields: &ContentFields,
+    ) -> Result<()> {
+        match fields {
+            ContentFields::Book(fields) => {
+                Self::walk_field(visitor, &format!("{}.content", path), &fields.content)?;
+                Self::walk_optionals(
+                    visitor,
+                    path,
+                    &[
+                        ("title", &fields.title),
+                        ("prev_url", &fields.prev_url),
+                        ("next_url", &fields.next_url),
+                    ],
+                )
+            }
+            ContentFields::Manga(fields) => {
+                Self::walk_field(visitor, &format!("{}.images", path), &fields.images)?;
+                Self::walk_optionals(
+                    visitor,
+                    path,
+                    &[
+                        ("title", &fields.title),
+                        ("next_chapter_url", &fields.next_chapter_url),
+                        ("prev_chapter_url", &fields.prev_chapter_url),
+                    ],
+                )
+            }
+            ContentFields::Video(fields) => {
+                Self::walk_field(visitor, &format!("{}.play_url", path), &fields.play_url)?;
+                Self::walk_optionals(
+                    visitor,
+                    path,
+                    &[("title", &fields.title), ("quality", &fields.quality)],
+                )
+            }
+            ContentFields::Audio(fields) => {
+                Self::walk_field(visitor, &format!("{}.play_url", path), &fields.play_url)?;
+                Self::walk_optionals(
+                    visitor,
+                    path,
+                    &[
+                        ("title", &fields.title),
+                        ("artist", &fields.artist),
+                        ("cover", &fields.cover),
+                        ("lyrics", &fields.lyrics),
+                        ("duration", &fields.duration),
+                    ],
+                )
+            }
+        }
+    }
+}
@@ -3,8 +3,25 @@
 use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
+    error::RuntimeError,
+    extractor::{ExtractEngine, SharedValue, filter::url::resolve_url, value::ExtractValueData},
+    http::{FetchedPage, fetch_page, resolve_flow_config},
+    model::{AudioPlay, BookContent, MangaContent, VideoPlay},
+    template::TemplateExt,
 };
-use crawler_schema::flow::ContentFlow;
+use crawler_schema::{
+    extract::FieldExtractor,
+    fields::{
+        AudioPlayFields,
+        BookContentFields,
+        ContentFields,
+        MangaReadFields,
+        OptionalFieldRule,
+        VideoPlayFields,
+    },
+    flow::ContentFlow,
+};
+use std::{collections::HashSet, sync::Arc};
 
 /// 内容请求
 #[derive(Debug, Clone)]
@@ -13,32 +30,327 @@ pub struct ContentRequest {
     pub url: String,
 }
 
-/// 内容响应
+/// 内容响应（通用）
 #[derive(Debug, Clone)]
-pub struct ContentResponse {
-    /// 内容数据
-    pub data: serde_json::Value,
+pub enum ContentResponse {
+    /// 书籍正文
+    Book(Box<BookContent>),
+    /// 漫画图片列表
+    Manga(Box<MangaContent>),
+    /// 视频播放地址
+    Video(Box<VideoPlay>),
+    /// 音频播放地址
+    Audio(Box<AudioPlay>),
+}
+
+impl ContentResponse {
+    /// 转换为 JSON 值
+    pub fn to_value(&self) -> serde_json::Value {
+        match self {
+            Self::Book(b) => serde_json::to_value(b).unwrap_or(serde_json::Value::Null),
+            Self::Manga(m) => serde_json::to_value(m).unwrap_or(serde_json::Value::Null),
+            Self::Video(v) => serde_json::to_value(v).unwrap_or(serde_json::Value::Null),
+            Self::Audio(a) => serde_json::to_value(a).unwrap_or(serde_json::Value::Null),
+        }
+    }
 }
 
 /// 内容流程执行器
 pub struct ContentFlowExecutor;
 
 impl ContentFlowExecutor {
+    /// 提取字符串字段
+    fn extract_string(
+        extractor: &FieldExtractor,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Option<String> {
+        ExtractEngine::extract_field(extractor, input.as_ref(), runtime_context, flow_context)
+            .ok()
+            .and_then(|v| v.as_str().map(|s| s.trim().to_string()))
+            .filter(|s| !s.is_empty())
+    }
+
+    /// 提取可选的字符串字段，未配置或提取失败时为 `None`
+    fn extract_optional(
+        field: &OptionalFieldRule,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Option<String> {
+        field
+            .as_ref()
+            .and_then(|f| Self::extract_string(&f.extractor, input, runtime_context, flow_context))
+    }
+
+    /// 提取 URL 字段，相对地址按页面 URL 转为绝对地址
+    fn extract_url(
+        extractor: &FieldExtractor,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        page_url: &str,
+    ) -> Option<String> {
+        Self::extract_string(extractor, input, runtime_context, flow_context)
+            .map(|url| resolve_url(page_url, &url))
+    }
+
+    /// 提取正文
+    ///
+    /// 选中多个段落节点时，逐段去除首尾空白、丢弃空段后以换行连接
+    fn extract_text(
+        extractor: &FieldExtractor,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<String> {
+        let value =
+            ExtractEngine::extract_field(extractor, input.as_ref(), runtime_context, flow_context)?;
+        let text = match value.as_ref() {
+            ExtractValueData::Array(paragraphs) => paragraphs
+                .iter()
+                .filter_map(|p| p.as_str().map(str::trim))
+                .filter(|p| !p.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            other => other
+                .as_str()
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default(),
+        };
+        Ok(text)
+    }
+
+    /// 提取书籍正文
+    fn extract_book_content(
+        fields: &BookContentFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        page_url: &str,
+    ) -> Result<BookContent> {
+        let text = Self::extract_text(
+            &fields.content.extractor,
+            html,
+            runtime_context,
+            flow_context,
+        )?;
+        if text.is_empty() && !fields.content.extractor.nullable {
+            return Err(RuntimeError::Extraction("无法提取正文".to_string()));
+        }
+
+        let mut content = BookContent::new(text);
+        if let Some(title) = fields
+            .title
+            .as_ref()
+            .and_then(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+        {
+            content = content.with_title(title);
+        }
+        let prev_url = fields.prev_url.as_ref().and_then(|f| {
+            Self::extract_url(&f.extractor, html, runtime_context, flow_context, page_url)
+        });
+        let next_url = fields.next_url.as_ref().and_then(|f| {
+            Self::extract_url(&f.extractor, html, runtime_context, flow_context, page_url)
+        });
+
+        Ok(content.with_pagination(prev_url, next_url))
+    }
+
+    /// 提取漫画图片列表
+    ///
+    /// 懒加载图片可在规则中用 `attr = "first(data-src, data-original, src)"` 取真实地址；
+    /// 相对地址按页面 URL 转为绝对地址，`data:` 内联占位图被丢弃，
+    /// 重复出现的地址（通常是占位图）只保留第一次
+    fn extract_manga_content(
+        fields: &MangaReadFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        page_url: &str,
+    ) -> Result<MangaContent> {
+        let value = ExtractEngine::extract_field(
+            &fields.images.extractor,
+            html.as_ref(),
+            runtime_context,
+            flow_context,
+        )?;
+        let candidates: Vec<&str> = match value.as_ref() {
+            ExtractValueData::Array(items) => items.iter().filter_map(|v| v.as_str()).collect(),
+            other => other.as_str().into_iter().collect(),
+        };
+
+        let mut seen = HashSet::new();
+        let images: Vec<String> = candidates
+            .into_iter()
+            .map(str::trim)
+            .filter(|src| !src.is_empty() && !src.starts_with("data:"))
+            .map(|src| resolve_url(page_url, src))
+            .filter(|url| seen.insert(url.clone()))
+            .collect();
+        if images.is_empty() && !fields.images.extractor.nullable {
+            return Err(RuntimeError::Extraction("未提取到任何图片".to_string()));
+        }
+
+        let mut content = MangaContent::new(images);
+        if let Some(title) = fields
+            .title
+            .as_ref()
+            .and_then(|f| Self::extract_string(&f.extractor, html, runtime_context, flow_context))
+        {
+            content = content.with_title(title);
+        }
+        content.prev_url = fields.prev_chapter_url.as_ref().and_then(|f| {
+            Self::extract_url(&f.extractor, html, runtime_context, flow_context, page_url)
+        });
+        content.next_url = fields.next_chapter_url.as_ref().and_then(|f| {
+            Self::extract_url(&f.extractor, html, runtime_context, flow_context, page_url)
+        });
+
+        Ok(content)
+    }
+
+    /// 提取播放地址，相对地址按页面 URL 转为绝对地址
+    fn extract_play_url(
+        extractor: &FieldExtractor,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        page_url: &str,
+    ) -> Result<String> {
+        let value =
+            ExtractEngine::extract_field(extractor, html.as_ref(), runtime_context, flow_context)?;
+        value
+            .as_str()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(|url| resolve_url(page_url, url))
+            .ok_or_else(|| RuntimeError::Extraction("未提取到播放地址".to_string()))
+    }
+
+    /// 提取视频播放信息
+    fn extract_video_play(
+        fields: &VideoPlayFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        page_url: &str,
+    ) -> Result<VideoPlay> {
+        let play_url = Self::extract_play_url(
+            &fields.play_url.extractor,
+            html,
+            runtime_context,
+            flow_context,
+            page_url,
+        )?;
+        let optional = |field: &OptionalFieldRule| {
+            Self::extract_optional(field, html, runtime_context, flow_context)
+        };
+
+        let mut play = VideoPlay::new(play_url);
+        play.title = optional(&fields.title);
+        play.quality = optional(&fields.quality);
+        Ok(play)
+    }
+
+    /// 提取音频播放信息
+    fn extract_audio_play(
+        fields: &AudioPlayFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+        page_url: &str,
+    ) -> Result<AudioPlay> {
+        let play_url = Self::extract_play_url(
+            &fields.play_url.extractor,
+            html,
+            runtime_context,
+            flow_context,
+            page_url,
+        )?;
+        let optional = |field: &OptionalFieldRule| {
+            Self::extract_optional(field, html, runtime_context, flow_context)
+        };
+
+        let mut play = AudioPlay::new(play_url);
+        play.title = optional(&fields.title);
+        play.artist = optional(&fields.artist);
+        play.cover = fields.cover.as_ref().and_then(|f| {
+            Self::extract_url(&f.extractor, html, runtime_context, flow_context, page_url)
+        });
+        play.lyrics = optional(&fields.lyrics);
+        play.duration = optional(&fields.duration);
+        Ok(play)
+    }
+
     /// 执行内容流程
     pub async fn execute(
         input: ContentRequest,
         flow: &ContentFlow,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         flow_context: &mut FlowContext,
     ) -> Result<ContentResponse> {
-        // 设置上下文变量
-        flow_context.set("content_url", serde_json::json!(input.url));
+        // 1. 设置上下文变量
+        flow_context.set("url", serde_json::json!(&input.url));
+        flow_context.set("content_url", serde_json::json!(&input.url));
+
+        // 2. 渲染 URL
+        let url = flow.url.render(flow_context)?;
 
-        // TODO: 实现内容流程
-        let _ = flow;
+        // 3. 发起 HTTP 请求
+        let http_config =
+            resolve_flow_config(runtime_context.rule().http.as_ref(), flow.http.as_ref());
+        let FetchedPage {
+            url: page_url,
+            body: html_text,
+        } = fetch_page(&url, http_config.as_ref(), runtime_context, flow_context).await?;
+        let html = Arc::new(ExtractValueData::Html(Arc::from(
+            html_text.into_boxed_str(),
+        )));
 
-        Ok(ContentResponse {
-            data: serde_json::json!({}),
-        })
+        // 4. 根据媒体类型提取字段
+        match &flow.fields {
+            ContentFields::Book(fields) => {
+                let content = Self::extract_book_content(
+                    fields,
+                    &html,
+                    runtime_context,
+                    flow_context,
+                    &page_url,
+                )?;
+                Ok(ContentResponse::Book(Box::new(content)))
+            }
+            ContentFields::Video(fields) => {
+                let play = Self::extract_video_play(
+                    fields,
+                    &html,
+                    runtime_context,
+                    flow_context,
+                    &page_url,
+                )?;
+                Ok(ContentResponse::Video(Box::new(play)))
+            }
+            ContentFields::Audio(fields) => {
+                let play = Self::extract_audio_play(
+                    fields,
+                    &html,
+                    runtime_context,
+                    flow_context,
+                    &page_url,
+                )?;
+                Ok(ContentResponse::Audio(Box::new(play)))
+            }
+            ContentFields::Manga(fields) => {
+                let content = Self::extract_manga_content(
+                    fields,
+                    &html,
+                    runtime_context,
+                    flow_context,
+                    &page_url,
+                )?;
+                Ok(ContentResponse::Manga(Box::new(content)))
+            }
+        }
     }
 }
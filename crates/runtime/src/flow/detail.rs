@@ -5,15 +5,40 @@ use crate::{
     context::{FlowContext, RuntimeContext},
     error::RuntimeError,
     extractor::{ExtractEngine, SharedValue, value::ExtractValueData},
-    model::{BookDetail, ChapterItem},
+    http::{FetchedPage, fetch_page, resolve_flow_config},
+    model::{
+        AudioDetail,
+        BookDetail,
+        ChapterItem,
+        EpisodeItem,
+        MangaDetail,
+        PlayLine,
+        TrackItem,
+        VideoDetail,
+    },
     template::TemplateExt,
 };
 use crawler_schema::{
-    fields::{BookDetailFields, ChapterListRule, DetailFields},
+    config::MediaType,
+    fields::{
+        AudioDetailFields,
+        BookDetailFields,
+        ChapterListRule,
+        DetailFields,
+        FieldRule,
+        MangaDetailFields,
+        OptionalFieldRule,
+        PlayLineListRule,
+        TrackListRule,
+        VideoDetailFields,
+    },
     flow::DetailFlow,
 };
 use std::sync::Arc;
 
+/// 漫画章节列表存入 `play_lines` 时使用的线路名
+const MANGA_CHAPTER_LINE: &str = "章节";
+
 /// 详情请求
 #[derive(Debug, Clone)]
 pub struct DetailRequest {
@@ -21,39 +46,73 @@ pub struct DetailRequest {
     pub url: String,
 }
 
-/// 详情响应（通用）
+/// 详情响应
+///
+/// 按媒体类型区分的详情模型
 #[derive(Debug, Clone)]
 pub enum DetailResponse {
     /// 书籍详情
     Book(Box<BookDetail>),
-    /// 其他类型（暂用 JSON）
-    Other(serde_json::Value),
+    /// 视频详情
+    Video(Box<VideoDetail>),
+    /// 音频详情
+    Audio(Box<AudioDetail>),
+    /// 漫画详情
+    Manga(Box<MangaDetail>),
 }
 
 impl DetailResponse {
+    /// 获取媒体类型
+    pub fn media_type(&self) -> MediaType {
+        match self {
+            Self::Book(_) => MediaType::Book,
+            Self::Video(_) => MediaType::Video,
+            Self::Audio(_) => MediaType::Audio,
+            Self::Manga(_) => MediaType::Manga,
+        }
+    }
+
     /// 获取标题
     pub fn title(&self) -> &str {
         match self {
             Self::Book(b) => &b.title,
-            Self::Other(v) => v.get("title").and_then(|t| t.as_str()).unwrap_or(""),
+            Self::Video(v) => &v.title,
+            Self::Audio(a) => &a.title,
+            Self::Manga(m) => &m.title,
         }
     }
 
-    /// 获取作者
+    /// 获取作者（视频为导演，音频为艺术家），未提取到时为空字符串
     pub fn author(&self) -> &str {
-        match self {
-            Self::Book(b) => &b.author,
-            Self::Other(v) => v.get("author").and_then(|t| t.as_str()).unwrap_or(""),
-        }
+        let author = match self {
+            Self::Book(b) => Some(&b.author),
+            Self::Video(v) => v.director.as_ref(),
+            Self::Audio(a) => a.artist.as_ref(),
+            Self::Manga(m) => m.author.as_ref(),
+        };
+        author.map(String::as_str).unwrap_or("")
     }
 
     /// 获取简介
     pub fn intro(&self) -> Option<&str> {
         match self {
             Self::Book(b) => b.intro.as_deref(),
-            Self::Other(v) => v.get("intro").and_then(|t| t.as_str()),
+            Self::Video(v) => v.intro.as_deref(),
+            Self::Audio(a) => a.intro.as_deref(),
+            Self::Manga(m) => m.intro.as_deref(),
         }
     }
+
+    /// 将详情模型序列化为 JSON 值
+    pub fn to_json(&self) -> serde_json::Value {
+        let value = match self {
+            Self::Book(b) => serde_json::to_value(b),
+            Self::Video(v) => serde_json::to_value(v),
+            Self::Audio(a) => serde_json::to_value(a),
+            Self::Manga(m) => serde_json::to_value(m),
+        };
+        value.unwrap_or(serde_json::Value::Null)
+    }
 }
 
 /// 详情流程执行器
@@ -74,6 +133,48 @@ impl DetailFlowExecutor {
             .filter(|s| !s.is_empty())
     }
 
+    /// 提取可选字符串字段
+    fn extract_optional(
+        rule: &OptionalFieldRule,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Option<String> {
+        rule.as_ref()
+            .and_then(|f| Self::extract_string(&f.extractor, input, runtime_context, flow_context))
+    }
+
+    /// 提取必需字符串字段，失败时返回 `Extraction` 错误
+    fn extract_required(
+        rule: &FieldRule,
+        name: &str,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<String> {
+        Self::extract_string(&rule.extractor, input, runtime_context, flow_context)
+            .ok_or_else(|| RuntimeError::Extraction(format!("无法提取{}", name)))
+    }
+
+    /// 提取列表容器，结果不是数组时视为空列表
+    fn extract_items(
+        rule: &FieldRule,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Vec<SharedValue>> {
+        let result = ExtractEngine::extract_field(
+            &rule.extractor,
+            input.as_ref(),
+            runtime_context,
+            flow_context,
+        )?;
+        Ok(match result.as_ref() {
+            ExtractValueData::Array(items) => items.iter().cloned().collect(),
+            _ => Vec::new(),
+        })
+    }
+
     /// 提取书籍详情
     fn extract_book_detail(
         fields: &BookDetailFields,
@@ -156,21 +257,10 @@ impl DetailFlowExecutor {
         runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> Result<Vec<ChapterItem>> {
-        // 先提取列表容器
-        let list_result = ExtractEngine::extract_field(
-            &rule.list.extractor,
-            html.as_ref(),
-            runtime_context,
-            flow_context,
-        )?;
-
-        let items = match list_result.as_ref() {
-            ExtractValueData::Array(arr) => arr,
-            _ => return Ok(vec![]),
-        };
+        let items = Self::extract_items(&rule.list, html, runtime_context, flow_context)?;
 
         let mut chapters = Vec::new();
-        for item in items.iter() {
+        for item in &items {
             let title =
                 Self::extract_string(&rule.title.extractor, item, runtime_context, flow_context);
             let url =
@@ -184,6 +274,177 @@ impl DetailFlowExecutor {
         Ok(chapters)
     }
 
+    /// 提取视频详情
+    fn extract_video_detail(
+        fields: &VideoDetailFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<VideoDetail> {
+        let title =
+            Self::extract_required(&fields.title, "标题", html, runtime_context, flow_context)?;
+        let optional = |rule: &OptionalFieldRule| {
+            Self::extract_optional(rule, html, runtime_context, flow_context)
+        };
+
+        let mut detail = VideoDetail::new(title);
+        detail.cover = optional(&fields.cover);
+        detail.intro = optional(&fields.intro);
+        detail.director = optional(&fields.director);
+        detail.actors = optional(&fields.actors);
+        detail.category = optional(&fields.category);
+        detail.tags = optional(&fields.tags);
+        detail.region = optional(&fields.region);
+        detail.year = optional(&fields.year);
+        detail.score = optional(&fields.score);
+        detail.language = optional(&fields.language);
+        detail.update_info = optional(&fields.update_info);
+        detail.duration = optional(&fields.duration);
+        if let Some(rule) = &fields.play_lines {
+            detail.play_lines =
+                Self::extract_play_lines(rule, html, runtime_context, flow_context)?;
+        }
+        detail.raw = serde_json::json!({});
+        Ok(detail)
+    }
+
+    /// 提取播放线路，未提取到剧集的线路被丢弃
+    fn extract_play_lines(
+        rule: &PlayLineListRule,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Vec<PlayLine>> {
+        let lines = Self::extract_items(&rule.lines, html, runtime_context, flow_context)?;
+
+        let mut play_lines = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            let name = Self::extract_string(
+                &rule.line_name.extractor,
+                line,
+                runtime_context,
+                flow_context,
+            )
+            .unwrap_or_else(|| format!("线路{}", index + 1));
+
+            let episodes =
+                Self::extract_items(&rule.episodes.list, line, runtime_context, flow_context)?;
+            let mut play_line = PlayLine::new(name);
+            for episode in &episodes {
+                let name = Self::extract_string(
+                    &rule.episodes.name.extractor,
+                    episode,
+                    runtime_context,
+                    flow_context,
+                );
+                let url = Self::extract_string(
+                    &rule.episodes.url.extractor,
+                    episode,
+                    runtime_context,
+                    flow_context,
+                );
+                if let (Some(name), Some(url)) = (name, url) {
+                    play_line.add_episode(EpisodeItem::new(name, url));
+                }
+            }
+
+            if !play_line.episodes.is_empty() {
+                play_lines.push(play_line);
+            }
+        }
+
+        Ok(play_lines)
+    }
+
+    /// 提取音频详情
+    fn extract_audio_detail(
+        fields: &AudioDetailFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<AudioDetail> {
+        let title =
+            Self::extract_required(&fields.title, "标题", html, runtime_context, flow_context)?;
+        let optional = |rule: &OptionalFieldRule| {
+            Self::extract_optional(rule, html, runtime_context, flow_context)
+        };
+
+        let mut detail = AudioDetail::new(title);
+        detail.artist = optional(&fields.artist);
+        detail.cover = optional(&fields.cover);
+        detail.intro = optional(&fields.intro);
+        detail.album = optional(&fields.album);
+        detail.category = optional(&fields.category);
+        detail.tags = optional(&fields.tags);
+        detail.update_time = optional(&fields.update_time);
+        detail.play_count = optional(&fields.play_count);
+        if let Some(rule) = &fields.tracks {
+            detail.tracks = Self::extract_tracks(rule, html, runtime_context, flow_context)?;
+        }
+        detail.raw = serde_json::json!({});
+        Ok(detail)
+    }
+
+    /// 提取音轨列表
+    fn extract_tracks(
+        rule: &TrackListRule,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<Vec<TrackItem>> {
+        let items = Self::extract_items(&rule.list, html, runtime_context, flow_context)?;
+
+        let mut tracks = Vec::new();
+        for item in &items {
+            let name =
+                Self::extract_string(&rule.name.extractor, item, runtime_context, flow_context);
+            let url =
+                Self::extract_string(&rule.url.extractor, item, runtime_context, flow_context);
+            if let (Some(name), Some(url)) = (name, url) {
+                let mut track = TrackItem::new(name, url);
+                track.duration =
+                    Self::extract_optional(&rule.duration, item, runtime_context, flow_context);
+                tracks.push(track);
+            }
+        }
+
+        Ok(tracks)
+    }
+
+    /// 提取漫画详情
+    ///
+    /// 章节列表作为单条线路存入 `play_lines`
+    fn extract_manga_detail(
+        fields: &MangaDetailFields,
+        html: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<MangaDetail> {
+        let title =
+            Self::extract_required(&fields.title, "标题", html, runtime_context, flow_context)?;
+        let optional = |rule: &OptionalFieldRule| {
+            Self::extract_optional(rule, html, runtime_context, flow_context)
+        };
+
+        let mut detail = MangaDetail::new(title);
+        detail.author = optional(&fields.author);
+        detail.cover = optional(&fields.cover);
+        detail.intro = optional(&fields.intro);
+        detail.category = optional(&fields.category);
+        detail.tags = optional(&fields.tags);
+        detail.status = optional(&fields.status);
+        detail.update_time = optional(&fields.update_time);
+        if let Some(rule) = &fields.chapters {
+            let episodes = Self::extract_chapters(rule, html, runtime_context, flow_context)?
+                .into_iter()
+                .map(|chapter| EpisodeItem::new(chapter.title, chapter.url))
+                .collect();
+            detail.add_play_line(MANGA_CHAPTER_LINE, episodes);
+        }
+        detail.raw = serde_json::json!({});
+        Ok(detail)
+    }
+
     /// 执行详情流程
     pub async fn execute(
         input: DetailRequest,
@@ -198,34 +459,31 @@ impl DetailFlowExecutor {
         let url = flow.url.render(flow_context)?;
 
         // 3. 发起 HTTP 请求
-        let response = runtime_context.http_client().get(&url).await?;
-        let html_text = response
-            .text()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(format!("读取响应失败: {}", e)))?;
+        let http_config =
+            resolve_flow_config(runtime_context.rule().http.as_ref(), flow.http.as_ref());
+        let FetchedPage {
+            url: _page_url,
+            body: html_text,
+        } = fetch_page(&url, http_config.as_ref(), runtime_context, flow_context).await?;
         let html = Arc::new(ExtractValueData::Html(Arc::from(
             html_text.into_boxed_str(),
         )));
 
         // 4. 根据媒体类型提取字段
-        match &flow.fields {
-            DetailFields::Book(fields) => {
-                let detail =
-                    Self::extract_book_detail(fields, &html, runtime_context, flow_context)?;
-                Ok(DetailResponse::Book(Box::new(detail)))
-            }
-            DetailFields::Video(_) => {
-                // TODO: 实现视频详情提取
-                Ok(DetailResponse::Other(serde_json::json!({"type": "video"})))
-            }
-            DetailFields::Audio(_) => {
-                // TODO: 实现音频详情提取
-                Ok(DetailResponse::Other(serde_json::json!({"type": "audio"})))
-            }
-            DetailFields::Manga(_) => {
-                // TODO: 实现漫画详情提取
-                Ok(DetailResponse::Other(serde_json::json!({"type": "manga"})))
-            }
-        }
+        let detail = match &flow.fields {
+            DetailFields::Book(fields) => DetailResponse::Book(Box::new(
+                Self::extract_book_detail(fields, &html, runtime_context, flow_context)?,
+            )),
+            DetailFields::Video(fields) => DetailResponse::Video(Box::new(
+                Self::extract_video_detail(fields, &html, runtime_context, flow_context)?,
+            )),
+            DetailFields::Audio(fields) => DetailResponse::Audio(Box::new(
+                Self::extract_audio_detail(fields, &html, runtime_context, flow_context)?,
+            )),
+            DetailFields::Manga(fields) => DetailResponse::Manga(Box::new(
+                Self::extract_manga_detail(fields, &html, runtime_context, flow_context)?,
+            )),
+        };
+        Ok(detail)
     }
 }
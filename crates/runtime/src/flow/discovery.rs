@@ -1,27 +1,56 @@
 //! # 发现流程执行器
+//!
+//! 获取分类页/列表页，复用搜索流程的列表项提取逻辑，产出 `SearchItem` 列表
 
 use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
+    error::RuntimeError,
+    extractor::{ExtractEngine, value::ExtractValueData},
+    flow::{pager::PaginationExt, search::SearchFlowExecutor, selection::check_selection_key},
+    http::{FetchedPage, fetch_page, resolve_flow_config},
+    model::SearchItem,
+    template::TemplateExt,
 };
 use crawler_schema::flow::DiscoveryFlow;
+use serde_json::Value;
+use std::{collections::HashMap, sync::Arc};
+use url::Url;
 
 /// 发现请求
 #[derive(Debug, Clone)]
 pub struct DiscoveryRequest {
     /// 筛选条件
-    pub filters: std::collections::HashMap<String, String>,
+    pub filters: HashMap<String, String>,
     /// 页码
     pub page: u32,
+    /// 游标（游标分页时为上一页响应中的 `next_cursor`）
+    pub cursor: Option<String>,
 }
 
 /// 发现响应
 #[derive(Debug, Clone)]
 pub struct DiscoveryResponse {
     /// 结果列表
-    pub items: Vec<serde_json::Value>,
+    pub items: Vec<SearchItem>,
     /// 是否有下一页
     pub has_next: bool,
+    /// 下一页游标（游标分页）
+    pub next_cursor: Option<String>,
+    /// 原始数据
+    pub raw_items: Vec<Value>,
+}
+
+impl DiscoveryResponse {
+    /// 空页（无结果、无下一页）
+    fn empty() -> Self {
+        Self {
+            items: Vec::new(),
+            has_next: false,
+            next_cursor: None,
+            raw_items: Vec::new(),
+        }
+    }
 }
 
 /// 发现流程执行器
@@ -29,24 +58,110 @@ pub struct DiscoveryFlowExecutor;
 
 impl DiscoveryFlowExecutor {
     /// 执行发现流程
+    ///
+    /// 未匹配到列表（如翻过最后一页）时返回空结果而非错误
     pub async fn execute(
         input: DiscoveryRequest,
         flow: &DiscoveryFlow,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         flow_context: &mut FlowContext,
     ) -> Result<DiscoveryResponse> {
+        let base_url = runtime_context.base_url().to_string();
+
         // 设置上下文变量
+        flow_context.set("page", serde_json::json!(input.page));
+        if let Some(pagination) = &flow.pagination {
+            if !pagination.allows_page(input.page) {
+                return Ok(DiscoveryResponse::empty());
+            }
+            flow_context.extend(pagination.request_vars(input.page, input.cursor.as_deref())?);
+        }
+        for key in input.filters.keys() {
+            check_selection_key("discovery.filters", key, flow_context)?;
+        }
         for (key, value) in &input.filters {
             flow_context.set(key, serde_json::json!(value));
         }
-        flow_context.set("page", serde_json::json!(input.page));
 
-        // TODO: 实现发现流程
-        let _ = flow;
+        // 1. 渲染 URL
+        let url = flow.url.render(flow_context)?;
+        let full_url = Url::parse(&base_url)
+            .and_then(|base| base.join(&url))
+            .or_else(|_| Url::parse(&url))
+            .map_err(|e| RuntimeError::HttpRequest(format!("Invalid URL '{}': {}", url, e)))?
+            .to_string();
+
+        // 2. 发起 HTTP 请求
+        let http_config =
+            resolve_flow_config(runtime_context.rule().http.as_ref(), flow.http.as_ref());
+        let FetchedPage {
+            url: page_url,
+            body: html,
+        } = fetch_page(
+            &full_url,
+            http_config.as_ref(),
+            runtime_context,
+            flow_context,
+        )
+        .await?;
+
+        // 3. 提取列表（未匹配到列表视为空页，其他错误照常返回）
+        let html_value = Arc::new(ExtractValueData::Html(Arc::from(html.into_boxed_str())));
+        let list_result = match ExtractEngine::extract_field(
+            &flow.list,
+            html_value.as_ref(),
+            runtime_context,
+            flow_context,
+        ) {
+            Ok(list) => list,
+            Err(RuntimeError::EmptyValue) => {
+                tracing::debug!("发现页列表为空: {}", page_url);
+                return Ok(DiscoveryResponse::empty());
+            }
+            Err(e) => return Err(e),
+        };
+
+        // 4. 遍历列表项，提取字段
+        let entries = match list_result.as_ref() {
+            ExtractValueData::Array(arr) => arr.iter().cloned().collect(),
+            ExtractValueData::Html(_) => vec![list_result.clone()],
+            _ => Vec::new(),
+        };
+        let mut items = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            match SearchFlowExecutor::extract_item(
+                &flow.fields,
+                entry,
+                runtime_context,
+                flow_context,
+                &page_url,
+            ) {
+                Ok(item) => items.push(item),
+                Err(e) => tracing::warn!("提取发现页列表项失败: {}", e),
+            }
+        }
+        let raw_items = items.iter().map(|item| item.raw.clone()).collect();
+
+        // 5. 判断是否有下一页（未配置分页时，有结果就认为可能有下一页）
+        let (has_next, next_cursor) = match &flow.pagination {
+            Some(pagination) => {
+                let info = pagination.page_info(
+                    input.page,
+                    html_value.as_ref(),
+                    items.len(),
+                    runtime_context,
+                    flow_context,
+                );
+                (info.has_next, info.next_cursor)
+            }
+            None => (!items.is_empty(), None),
+        };
 
         Ok(DiscoveryResponse {
-            items: vec![],
-            has_next: false,
+            items,
+            has_next,
+            next_cursor,
+            raw_items,
         })
     }
 }
@@ -1,18 +1,41 @@
 //! # 登录流程执行器
+//!
+//! 执行顺序：
+//! 1. 执行 `check_login`（输入为当前站点的 Cookie 字符串），返回真值时跳过登录，
+//!    此时无需提供凭证字段
+//! 2. 校验调用方提供的凭证字段，缺少必填字段时在发起任何请求前返回 `MissingConfig`
+//! 3. 按登录模式执行登录，凭证写入共享 HTTP 客户端的 Cookie 存储与会话请求头，
+//!    之后所有流程的请求自动携带
 
 use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
+    error::RuntimeError,
+    extractor::value::ExtractValueData,
+    script::ScriptExecutor,
+    template::TemplateExt,
+    webview::{WebViewCloseReason, WebViewRequest},
 };
-use crawler_schema::flow::LoginFlow;
+use crawler_schema::flow::{
+    CredentialFieldType,
+    CredentialLoginFlow,
+    CredentialStorage,
+    LoginFlow,
+    LoginUIElement,
+    ScriptLoginFlow,
+    WebViewLoginFlow,
+};
+use reqwest::Url;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// 凭证模式未声明字段时使用的默认 Cookie 字段
+const DEFAULT_COOKIE_FIELD: &str = "cookie";
 
 /// 登录请求
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct LoginRequest {
-    /// 用户名
-    pub username: String,
-    /// 密码
-    pub password: String,
+    /// 用户填写的字段值（键为 `ui` / `fields` 中声明的 key）
+    pub fields: HashMap<String, String>,
 }
 
 /// 登录响应
@@ -20,10 +43,32 @@ pub struct LoginRequest {
 pub struct LoginResponse {
     /// 是否成功
     pub success: bool,
+    /// 是否因 `check_login` 判定已登录而跳过登录
+    pub already_logged_in: bool,
     /// 会话信息
     pub session: Option<serde_json::Value>,
 }
 
+impl LoginResponse {
+    /// 已登录，未执行登录
+    fn already_logged_in() -> Self {
+        Self {
+            success: true,
+            already_logged_in: true,
+            session: None,
+        }
+    }
+
+    /// 执行了登录
+    fn finished(success: bool, session: Option<serde_json::Value>) -> Self {
+        Self {
+            success,
+            already_logged_in: false,
+            session,
+        }
+    }
+}
+
 /// 登录流程执行器
 pub struct LoginFlowExecutor;
 
@@ -32,19 +77,254 @@ impl LoginFlowExecutor {
     pub async fn execute(
         input: LoginRequest,
         flow: &LoginFlow,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         flow_context: &mut FlowContext,
     ) -> Result<LoginResponse> {
-        // 设置上下文变量
-        flow_context.set("username", serde_json::json!(input.username));
-        flow_context.set("password", serde_json::json!(input.password));
+        // 1. 检查登录状态（已登录时无需填写字段）
+        let site_url = Self::site_url(runtime_context.base_url())?;
+        if let Some(check) = flow.check_login() {
+            let cookies = runtime_context
+                .http_client()
+                .cookie_header(&site_url)
+                .unwrap_or_default();
+            let input = ExtractValueData::String(Arc::from(cookies.into_boxed_str()));
+            let result = ScriptExecutor::execute(check, &input, runtime_context, flow_context)?;
+            if result.is_truthy() {
+                tracing::debug!("check_login 判定已登录，跳过登录");
+                return Ok(LoginResponse::already_logged_in());
+            }
+        }
 
-        // TODO: 实现登录流程
-        let _ = flow;
+        // 2. 校验必填字段，在发起任何请求之前
+        for key in Self::required_fields(flow) {
+            if input.fields.get(&key).is_none_or(|v| v.trim().is_empty()) {
+                return Err(RuntimeError::MissingConfig {
+                    field: format!("login.{}", key),
+                });
+            }
+        }
 
-        Ok(LoginResponse {
-            success: false,
-            session: None,
+        // 3. 绑定字段
+        for (key, value) in &input.fields {
+            flow_context.set(key.clone(), serde_json::json!(value));
+        }
+
+        // 4. 按模式登录
+        match flow {
+            LoginFlow::Script(script_flow) => {
+                Self::script_login(script_flow, runtime_context, flow_context)
+            }
+            LoginFlow::Webview(webview_flow) => {
+                Self::webview_login(webview_flow, &site_url, runtime_context, flow_context).await
+            }
+            LoginFlow::Credential(credential_flow) => Self::credential_login(
+                credential_flow,
+                &input,
+                &site_url,
+                runtime_context,
+                flow_context,
+            ),
+        }
+    }
+
+    /// 必填字段的 key 列表
+    fn required_fields(flow: &LoginFlow) -> Vec<String> {
+        match flow {
+            LoginFlow::Script(script_flow) => script_flow
+                .ui
+                .iter()
+                .filter_map(|element| match element {
+                    LoginUIElement::Text(input) if input.required => Some(input.key.clone()),
+                    LoginUIElement::Password(input) if input.required => Some(input.key.clone()),
+                    _ => None,
+                })
+                .collect(),
+            LoginFlow::Webview(_) => Vec::new(),
+            LoginFlow::Credential(credential_flow) => match &credential_flow.fields {
+                Some(fields) => fields
+                    .iter()
+                    .filter(|field| field.required)
+                    .map(|field| field.key.clone())
+                    .collect(),
+                None => vec![DEFAULT_COOKIE_FIELD.to_string()],
+            },
+        }
+    }
+
+    /// 将域名解析为站点 URL，缺少协议时补全为 https
+    fn site_url(domain: &str) -> Result<Url> {
+        let url = if domain.contains("://") {
+            domain.to_string()
+        } else {
+            format!("https://{}", domain)
+        };
+        Url::parse(&url).map_err(|e| RuntimeError::InvalidConfigValue {
+            field: "meta.domain".to_string(),
+            reason: e.to_string(),
         })
     }
+
+    /// 脚本交互模式：执行 `init_script` 与 `login_script`，后者返回真值即登录成功
+    fn script_login(
+        flow: &ScriptLoginFlow,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<LoginResponse> {
+        if let Some(init) = &flow.init_script {
+            ScriptExecutor::execute(init, &ExtractValueData::Null, runtime_context, flow_context)?;
+        }
+        let result = ScriptExecutor::execute(
+            &flow.login_script,
+            &ExtractValueData::Null,
+            runtime_context,
+            flow_context,
+        )?;
+        Ok(LoginResponse::finished(
+            result.is_truthy(),
+            Some(result.to_owned_json()),
+        ))
+    }
+
+    /// 网页模式：打开 WebView 等待 `check_script` 通过，登录后的 Cookie 写回共享存储
+    async fn webview_login(
+        flow: &WebViewLoginFlow,
+        site_url: &Url,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<LoginResponse> {
+        let http_client = runtime_context.http_client();
+        let start_url = flow.start_url.render(flow_context)?;
+
+        let mut request = WebViewRequest::new(&start_url)
+            .with_timeout(Duration::from_secs(
+                flow.timeout_seconds.unwrap_or(300) as u64
+            ))
+            .with_check_interval(Duration::from_millis(
+                flow.check_interval_ms.unwrap_or(500) as u64
+            ));
+        request.allow_redirects = flow.allow_redirects;
+        if let Some(ua) = flow
+            .user_agent
+            .as_ref()
+            .or(http_client.config().user_agent.as_ref())
+        {
+            request = request.with_user_agent(ua);
+        }
+        if let Some(script) = &flow.inject_script {
+            request = request.with_inject_script(script);
+        }
+        if let Some(check) = &flow.check_script {
+            request = request.with_success_check(check);
+        }
+        for (name, value) in http_client
+            .cookie_header(site_url)
+            .iter()
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+        {
+            request = request.with_cookie(name, value);
+        }
+
+        let response = runtime_context.webview_provider().open(request).await?;
+        if !response.success {
+            return match response.close_reason {
+                WebViewCloseReason::Timeout => Err(RuntimeError::WebViewTimeout),
+                WebViewCloseReason::UserClosed => Err(RuntimeError::WebViewUserClosed),
+                _ => Err(RuntimeError::WebViewError(
+                    response.error.unwrap_or_else(|| "登录失败".to_string()),
+                )),
+            };
+        }
+
+        let cookie_url = response
+            .final_url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok())
+            .unwrap_or_else(|| site_url.clone());
+        let cookies = response
+            .cookies
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        http_client.set_cookies(&cookie_url, &cookies);
+        if cookie_url.host_str() != site_url.host_str() {
+            http_client.set_cookies(site_url, &cookies);
+        }
+
+        let session = match &flow.finish_script {
+            Some(finish) => {
+                let input = ExtractValueData::String(Arc::from(cookies.into_boxed_str()));
+                let result =
+                    ScriptExecutor::execute(finish, &input, runtime_context, flow_context)?;
+                Some(result.to_owned_json())
+            }
+            None => None,
+        };
+        Ok(LoginResponse::finished(true, session))
+    }
+
+    /// 凭证模式：按 `storage` 写入 Cookie 或会话请求头，再执行 `validate_script`
+    fn credential_login(
+        flow: &CredentialLoginFlow,
+        input: &LoginRequest,
+        site_url: &Url,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Result<LoginResponse> {
+        let http_client = runtime_context.http_client();
+        let default_storage = [CredentialStorage::Cookie(Default::default())];
+        let storages = flow.storage.as_deref().unwrap_or(&default_storage);
+
+        for storage in storages {
+            match storage {
+                CredentialStorage::Cookie(cookie) => {
+                    let key = cookie
+                        .field_key
+                        .clone()
+                        .unwrap_or_else(|| Self::cookie_field_key(flow));
+                    let Some(value) = input.fields.get(&key) else {
+                        continue;
+                    };
+                    let url = match &cookie.domain {
+                        Some(domain) => Self::site_url(domain)?,
+                        None => site_url.clone(),
+                    };
+                    http_client.set_cookies(&url, value);
+                }
+                CredentialStorage::Header(header) => {
+                    let value = header.header_template.render(flow_context)?;
+                    http_client.set_session_header(&header.header_name, value);
+                }
+            }
+        }
+
+        let success = match &flow.validate_script {
+            Some(validate) => {
+                let cookies = http_client.cookie_header(site_url).unwrap_or_default();
+                let input = ExtractValueData::String(Arc::from(cookies.into_boxed_str()));
+                ScriptExecutor::execute(validate, &input, runtime_context, flow_context)?
+                    .is_truthy()
+            }
+            None => true,
+        };
+        Ok(LoginResponse::finished(success, None))
+    }
+
+    /// Cookie 存储未指定字段时：使用名为 `cookie` 的字段或第一个多行文本字段
+    fn cookie_field_key(flow: &CredentialLoginFlow) -> String {
+        let Some(fields) = &flow.fields else {
+            return DEFAULT_COOKIE_FIELD.to_string();
+        };
+        fields
+            .iter()
+            .find(|field| field.key == DEFAULT_COOKIE_FIELD)
+            .or_else(|| {
+                fields
+                    .iter()
+                    .find(|field| field.field_type == CredentialFieldType::Textarea)
+            })
+            .map(|field| field.key.clone())
+            .unwrap_or_else(|| DEFAULT_COOKIE_FIELD.to_string())
+    }
 }
@@ -5,6 +5,7 @@
 //! # 流程类型
 //!
 //! - `search` - 搜索流程，支持分页
+//! - `suggest` - 搜索建议流程
 //! - `discovery` - 发现流程，支持筛选和分页
 //! - `detail` - 详情流程
 //! - `content` - 内容流程
@@ -17,13 +18,17 @@ pub mod executor;
 pub mod login;
 pub mod pager;
 pub mod search;
+pub mod selection;
+pub mod suggest;
 
 pub use executor::FlowExecutor;
 pub use pager::{
     DiscoveryPager,
     DiscoveryPagerState,
+    PageInfo,
     Pager,
     PagerState,
+    PaginationExt,
     SearchPager,
     SearchPagerState,
 };
@@ -2,11 +2,170 @@
 //!
 //! 为流程结果提供链式分页能力
 
-use crate::{Result, context::RuntimeContext};
-use crawler_schema::flow::common::Pagination;
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    error::RuntimeError,
+    extractor::{ExtractEngine, value::ExtractValueData},
+};
+use crawler_schema::{extract::FieldExtractor, flow::common::Pagination};
 use serde_json::Value;
 use std::{collections::HashMap, sync::Arc};
 
+// ============================================================================
+// 分页驱动
+// ============================================================================
+
+/// 当前页的翻页信息
+#[derive(Debug, Clone, Default)]
+pub struct PageInfo {
+    /// 是否有下一页
+    pub has_next: bool,
+    /// 下一页游标（游标分页）
+    pub next_cursor: Option<String>,
+}
+
+/// 分页配置扩展
+///
+/// 将逻辑页码（从 1 开始）换算为请求参数，并根据响应判断能否继续翻页
+pub trait PaginationExt {
+    /// 计算第 `page` 页请求的 URL 模板变量
+    ///
+    /// - 页码分页：`param` = `start + page - 1`
+    /// - 偏移分页：`param` = `start + (page - 1) * step`，设置了 `limit_param` 时同时写入 `step`
+    /// - 游标分页：`param` = 上一页提取的游标（首页无游标时不设置）
+    ///
+    /// 页码或偏移量超出 `u32` 范围时返回 `Pagination` 错误
+    fn request_vars(&self, page: u32, cursor: Option<&str>) -> Result<HashMap<String, Value>>;
+
+    /// 第 `page` 页是否在 `max_pages` / `max_offset` / `max_requests` 限制内
+    ///
+    /// 偏移量超出 `u32` 范围时视为超出限制
+    fn allows_page(&self, page: u32) -> bool;
+
+    /// 根据当前页的响应和结果数计算翻页信息
+    fn page_info(
+        &self,
+        page: u32,
+        response: &ExtractValueData,
+        item_count: usize,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> PageInfo;
+}
+
+impl PaginationExt for Pagination {
+    fn request_vars(&self, page: u32, cursor: Option<&str>) -> Result<HashMap<String, Value>> {
+        let index = page.max(1) - 1;
+        let mut vars = HashMap::new();
+        match self {
+            Pagination::PageNumber(config) => {
+                let number = config.start.checked_add(index).ok_or_else(|| {
+                    RuntimeError::Pagination(format!(
+                        "页码溢出: start = {}, page = {}",
+                        config.start, page
+                    ))
+                })?;
+                vars.insert(config.param.clone(), Value::from(number));
+            }
+            Pagination::Offset(config) => {
+                let offset = offset_of(config.start, config.step, index).ok_or_else(|| {
+                    RuntimeError::Pagination(format!(
+                        "偏移量溢出: start = {}, step = {}, page = {}",
+                        config.start, config.step, page
+                    ))
+                })?;
+                vars.insert(config.param.clone(), Value::from(offset));
+                if let Some(limit_param) = &config.limit_param {
+                    vars.insert(limit_param.clone(), Value::from(config.step));
+                }
+            }
+            Pagination::Cursor(config) => {
+                if let Some(cursor) = cursor {
+                    vars.insert(config.param.clone(), Value::from(cursor));
+                }
+            }
+            Pagination::None => {}
+        }
+        Ok(vars)
+    }
+
+    fn allows_page(&self, page: u32) -> bool {
+        match self {
+            Pagination::PageNumber(config) => config.max_pages.is_none_or(|max| page <= max),
+            Pagination::Offset(config) => config.max_offset.is_none_or(|max| {
+                offset_of(config.start, config.step, page.max(1) - 1)
+                    .is_some_and(|offset| offset <= max)
+            }),
+            Pagination::Cursor(config) => config.max_requests.is_none_or(|max| page <= max),
+            Pagination::None => page <= 1,
+        }
+    }
+
+    fn page_info(
+        &self,
+        page: u32,
+        response: &ExtractValueData,
+        item_count: usize,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> PageInfo {
+        let extract = |extractor: &FieldExtractor| {
+            ExtractEngine::extract_field(extractor, response, runtime_context, flow_context).ok()
+        };
+        // 未配置 has_next 时，以本页是否有结果作为依据（游标分页以是否取到游标为准）
+        let has_next_rule = |rule: Option<&FieldExtractor>| match rule {
+            Some(extractor) => extract(extractor).is_some_and(|v| v.is_truthy()),
+            None => item_count > 0 || matches!(self, Pagination::Cursor(_)),
+        };
+
+        let (has_next, next_cursor) = match self {
+            Pagination::PageNumber(config) => (has_next_rule(config.has_next.as_ref()), None),
+            Pagination::Offset(config) => {
+                let next_offset = offset_of(config.start, config.step, page.max(1));
+                let total =
+                    config
+                        .total_count
+                        .as_ref()
+                        .and_then(extract)
+                        .and_then(|v| match v.as_ref() {
+                            ExtractValueData::Json(json) => json.as_u64(),
+                            other => other.as_str().and_then(|s| s.trim().parse().ok()),
+                        });
+                let has_next = match (next_offset, total) {
+                    (None, _) => false,
+                    (Some(next_offset), Some(total)) => u64::from(next_offset) < total,
+                    (Some(_), None) => item_count > 0,
+                };
+                (has_next, None)
+            }
+            Pagination::Cursor(config) => {
+                let cursor = extract(&config.next_cursor)
+                    .and_then(|v| match v.as_ref() {
+                        ExtractValueData::Json(json) if !json.is_null() && !json.is_string() => {
+                            Some(json.to_string())
+                        }
+                        other => other.as_str().map(str::to_string),
+                    })
+                    .filter(|c| !c.is_empty());
+                let has_next = cursor.is_some() && has_next_rule(config.has_next.as_ref());
+                (has_next, cursor)
+            }
+            Pagination::None => (false, None),
+        };
+
+        PageInfo {
+            has_next: has_next && page < u32::MAX && self.allows_page(page + 1),
+            next_cursor,
+        }
+    }
+}
+
+/// 计算偏移分页第 `index + 1` 页的偏移量：`start + index * step`，溢出时返回 `None`
+fn offset_of(start: u32, step: u32, index: u32) -> Option<u32> {
+    index.checked_mul(step)?.checked_add(start)
+}
+
 /// 分页状态 trait
 ///
 /// 不同流程（Search/Discovery）实现不同的状态结构
@@ -198,11 +357,15 @@ impl<S: PagerState> Pager<S> {
     }
 
     /// 创建下一页的分页器
+    ///
+    /// 页码已达 `u32::MAX` 时返回 `None`
     pub fn next_page_pager(&self) -> Option<Self> {
+        let next_page = self.state.current_page().checked_add(1)?;
+
         // 如果是游标分页，需要有游标才能翻页
         if let Some(Pagination::Cursor(_)) = &self.pagination {
             let cursor = self.next_cursor.clone()?;
-            let new_state = self.state.with_page(self.state.current_page() + 1);
+            let new_state = self.state.with_page(next_page);
             // 需要在 state 中存储 cursor，这里通过重新创建来实现
             return Some(Self {
                 runtime: Arc::clone(&self.runtime),
@@ -216,7 +379,7 @@ impl<S: PagerState> Pager<S> {
         Some(Self {
             runtime: Arc::clone(&self.runtime),
             pagination: self.pagination.clone(),
-            state: self.state.with_page(self.state.current_page() + 1),
+            state: self.state.with_page(next_page),
             next_cursor: None,
         })
     }
@@ -4,21 +4,43 @@ use crate::{
     Result,
     context::{FlowContext, RuntimeContext},
     error::RuntimeError,
-    extractor::{ExtractEngine, SharedValue, value::ExtractValueData},
+    extractor::{ExtractEngine, SharedValue, filter::url::resolve_url, value::ExtractValueData},
+    flow::{
+        detail::{DetailFlowExecutor, DetailRequest},
+        pager::PaginationExt,
+        selection::bind_selections,
+    },
+    http::{FetchedPage, fetch_page, resolve_flow_config},
     model::SearchItem,
     template::TemplateExt,
 };
 use crawler_schema::{extract::FieldExtractor, fields::ItemFields, flow::SearchFlow};
 use serde_json::{Map, Value};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
+use tokio::{sync::Semaphore, task::JoinSet};
+
+/// 详情预取的默认并发数
+pub const DEFAULT_PREFETCH_CONCURRENCY: usize = 4;
 
 /// 搜索请求
 #[derive(Debug, Clone)]
 pub struct SearchRequest {
     /// 搜索关键词
     pub keyword: String,
-    /// 页码
+    /// 页码（从 1 开始，按分页配置换算为请求参数）
     pub page: u32,
+    /// 游标（游标分页时为上一页响应中的 `next_cursor`）
+    pub cursor: Option<String>,
+    /// 选中的排序值（排序组 `key` → 值）
+    pub sorts: HashMap<String, Vec<String>>,
+    /// 选中的筛选值（筛选组 `key` → 值），未定义的 `key` 返回错误
+    pub filters: HashMap<String, Vec<String>>,
+    /// 是否为每个结果预取详情（开销较大，默认关闭）
+    ///
+    /// 预取成功的详情写入结果 `raw` 的 `detail` 字段
+    pub prefetch_detail: bool,
+    /// 详情预取的最大并发数（`None` 时使用 [`DEFAULT_PREFETCH_CONCURRENCY`]）
+    pub prefetch_concurrency: Option<usize>,
 }
 
 /// 搜索结果
@@ -28,6 +50,8 @@ pub struct SearchResponse {
     pub items: Vec<SearchItem>,
     /// 是否有下一页
     pub has_next: bool,
+    /// 下一页游标（游标分页）
+    pub next_cursor: Option<String>,
     /// 原始数据
     pub raw_items: Vec<Value>,
 }
@@ -48,8 +72,8 @@ impl SearchFlowExecutor {
             .and_then(|v| v.as_str().map(|s| s.to_string()))
     }
 
-    /// 从列表项提取搜索结果
-    fn extract_item(
+    /// 从列表项提取搜索结果（发现流程共用）
+    pub(crate) fn extract_item(
         fields: &ItemFields,
         item_html: &SharedValue,
         runtime_context: &RuntimeContext,
@@ -73,13 +97,9 @@ impl SearchFlowExecutor {
         )
         .ok_or_else(|| RuntimeError::Extraction("Failed to extract url".to_string()))?;
 
-        // 处理相对 URL
+        // 处理相对 URL（基于重定向后的最终页面 URL）
         let url = if !url.starts_with("http") && !base_url.is_empty() {
-            if url.starts_with('/') {
-                format!("{}{}", base_url.trim_end_matches('/'), url)
-            } else {
-                format!("{}/{}", base_url.trim_end_matches('/'), url)
-            }
+            resolve_url(base_url, &url)
         } else {
             url
         };
@@ -151,6 +171,30 @@ impl SearchFlowExecutor {
         flow_context.set("keyword", serde_json::json!(input.keyword));
         flow_context.set("page", serde_json::json!(input.page));
         flow_context.set("base_url", serde_json::json!(&base_url));
+        if let Some(pagination) = &flow.pagination {
+            if !pagination.allows_page(input.page) {
+                return Ok(SearchResponse {
+                    items: Vec::new(),
+                    has_next: false,
+                    next_cursor: None,
+                    raw_items: Vec::new(),
+                });
+            }
+            // 分页参数（如 offset、cursor），与分页参数同名时覆盖 `page`
+            flow_context.extend(pagination.request_vars(input.page, input.cursor.as_deref())?);
+        }
+        bind_selections(
+            "search.sorts",
+            flow.sorts.as_deref().unwrap_or_default(),
+            &input.sorts,
+            flow_context,
+        )?;
+        bind_selections(
+            "search.filters",
+            flow.filters.as_deref().unwrap_or_default(),
+            &input.filters,
+            flow_context,
+        )?;
 
         // 1. 渲染 URL
         let url = flow.url.render(flow_context)?;
@@ -161,16 +205,18 @@ impl SearchFlowExecutor {
         };
 
         // 2. 发起 HTTP 请求
-        let response = runtime_context
-            .http_client()
-            .get(&full_url)
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(format!("Request failed: {}", e)))?;
-
-        let html = response
-            .text()
-            .await
-            .map_err(|e| RuntimeError::HttpRequest(format!("Failed to read response: {}", e)))?;
+        let http_config =
+            resolve_flow_config(runtime_context.rule().http.as_ref(), flow.http.as_ref());
+        let FetchedPage {
+            url: page_url,
+            body: html,
+        } = fetch_page(
+            &full_url,
+            http_config.as_ref(),
+            runtime_context,
+            flow_context,
+        )
+        .await?;
 
         // 3. 提取列表
         let html_value = Arc::new(ExtractValueData::Html(Arc::from(html.into_boxed_str())));
@@ -193,7 +239,7 @@ impl SearchFlowExecutor {
                         item_value,
                         runtime_context,
                         flow_context,
-                        &base_url,
+                        &page_url,
                     ) {
                         Ok(item) => {
                             raw_items.push(item.raw.clone());
@@ -214,7 +260,7 @@ impl SearchFlowExecutor {
                     &item_value,
                     runtime_context,
                     flow_context,
-                    &base_url,
+                    &page_url,
                 ) {
                     raw_items.push(item.raw.clone());
                     items.push(item);
@@ -223,13 +269,87 @@ impl SearchFlowExecutor {
             _ => {}
         }
 
-        // 5. 判断是否有下一页（简单实现：有结果就认为可能有下一页）
-        let has_next = !items.is_empty();
+        // 5. 按需预取详情（演练模式不预取）
+        if input.prefetch_detail && !items.is_empty() && !flow_context.is_dry_run() {
+            let concurrency = input
+                .prefetch_concurrency
+                .unwrap_or(DEFAULT_PREFETCH_CONCURRENCY);
+            Self::prefetch_details(&mut items, flow_context, concurrency).await;
+            raw_items = items.iter().map(|item| item.raw.clone()).collect();
+        }
+
+        // 6. 判断是否有下一页（未配置分页时，有结果就认为可能有下一页）
+        let (has_next, next_cursor) = match &flow.pagination {
+            Some(pagination) => {
+                let info = pagination.page_info(
+                    input.page,
+                    html_value.as_ref(),
+                    items.len(),
+                    runtime_context,
+                    flow_context,
+                );
+                (info.has_next, info.next_cursor)
+            }
+            None => (!items.is_empty(), None),
+        };
 
         Ok(SearchResponse {
             items,
             has_next,
+            next_cursor,
             raw_items,
         })
     }
+
+    /// 并发预取搜索结果的详情，写入各结果 `raw.detail`
+    ///
+    /// 每个详情使用由搜索流程派生的上下文（见 [`FlowContext::fork`]），变量互不影响，
+    /// 但请求计入同一个 `max_http_requests` 上限与执行时间，并经过 HTTP 客户端的按主机限流。
+    /// 预取任务随本方法的 Future 一同取消（JoinSet 丢弃时中止所有任务）；
+    /// 单个详情失败只记录警告，不影响搜索结果。
+    async fn prefetch_details(
+        items: &mut [SearchItem],
+        flow_context: &FlowContext,
+        concurrency: usize,
+    ) {
+        let runtime = Arc::clone(flow_context.runtime());
+        let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+
+        for (index, item) in items.iter().enumerate() {
+            let runtime = Arc::clone(&runtime);
+            let semaphore = Arc::clone(&semaphore);
+            let mut detail_context = flow_context.fork();
+            let request = DetailRequest {
+                url: item.url.clone(),
+            };
+            tasks.spawn(async move {
+                // 信号量不会被关闭，acquire 只会成功
+                let _permit = semaphore.acquire_owned().await.ok();
+                let result = DetailFlowExecutor::execute(
+                    request,
+                    &runtime.rule().detail,
+                    &runtime,
+                    &mut detail_context,
+                )
+                .await;
+                (index, result)
+            });
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            let Ok((index, result)) = joined else {
+                continue;
+            };
+            let item = &mut items[index];
+            match result {
+                Ok(detail) => {
+                    if let Value::Object(raw) = &mut item.raw {
+                        raw.insert("detail".to_string(), detail.to_json());
+                    }
+                }
+                Err(e) => tracing::warn!("预取详情失败 {}: {}", item.url, e),
+            }
+        }
+    }
 }
@@ -0,0 +1,92 @@
+//! # 选项组参数绑定
+//!
+//! 将用户在筛选器组/排序组中选中的值绑定为流程变量，供 URL 模板引用
+
+use crate::{
+    Result,
+    context::FlowContext,
+    error::RuntimeError,
+    extractor::{filter::executor::FINAL_URL_VAR, selector::map::LOOP_VAR},
+};
+use crawler_schema::flow::FilterGroup;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 流程执行器自身写入的变量名，选项组不能以此为 `key`
+const RESERVED_KEYS: &[&str] = &[
+    "keyword",
+    "page",
+    "cursor",
+    "base_url",
+    "url",
+    "detail_url",
+    "content_url",
+    LOOP_VAR,
+    FINAL_URL_VAR,
+];
+
+/// 将选中的选项值绑定到流程上下文
+///
+/// - `field`：配置项名称（如 `search.sorts`），用于错误信息
+/// - `selected`：`key → 选中值列表`
+///
+/// 规则：
+/// - 选中的 `key` 必须在 `groups` 中定义，否则返回错误
+/// - 组的 `key` 不能是保留变量名（`keyword`、`page` 等），也不能与流程上下文中
+///   已有的变量（如分页参数、先绑定的选项组）同名，否则返回错误，避免覆盖流程变量
+/// - 非多选组最多选中一个值；多选组的值以 `,` 连接
+/// - 未选中的组使用第一个选项的值作为默认值（无选项时为空字符串）， 保证模板中引用的变量始终有定义
+pub fn bind_selections(
+    field: &str,
+    groups: &[FilterGroup],
+    selected: &HashMap<String, Vec<String>>,
+    flow_context: &mut FlowContext,
+) -> Result<()> {
+    for key in selected.keys() {
+        if !groups.iter().any(|g| &g.key == key) {
+            return Err(RuntimeError::InvalidConfigValue {
+                field: field.to_string(),
+                reason: format!("未定义的选项组 key '{}'", key),
+            });
+        }
+    }
+
+    for group in groups {
+        check_selection_key(field, &group.key, flow_context)?;
+    }
+
+    for group in groups {
+        let value = match selected.get(&group.key) {
+            Some(values) if !values.is_empty() => {
+                if !group.multiselect && values.len() > 1 {
+                    return Err(RuntimeError::InvalidConfigValue {
+                        field: field.to_string(),
+                        reason: format!("选项组 '{}' 不允许多选", group.key),
+                    });
+                }
+                values.join(",")
+            }
+            _ => group
+                .options
+                .first()
+                .map(|o| o.value.clone())
+                .unwrap_or_default(),
+        };
+        flow_context.set(group.key.clone(), Value::String(value));
+    }
+
+    Ok(())
+}
+
+/// 检查选项 `key` 是否可以绑定为流程变量
+///
+/// 保留变量名与流程上下文中已有的变量不可被选项值覆盖
+pub fn check_selection_key(field: &str, key: &str, flow_context: &FlowContext) -> Result<()> {
+    if RESERVED_KEYS.contains(&key) || flow_context.get(key).is_some() {
+        return Err(RuntimeError::InvalidConfigValue {
+            field: field.to_string(),
+            reason: format!("选项组 key '{}' 与流程变量重名", key),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,150 @@
+//! # 搜索建议流程执行器
+
+use crate::{
+    Result,
+    context::{FlowContext, RuntimeContext},
+    error::RuntimeError,
+    extractor::{ExtractEngine, SharedValue, filter::url::resolve_url, value::ExtractValueData},
+    http::{fetch_page, resolve_flow_config},
+    model::SearchItem,
+    template::TemplateExt,
+};
+use crawler_schema::{config::ResponseContentType, extract::FieldExtractor, flow::SuggestFlow};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// 搜索建议请求
+#[derive(Debug, Clone)]
+pub struct SuggestRequest {
+    /// 部分关键词
+    pub keyword: String,
+}
+
+/// 搜索建议流程执行器
+pub struct SuggestFlowExecutor;
+
+impl SuggestFlowExecutor {
+    /// 校验 URL 或请求体模板是否引用了 `keyword` 变量
+    ///
+    /// 按解析后的模板变量判断，字符串字面量或路径中出现的 `keyword` 字样不算引用。
+    /// 规则加载时由 [`SelectorValidator::check_rule`](crate::extractor::SelectorValidator::check_rule) 调用
+    pub fn validate(flow: &SuggestFlow) -> Result<()> {
+        let request = flow.http.as_ref().and_then(|h| h.request.as_ref());
+        let templates = std::iter::once(&flow.url)
+            .chain(request.and_then(|r| r.body.as_ref()))
+            .chain(
+                request
+                    .and_then(|r| r.form.as_ref())
+                    .into_iter()
+                    .flat_map(|f| f.values()),
+            );
+        for template in templates {
+            if template.variables()?.contains("keyword") {
+                return Ok(());
+            }
+        }
+        Err(RuntimeError::InvalidConfigValue {
+            field: "suggest.url".to_string(),
+            reason: "URL 或请求体模板必须引用 keyword 变量".to_string(),
+        })
+    }
+
+    /// 提取字段值为字符串
+    fn extract_string(
+        extractor: &FieldExtractor,
+        input: &SharedValue,
+        runtime_context: &RuntimeContext,
+        flow_context: &FlowContext,
+    ) -> Option<String> {
+        ExtractEngine::extract_field(extractor, input.as_ref(), runtime_context, flow_context)
+            .ok()
+            .and_then(|v| match v.as_ref() {
+                ExtractValueData::Json(j) => match j.as_ref() {
+                    Value::String(s) => Some(s.clone()),
+                    Value::Null => None,
+                    other => Some(other.to_string()),
+                },
+                other => other.as_str().map(|s| s.to_string()),
+            })
+    }
+
+    /// 执行搜索建议流程
+    ///
+    /// 返回仅包含 `title` 与 `url` 的搜索项
+    pub async fn execute(
+        input: SuggestRequest,
+        flow: &SuggestFlow,
+        runtime_context: &RuntimeContext,
+        flow_context: &mut FlowContext,
+    ) -> Result<Vec<SearchItem>> {
+        let base_url = runtime_context.base_url().to_string();
+        flow_context.set("keyword", serde_json::json!(input.keyword));
+
+        // 1. 渲染 URL
+        let url = flow.url.render(flow_context)?;
+        let full_url = if base_url.is_empty() {
+            url
+        } else {
+            resolve_url(&base_url, &url)
+        };
+
+        // 2. 发起 HTTP 请求
+        let http_config =
+            resolve_flow_config(runtime_context.rule().http.as_ref(), flow.http.as_ref());
+        let body = fetch_page(
+            &full_url,
+            http_config.as_ref(),
+            runtime_context,
+            flow_context,
+        )
+        .await?
+        .body;
+
+        // 3. 按内容类型构造输入（建议接口通常返回 JSON）
+        let is_json = http_config
+            .as_ref()
+            .and_then(|h| h.response.as_ref())
+            .and_then(|r| r.content_type.as_ref())
+            .is_some_and(|t| matches!(t, ResponseContentType::Json));
+        let input_value = if is_json {
+            let json: Value = serde_json::from_str(&body)
+                .map_err(|e| RuntimeError::Extraction(format!("Invalid JSON response: {}", e)))?;
+            ExtractValueData::from_json(&json)
+        } else {
+            ExtractValueData::Html(Arc::from(body.into_boxed_str()))
+        };
+
+        // 4. 提取列表
+        let list_result =
+            ExtractEngine::extract_field(&flow.list, &input_value, runtime_context, flow_context)?;
+
+        let entries: Vec<SharedValue> = match list_result.as_ref() {
+            ExtractValueData::Array(arr) => arr.iter().cloned().collect(),
+            ExtractValueData::Null => Vec::new(),
+            _ => vec![list_result.clone()],
+        };
+
+        // 5. 提取标题与 URL
+        let mut items = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            let title = Self::extract_string(
+                &flow.fields.title.extractor,
+                entry,
+                runtime_context,
+                flow_context,
+            );
+            let url = Self::extract_string(
+                &flow.fields.url.extractor,
+                entry,
+                runtime_context,
+                flow_context,
+            );
+            match (title, url) {
+                (Some(title), Some(url)) => items.push(SearchItem::new(title, url)),
+                _ => tracing::warn!("提取搜索建议项失败：缺少标题或 URL"),
+            }
+        }
+
+        Ok(items)
+    }
+}
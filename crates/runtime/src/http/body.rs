@@ -0,0 +1,100 @@
+//! # 响应体解码
+//!
+//! 按声明编码将响应字节解码为 UTF-8 文本，兼容 GBK 等旧式编码站点
+
+use crate::{Result, error::RuntimeError};
+use crawler_schema::config::{Meta, ResponseConfig};
+use encoding_rs::{Encoding, UTF_8};
+use std::time::Duration;
+
+/// `<meta charset>` 嗅探范围（字节）
+const SNIFF_LIMIT: usize = 1024;
+
+/// 规则中声明的响应编码
+///
+/// 优先使用流程响应配置的 `encoding`（`auto` 视为未声明），其次是 `meta.encoding`
+pub fn declared_encoding<'a>(
+    response_config: Option<&ResponseConfig>,
+    meta: &'a Meta,
+) -> Option<&'a str> {
+    response_config
+        .and_then(|c| c.encoding.as_ref())
+        .and_then(|e| e.label())
+        .or(meta.encoding.as_deref())
+}
+
+/// 读取响应体并解码为字符串
+///
+/// 编码判定顺序：规则声明 → `Content-Type` 的 charset → HTML `<meta charset>` → UTF-8。
+/// 无法识别的编码标签按 UTF-8 处理，非法字节替换为 U+FFFD。
+/// 设置了 `read_timeout` 时，两次收到数据的间隔超过该时长即返回错误
+pub async fn read_text(
+    mut response: reqwest::Response,
+    declared: Option<&str>,
+    read_timeout: Option<Duration>,
+) -> Result<String> {
+    let header_charset = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(charset_from_content_type)
+        .map(str::to_string);
+
+    let mut bytes = Vec::new();
+    loop {
+        let chunk = match read_timeout {
+            Some(limit) => tokio::time::timeout(limit, response.chunk())
+                .await
+                .map_err(|_| {
+                    RuntimeError::HttpRequest(format!(
+                        "Failed to read response: read timed out after {}s",
+                        limit.as_secs()
+                    ))
+                })?,
+            None => response.chunk().await,
+        };
+        match chunk
+            .map_err(|e| RuntimeError::HttpRequest(format!("Failed to read response: {}", e)))?
+        {
+            Some(chunk) => bytes.extend_from_slice(&chunk),
+            None => break,
+        }
+    }
+
+    let encoding = declared
+        .and_then(|label| Encoding::for_label(label.trim().as_bytes()))
+        .or_else(|| {
+            header_charset
+                .as_deref()
+                .and_then(|label| Encoding::for_label(label.as_bytes()))
+        })
+        .or_else(|| sniff_meta_charset(&bytes))
+        .unwrap_or(UTF_8);
+
+    let (text, _, _) = encoding.decode(&bytes);
+    Ok(text.into_owned())
+}
+
+/// 从 `Content-Type` 中提取 charset 参数
+fn charset_from_content_type(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// 在文档开头查找 `<meta charset="...">` 或 `content="...; charset=..."`
+fn sniff_meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(SNIFF_LIMIT)];
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+
+    let start = head.find("charset=")? + "charset=".len();
+    let label: String = head[start..]
+        .trim_start_matches(['"', '\''])
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+        .collect();
+    Encoding::for_label(label.as_bytes())
+}
@@ -2,23 +2,166 @@
 //!
 //! 封装 reqwest，提供连接池和重试机制
 
-use crate::{Result, error::RuntimeError};
-use crawler_schema::config::HttpConfig;
-use std::time::Duration;
+use crate::{
+    Result,
+    error::RuntimeError,
+    http::cookie::{CookieJar, StoredCookie},
+    util::concurrent::HostRateLimiter,
+};
+use crawler_schema::config::{HttpConfig, HttpMethod, ProxyStrategy};
+use dashmap::DashMap;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, RandomState},
+    net::{IpAddr, SocketAddr},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+/// 两次重试之间的最长等待时间，同时限制 `Retry-After` 与指数退避的结果
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// 请求体
+#[derive(Debug, Clone)]
+pub enum RequestBody {
+    /// 原始文本，默认 `Content-Type: application/x-www-form-urlencoded`
+    Raw(String),
+    /// 表单键值对，`Content-Type: application/x-www-form-urlencoded`
+    Form(Vec<(String, String)>),
+    /// JSON 值，`Content-Type: application/json`
+    Json(Value),
+}
+
+/// 单个请求的超时设置（流程级 HTTP 配置覆盖客户端的全局设置）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestTimeouts {
+    /// 总超时
+    pub total: Option<Duration>,
+    /// 读取（空闲）超时：两次收到数据之间的最长间隔
+    ///
+    /// [`HttpClient::request`] 只用它限制等待响应头的时长；响应体由
+    /// [`read_text`](crate::http::body::read_text) 按同一时长逐块限制
+    pub read: Option<Duration>,
+}
+
+impl RequestTimeouts {
+    /// 取 HTTP 配置中的 `timeout` 与 `read_timeout`（秒）
+    pub fn from_config(config: &HttpConfig) -> Self {
+        let secs = |value: Option<u32>| value.map(|v| Duration::from_secs(v as u64));
+        Self {
+            total: secs(config.timeout),
+            read: secs(config.read_timeout),
+        }
+    }
+}
 
 /// HTTP 客户端
 ///
-/// 封装 reqwest::Client，提供连接池复用
+/// 封装 reqwest::Client，提供连接池复用与按主机限流
+///
+/// 克隆的客户端共享同一个限流器，所有流程执行器的请求共同遵守限制；
+/// 同样共享 Cookie 存储与会话请求头，登录后写入的凭证对所有请求生效
 #[derive(Debug, Clone)]
 pub struct HttpClient {
     client: reqwest::Client,
     config: HttpConfig,
+    limiter: Arc<HostRateLimiter>,
+    proxies: Arc<ProxyPool>,
+    cookie_jar: Arc<CookieJar>,
+    session_headers: Arc<DashMap<String, String>>,
+}
+
+/// 代理池：每个代理对应一个 reqwest::Client，按策略选择
+#[derive(Debug)]
+struct ProxyPool {
+    clients: Vec<reqwest::Client>,
+    strategy: ProxyStrategy,
+    cursor: AtomicUsize,
+    sticky: DashMap<String, usize>,
+    random: RandomState,
+}
+
+impl ProxyPool {
+    /// 为目标主机选择客户端，代理池为空时返回 `None`
+    fn select(&self, host: &str) -> Option<&reqwest::Client> {
+        if self.clients.is_empty() {
+            return None;
+        }
+        let len = self.clients.len();
+        let index = match self.strategy {
+            ProxyStrategy::RoundRobin => self.cursor.fetch_add(1, Ordering::Relaxed) % len,
+            ProxyStrategy::Random => {
+                let seed = self.cursor.fetch_add(1, Ordering::Relaxed);
+                self.random.hash_one(seed) as usize % len
+            }
+            ProxyStrategy::StickyPerHost => *self
+                .sticky
+                .entry(host.to_string())
+                .or_insert_with(|| self.cursor.fetch_add(1, Ordering::Relaxed) % len),
+        };
+        self.clients.get(index)
+    }
 }
 
 impl HttpClient {
+    /// 解析 `resolve` 中的地址
+    ///
+    /// 接受 `IP` 或 `IP:端口`；只写 IP 时端口为 0，即沿用协议的默认端口。
+    /// URL 中显式指定的端口总是优先
+    pub fn resolve_addr(addr: &str) -> std::result::Result<SocketAddr, String> {
+        addr.parse::<SocketAddr>()
+            .or_else(|_| addr.parse::<IpAddr>().map(|ip| SocketAddr::new(ip, 0)))
+            .map_err(|_| format!("'{}' 不是有效的 IP 或 IP:端口", addr))
+    }
+
     /// 创建新的 HTTP 客户端
+    ///
+    /// 代理地址（`proxy` 与 `proxies`）在此处解析，无效时返回 `HttpConfig` 错误
     pub fn new(config: HttpConfig) -> Result<Self> {
-        let mut client_builder = reqwest::Client::builder();
+        let cookie_jar = Arc::new(CookieJar::new());
+        let client = Self::build_client(&config, config.proxy.as_deref(), &cookie_jar)?;
+
+        let mut clients = Vec::new();
+        for proxy in config.proxies.iter().flatten() {
+            clients.push(Self::build_client(&config, Some(proxy), &cookie_jar)?);
+        }
+        let proxies = Arc::new(ProxyPool {
+            clients,
+            strategy: config.proxy_strategy.unwrap_or_default(),
+            cursor: AtomicUsize::new(0),
+            sticky: DashMap::new(),
+            random: RandomState::new(),
+        });
+
+        // 按主机限流：max_concurrent 并发上限 + request_delay 最小请求间隔
+        let limiter = Arc::new(HostRateLimiter::new(
+            config.max_concurrent.map(|n| n as usize),
+            Duration::from_millis(config.request_delay.unwrap_or(0) as u64),
+        ));
+
+        Ok(Self {
+            client,
+            config,
+            limiter,
+            proxies,
+            cookie_jar,
+            session_headers: Arc::new(DashMap::new()),
+        })
+    }
+
+    /// 按配置构建 reqwest::Client，`proxy` 为使用的代理地址
+    ///
+    /// 所有代理客户端共用同一个 Cookie 存储
+    fn build_client(
+        config: &HttpConfig,
+        proxy: Option<&str>,
+        cookie_jar: &Arc<CookieJar>,
+    ) -> Result<reqwest::Client> {
+        let mut client_builder = reqwest::Client::builder().cookie_provider(cookie_jar.clone());
 
         // 配置超时
         if let Some(timeout) = config.timeout {
@@ -31,6 +174,11 @@ impl HttpClient {
                 client_builder.connect_timeout(Duration::from_secs(connect_timeout as u64));
         }
 
+        // 配置读取（空闲）超时
+        if let Some(read_timeout) = config.read_timeout {
+            client_builder = client_builder.read_timeout(Duration::from_secs(read_timeout as u64));
+        }
+
         // 配置重定向
         if let Some(follow) = config.follow_redirects {
             if !follow {
@@ -47,20 +195,27 @@ impl HttpClient {
         }
 
         // 配置代理
-        if let Some(proxy) = &config.proxy {
-            let proxy = reqwest::Proxy::all(proxy)
-                .map_err(|e| RuntimeError::HttpConfig(format!("Invalid proxy: {}", e)))?;
+        if let Some(proxy) = proxy {
+            let proxy = reqwest::Proxy::all(proxy).map_err(|e| {
+                RuntimeError::HttpConfig(format!("Invalid proxy '{}': {}", proxy, e))
+            })?;
             client_builder = client_builder.proxy(proxy);
         }
 
+        // 配置 DNS 解析覆盖
+        for (host, addr) in config.resolve.iter().flatten() {
+            let addr = Self::resolve_addr(addr).map_err(|reason| {
+                RuntimeError::HttpConfig(format!("Invalid resolve for host '{}': {}", host, reason))
+            })?;
+            client_builder = client_builder.resolve(host, addr);
+        }
+
         // 配置连接池
         client_builder = client_builder.pool_max_idle_per_host(10);
 
-        let client = client_builder
+        client_builder
             .build()
-            .map_err(|e| RuntimeError::HttpConfig(format!("Failed to build client: {}", e)))?;
-
-        Ok(Self { client, config })
+            .map_err(|e| RuntimeError::HttpConfig(format!("Failed to build client: {}", e)))
     }
 
     /// 获取底层 reqwest::Client
@@ -73,6 +228,48 @@ impl HttpClient {
         &self.config
     }
 
+    /// 获取共享的 Cookie 存储
+    pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+        &self.cookie_jar
+    }
+
+    /// 写入 Cookie
+    ///
+    /// `cookies` 为 `name=value; name2=value2` 形式的 Cookie 字符串，
+    /// 各项作为 `url` 所在域名的 Cookie 保存；无法解析的项被忽略
+    pub fn set_cookies(&self, url: &reqwest::Url, cookies: &str) {
+        for pair in cookies.split(';') {
+            let pair = pair.trim();
+            if pair.contains('=') && !pair.starts_with('=') {
+                self.cookie_jar
+                    .add_cookie_str(&format!("{}; Path=/", pair), url);
+            }
+        }
+    }
+
+    /// 获取发往 `url` 的请求会携带的 Cookie 字符串，无 Cookie 时返回 `None`
+    pub fn cookie_header(&self, url: &reqwest::Url) -> Option<String> {
+        self.cookie_jar.cookie_string(url)
+    }
+
+    /// 导出所有 Cookie，可序列化后保存会话
+    pub fn export_cookies(&self) -> Vec<StoredCookie> {
+        self.cookie_jar.export()
+    }
+
+    /// 导入之前导出的 Cookie，恢复会话
+    pub fn import_cookies<'a>(&self, cookies: impl IntoIterator<Item = &'a StoredCookie>) {
+        self.cookie_jar.import(cookies);
+    }
+
+    /// 设置会话请求头
+    ///
+    /// 会话请求头附加到之后的每个请求，优先级高于全局请求头、低于调用方显式指定的请求头
+    pub fn set_session_header(&self, name: impl Into<String>, value: impl Into<String>) {
+        self.session_headers
+            .insert(name.into().to_ascii_lowercase(), value.into());
+    }
+
     /// 发起 GET 请求
     pub async fn get(&self, url: &str) -> Result<reqwest::Response> {
         let mut request = self.client.get(url);
@@ -91,7 +288,7 @@ impl HttpClient {
             request = request.header("User-Agent", ua);
         }
 
-        self.execute_with_retry(request).await
+        self.execute_with_retry(request, None).await
     }
 
     /// 发起 POST 请求
@@ -112,7 +309,7 @@ impl HttpClient {
             request = request.header("User-Agent", ua);
         }
 
-        self.execute_with_retry(request).await
+        self.execute_with_retry(request, None).await
     }
 
     /// 发起 POST 表单请求
@@ -137,45 +334,165 @@ impl HttpClient {
             request = request.header("User-Agent", ua);
         }
 
-        self.execute_with_retry(request).await
+        self.execute_with_retry(request, None).await
+    }
+
+    /// 发起任意方法的请求
+    ///
+    /// `headers` 覆盖全局请求头中的同名项；未显式指定 `Content-Type` 时按请求体类型设置默认值。
+    /// `timeouts` 中设置的项覆盖客户端的全局超时。返回时只读到了响应头，
+    /// 读取响应体应使用 [`read_text`](crate::http::body::read_text) 并传入 `timeouts.read`
+    /// （[`fetch_page`](crate::http::fetch_page) 即如此），否则响应体只受客户端全局的
+    /// `read_timeout` 约束
+    pub async fn request(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        body: Option<RequestBody>,
+        headers: &HashMap<String, String>,
+        timeouts: RequestTimeouts,
+    ) -> Result<reqwest::Response> {
+        let method = reqwest::Method::from_bytes(method.as_str().as_bytes())
+            .map_err(|e| RuntimeError::HttpRequest(format!("Invalid method: {}", e)))?;
+        let mut request = self.client.request(method, url);
+
+        // 合并请求头：全局 < User-Agent < 会话 < 调用方
+        let mut merged: HashMap<String, String> = HashMap::new();
+        if let Some(req_config) = &self.config.request
+            && let Some(global) = &req_config.headers
+        {
+            for (key, value) in global {
+                merged.insert(key.to_ascii_lowercase(), value.as_str().to_string());
+            }
+        }
+        if let Some(ua) = &self.config.user_agent {
+            merged.insert("user-agent".to_string(), ua.clone());
+        }
+        for entry in self.session_headers.iter() {
+            merged.insert(entry.key().clone(), entry.value().clone());
+        }
+        for (key, value) in headers {
+            merged.insert(key.to_ascii_lowercase(), value.clone());
+        }
+
+        let has_content_type = merged.contains_key("content-type");
+        request = match body {
+            Some(RequestBody::Raw(text)) => {
+                if !has_content_type {
+                    request = request.header(
+                        reqwest::header::CONTENT_TYPE,
+                        "application/x-www-form-urlencoded",
+                    );
+                }
+                request.body(text)
+            }
+            Some(RequestBody::Form(form)) => request.form(&form),
+            Some(RequestBody::Json(json)) => request.json(&json),
+            None => request,
+        };
+        // `headers` 覆盖同名项，确保显式的 Content-Type 优先于请求体默认值
+        let mut header_map = reqwest::header::HeaderMap::new();
+        for (key, value) in &merged {
+            let name = reqwest::header::HeaderName::from_bytes(key.as_bytes()).map_err(|e| {
+                RuntimeError::HttpRequest(format!("Invalid header '{}': {}", key, e))
+            })?;
+            let value = reqwest::header::HeaderValue::from_str(value).map_err(|e| {
+                RuntimeError::HttpRequest(format!("Invalid header '{}': {}", key, e))
+            })?;
+            header_map.insert(name, value);
+        }
+        request = request.headers(header_map);
+        if let Some(total) = timeouts.total {
+            request = request.timeout(total);
+        }
+
+        self.execute_with_retry(request, timeouts.read).await
     }
 
-    /// 执行请求（带重试）
+    /// 执行请求（带重试与限流）
+    ///
+    /// 连接错误以及 5xx/429 响应会触发重试，间隔按 `retry_backoff` 指数增长；
+    /// 响应带有 `Retry-After`（秒）时优先使用该值。等待时间最长为 [`MAX_RETRY_DELAY`]。
+    ///
+    /// `read_timeout` 限制等待响应头的时长，超时按连接错误处理（可重试）；
+    /// 响应体的读取超时由调用方在读取时施加
     async fn execute_with_retry(
         &self,
         request: reqwest::RequestBuilder,
+        read_timeout: Option<Duration>,
     ) -> Result<reqwest::Response> {
         let retry_count = self.config.retry_count.unwrap_or(0);
-        let retry_delay = self.config.retry_delay.unwrap_or(1000);
+        let backoff = self.config.retry_backoff.unwrap_or(1.0).max(1.0);
+        let mut delay = Duration::from_millis(self.config.retry_delay.unwrap_or(1000) as u64)
+            .min(MAX_RETRY_DELAY);
 
-        let mut last_error = None;
+        let mut last_error = String::new();
 
         for attempt in 0..=retry_count {
-            if attempt > 0 {
-                tokio::time::sleep(Duration::from_millis(retry_delay as u64)).await;
-            }
+            let req = request
+                .try_clone()
+                .ok_or_else(|| RuntimeError::HttpRequest("Failed to clone request".to_string()))?
+                .build()
+                .map_err(|e| RuntimeError::HttpRequest(format!("Invalid request: {}", e)))?;
+            let host = req.url().host_str().unwrap_or_default().to_string();
 
-            match request.try_clone() {
-                Some(req) => match req.send().await {
-                    Ok(response) => return Ok(response),
-                    Err(e) => {
-                        last_error = Some(e);
-                    }
+            let mut wait = delay;
+            let permit = self.limiter.acquire(&host).await;
+            let client = self.proxies.select(&host).unwrap_or(&self.client);
+            let result = match read_timeout {
+                Some(limit) => match tokio::time::timeout(limit, client.execute(req)).await {
+                    Ok(result) => result.map_err(|e| e.to_string()),
+                    Err(_) => Err(format!("read timed out after {}s", limit.as_secs())),
                 },
-                None => {
-                    return Err(RuntimeError::HttpRequest(
-                        "Failed to clone request".to_string(),
-                    ));
+                None => client.execute(req).await.map_err(|e| e.to_string()),
+            };
+            drop(permit);
+
+            match result {
+                Ok(response) => {
+                    let status = response.status();
+                    if !Self::is_retryable_status(status) || retry_count == 0 {
+                        return Ok(response);
+                    }
+                    if let Some(retry_after) = Self::retry_after(&response) {
+                        wait = retry_after.min(MAX_RETRY_DELAY);
+                    }
+                    last_error = format!("status {}", status);
                 }
+                Err(e) => last_error = e,
+            }
+
+            if attempt < retry_count {
+                tokio::time::sleep(wait).await;
+                delay = Duration::try_from_secs_f32(delay.as_secs_f32() * backoff)
+                    .map_or(MAX_RETRY_DELAY, |next| next.min(MAX_RETRY_DELAY));
             }
         }
 
         Err(RuntimeError::HttpRequest(format!(
-            "Request failed after {} retries: {}",
-            retry_count,
-            last_error.unwrap()
+            "Request failed after {} attempts: {}",
+            retry_count + 1,
+            last_error
         )))
     }
+
+    /// 是否为可重试的状态码（5xx 或 429）
+    fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+    }
+
+    /// 解析 `Retry-After` 响应头（仅支持秒数形式）
+    fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+        response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(Duration::from_secs)
+    }
 }
 
 impl Default for HttpClient {
@@ -2,7 +2,10 @@
 //!
 //! 为 HttpConfig 提供合并和转换功能
 
+use crate::{Result, context::FlowContext, error::RuntimeError};
 use crawler_schema::config::{HttpConfig, RequestConfig, ResponseConfig};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
 
 /// HTTP 配置扩展 trait
 pub trait HttpConfigExt {
@@ -19,14 +22,27 @@ impl HttpConfigExt for HttpConfig {
             user_agent: other.user_agent.clone().or_else(|| self.user_agent.clone()),
             timeout: other.timeout.or(self.timeout),
             proxy: other.proxy.clone().or_else(|| self.proxy.clone()),
+            proxies: other.proxies.clone().or_else(|| self.proxies.clone()),
+            proxy_strategy: other.proxy_strategy.or(self.proxy_strategy),
             follow_redirects: other.follow_redirects.or(self.follow_redirects),
             max_redirects: other.max_redirects.or(self.max_redirects),
+            resolve: match (&self.resolve, &other.resolve) {
+                (Some(base), Some(over)) => {
+                    let mut merged = base.clone();
+                    merged.extend(over.clone());
+                    Some(merged)
+                }
+                (base, over) => over.clone().or_else(|| base.clone()),
+            },
             connect_timeout: other.connect_timeout.or(self.connect_timeout),
+            read_timeout: other.read_timeout.or(self.read_timeout),
             verify_ssl: other.verify_ssl.or(self.verify_ssl),
             request_delay: other.request_delay.or(self.request_delay),
             max_concurrent: other.max_concurrent.or(self.max_concurrent),
             retry_count: other.retry_count.or(self.retry_count),
             retry_delay: other.retry_delay.or(self.retry_delay),
+            retry_backoff: other.retry_backoff.or(self.retry_backoff),
+            render: other.render.clone().or_else(|| self.render.clone()),
             request: merge_request_config(&self.request, &other.request),
             response: merge_response_config(&self.response, &other.response),
         }
@@ -39,6 +55,55 @@ impl HttpConfigExt for HttpConfig {
     }
 }
 
+/// 响应配置扩展 trait
+pub trait ResponseConfigExt {
+    /// 检查响应状态码是否符合 `expect_status`
+    fn check_status(&self, status: u16) -> Result<()>;
+
+    /// 按 `capture_headers` 将响应头写入流程变量
+    fn capture_headers(&self, headers: &HeaderMap, flow_context: &mut FlowContext);
+}
+
+impl ResponseConfigExt for ResponseConfig {
+    fn check_status(&self, status: u16) -> Result<()> {
+        match &self.expect_status {
+            Some(expected) if !expected.contains(&status) => Err(RuntimeError::HttpRequest(
+                format!("unexpected status {} (expected {:?})", status, expected),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn capture_headers(&self, headers: &HeaderMap, flow_context: &mut FlowContext) {
+        for (var, header) in self.capture_headers.iter().flatten() {
+            let values: Vec<&str> = headers
+                .get_all(header.as_str())
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .collect();
+            let value = if values.is_empty() {
+                Value::Null
+            } else {
+                Value::String(values.join("; "))
+            };
+            flow_context.set(var.clone(), value);
+        }
+    }
+}
+
+/// 解析流程的生效 HTTP 配置（流程配置覆盖全局配置）
+pub fn resolve_flow_config(
+    global: Option<&HttpConfig>,
+    flow: Option<&HttpConfig>,
+) -> Option<HttpConfig> {
+    match (global, flow) {
+        (None, None) => None,
+        (Some(g), None) => Some(g.clone()),
+        (None, Some(f)) => Some(f.clone()),
+        (Some(g), Some(f)) => Some(g.merge(f)),
+    }
+}
+
 /// 合并请求配置
 fn merge_request_config(
     base: &Option<RequestConfig>,
@@ -53,8 +118,11 @@ fn merge_request_config(
             if o.method.is_some() {
                 merged.method = o.method;
             }
-            if o.body.is_some() {
+            // 请求体三选一，覆盖方指定任意一种时整体替换
+            if o.body.is_some() || o.form.is_some() || o.json.is_some() {
                 merged.body = o.body.clone();
+                merged.form = o.form.clone();
+                merged.json = o.json.clone();
             }
             if o.content_type.is_some() {
                 merged.content_type = o.content_type.clone();
@@ -95,6 +163,18 @@ fn merge_response_config(
             if o.preprocess.is_some() {
                 merged.preprocess = o.preprocess.clone();
             }
+            if o.expect_status.is_some() {
+                merged.expect_status = o.expect_status.clone();
+            }
+            // 合并 capture_headers
+            merged.capture_headers = match (&b.capture_headers, &o.capture_headers) {
+                (Some(bh), Some(oh)) => {
+                    let mut h = bh.clone();
+                    h.extend(oh.clone());
+                    Some(h)
+                }
+                (base, over) => over.clone().or_else(|| base.clone()),
+            };
             Some(merged)
         }
     }
@@ -0,0 +1,192 @@
+//! # Cookie 存储
+//!
+//! 基于 `reqwest::cookie::Jar`，在请求间保持 Cookie，并额外记录已保存的 Cookie，
+//! 以便导出为可序列化的快照（供 UI 保存会话）或从快照恢复。
+//!
+//! 记录只保存名称、值、所属域名以及是否仅限该主机（未设置 `Domain` 属性），
+//! 不保存路径与过期时间；`Max-Age` 不大于 0 或 `Expires` 已过期的响应 Cookie 视为删除
+//! （同时设置时以 `Max-Age` 为准）。
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use reqwest::{
+    Url,
+    cookie::{CookieStore, Jar},
+    header::HeaderValue,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 导出的 Cookie
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCookie {
+    /// 所属域名（不含前导 `.`），对该域名及其子域名生效
+    pub domain: String,
+    /// 名称
+    pub name: String,
+    /// 值
+    pub value: String,
+    /// 仅对 `domain` 本身生效，不发往子域名（响应未设置 `Domain` 属性）
+    #[serde(default)]
+    pub host_only: bool,
+}
+
+/// 已记录的 Cookie 值
+#[derive(Debug, Clone)]
+struct RecordedCookie {
+    value: String,
+    host_only: bool,
+}
+
+/// Cookie 存储
+///
+/// 实现 `CookieStore`，作为 reqwest 客户端的 Cookie 提供者
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    jar: Jar,
+    /// 域名 -> 名称 -> 值
+    entries: DashMap<String, BTreeMap<String, RecordedCookie>>,
+}
+
+impl CookieJar {
+    /// 创建空的 Cookie 存储
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 写入一条 `Set-Cookie` 形式的 Cookie，`url` 为来源地址
+    pub fn add_cookie_str(&self, cookie: &str, url: &Url) {
+        self.jar.add_cookie_str(cookie, url);
+        self.record(cookie, url);
+    }
+
+    /// 获取发往 `url` 的请求会携带的 Cookie 字符串，无 Cookie 时返回 `None`
+    pub fn cookie_string(&self, url: &Url) -> Option<String> {
+        self.jar
+            .cookies(url)
+            .and_then(|value| value.to_str().ok().map(str::to_string))
+    }
+
+    /// 导出所有 Cookie，按域名、名称排序
+    pub fn export(&self) -> Vec<StoredCookie> {
+        let mut cookies: Vec<StoredCookie> = self
+            .entries
+            .iter()
+            .flat_map(|entry| {
+                let domain = entry.key().clone();
+                entry
+                    .value()
+                    .iter()
+                    .map(|(name, cookie)| StoredCookie {
+                        domain: domain.clone(),
+                        name: name.clone(),
+                        value: cookie.value.clone(),
+                        host_only: cookie.host_only,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        cookies.sort_by(|a, b| (&a.domain, &a.name).cmp(&(&b.domain, &b.name)));
+        cookies
+    }
+
+    /// 导入 Cookie，同名 Cookie 被覆盖；域名无法构成 URL 的项被忽略
+    ///
+    /// `host_only` 的 Cookie 不写入 `Domain` 属性，导入后同样只发往该主机
+    pub fn import<'a>(&self, cookies: impl IntoIterator<Item = &'a StoredCookie>) {
+        for cookie in cookies {
+            let Ok(url) = Url::parse(&format!("https://{}/", cookie.domain)) else {
+                tracing::warn!("忽略无效的 Cookie 域名: {}", cookie.domain);
+                continue;
+            };
+            let set_cookie = if cookie.host_only {
+                format!("{}={}; Path=/", cookie.name, cookie.value)
+            } else {
+                format!(
+                    "{}={}; Domain={}; Path=/",
+                    cookie.name, cookie.value, cookie.domain
+                )
+            };
+            self.add_cookie_str(&set_cookie, &url);
+        }
+    }
+
+    /// 记录 Cookie 的名称、值与域名
+    fn record(&self, cookie: &str, url: &Url) {
+        let mut parts = cookie.split(';');
+        let Some((name, value)) = parts.next().and_then(|pair| pair.split_once('=')) else {
+            return;
+        };
+        let (name, value) = (name.trim(), value.trim());
+        if name.is_empty() {
+            return;
+        }
+
+        let mut domain = url.host_str().unwrap_or_default().to_string();
+        let mut host_only = true;
+        let mut max_age_expired = None;
+        let mut expires_passed = false;
+        for attr in parts {
+            let Some((key, val)) = attr.split_once('=') else {
+                continue;
+            };
+            let val = val.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" if !val.is_empty() => {
+                    domain = val.trim_start_matches('.').to_ascii_lowercase();
+                    host_only = false;
+                }
+                "max-age" => {
+                    max_age_expired = val.parse::<i64>().ok().map(|age| age <= 0);
+                }
+                "expires" => {
+                    expires_passed =
+                        parse_expires(val).is_some_and(|expires| expires <= Utc::now());
+                }
+                _ => {}
+            }
+        }
+        if domain.is_empty() {
+            return;
+        }
+
+        if max_age_expired.unwrap_or(expires_passed) {
+            if let Some(mut names) = self.entries.get_mut(&domain) {
+                names.remove(name);
+            }
+        } else {
+            self.entries.entry(domain).or_default().insert(
+                name.to_string(),
+                RecordedCookie {
+                    value: value.to_string(),
+                    host_only,
+                },
+            );
+        }
+    }
+}
+
+/// 解析 `Expires` 属性中的 HTTP 日期
+///
+/// 兼容 `Wed, 21 Oct 2015 07:28:00 GMT` 与旧式的 `Wed, 21-Oct-2015 07:28:00 GMT`
+fn parse_expires(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(&value.replace('-', " "))
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+impl CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let headers: Vec<&HeaderValue> = cookie_headers.collect();
+        for header in &headers {
+            if let Ok(cookie) = header.to_str() {
+                self.record(cookie, url);
+            }
+        }
+        self.jar.set_cookies(&mut headers.into_iter(), url);
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        self.jar.cookies(url)
+    }
+}
@@ -0,0 +1,153 @@
+//! # 页面抓取
+//!
+//! 各流程执行器共用的"渲染 URL 之后、提取字段之前"的抓取步骤
+
+use crate::{
+    Result,
+    RuntimeError,
+    challenge::{ChallengeManager, ResponseContext},
+    context::{FlowContext, RuntimeContext},
+    extractor::filter::executor::FINAL_URL_VAR,
+    http::{
+        RequestBuilder,
+        RequestTimeouts,
+        ResponseConfigExt,
+        body::{declared_encoding, read_text},
+    },
+    webview::render_page,
+};
+use crawler_schema::{config::HttpConfig, template::Template};
+use reqwest::header::HeaderMap;
+
+/// 抓取到的页面
+#[derive(Debug, Clone)]
+pub struct FetchedPage {
+    /// 最终 URL（重定向之后）
+    pub url: String,
+    /// 解码后的响应正文
+    pub body: String,
+}
+
+/// 按流程的 HTTP 配置抓取页面
+///
+/// `http_config` 为全局配置与流程覆盖合并后的配置。依次处理：
+/// - 计入流程的 HTTP 请求数（见 [`FlowContext::record_http_request`]）
+/// - 演练模式下以样本代替网络请求
+/// - 配置了 `render` 时由 WebView 渲染页面，否则发起请求并按声明的编码解码正文
+/// - 规则配置了 `challenge` 时检测验证页面，由处理器获取凭证后重试一次
+/// - 检查状态码、捕获响应头（WebView 未报告状态码时跳过状态码检查）
+///
+/// 最终 URL 写入流程变量 [`FINAL_URL_VAR`]，供 `absolute_url` 等过滤器使用
+pub async fn fetch_page(
+    url: &str,
+    http_config: Option<&HttpConfig>,
+    runtime_context: &RuntimeContext,
+    flow_context: &mut FlowContext,
+) -> Result<FetchedPage> {
+    flow_context.record_http_request()?;
+    let response_config = http_config.and_then(|c| c.response.as_ref());
+
+    let page = match (
+        flow_context.take_fixture(url)?,
+        http_config.and_then(|c| c.render.as_ref()),
+    ) {
+        (Some(fixture), _) => FetchedPage {
+            url: url.to_string(),
+            body: fixture,
+        },
+        (None, Some(render)) => {
+            let user_agent = http_config.and_then(|c| c.user_agent.as_deref());
+            let page = render_page(render, url, user_agent, runtime_context).await?;
+            if let Some(response_config) = response_config {
+                if let Some(status) = page.status {
+                    response_config.check_status(status)?;
+                }
+                response_config.capture_headers(&page.headers, flow_context);
+            }
+            FetchedPage {
+                url: page.url,
+                body: page.html,
+            }
+        }
+        (None, None) => {
+            let mut page = request_page(url, http_config, runtime_context, flow_context).await?;
+            if let Some(config) = &runtime_context.rule().challenge {
+                let manager = ChallengeManager::new(config.clone(), flow_context.runtime().clone())
+                    .with_credentials_cache(runtime_context.challenge_credentials().clone());
+                let response = page.detection_context();
+                if manager.detect(&response).detected {
+                    // 处理验证后（凭证已写入共享客户端）重试一次
+                    manager.handle(url, response).await?;
+                    flow_context.record_http_request()?;
+                    page = request_page(url, http_config, runtime_context, flow_context).await?;
+                    if manager.detect(&page.detection_context()).detected {
+                        manager.clear_cached_credentials(url).await;
+                        return Err(RuntimeError::ChallengeFailed(format!(
+                            "获取凭证后仍返回验证页面: {}",
+                            url
+                        )));
+                    }
+                }
+            }
+
+            if let Some(response_config) = response_config {
+                response_config.check_status(page.status)?;
+                response_config.capture_headers(&page.headers, flow_context);
+            }
+            FetchedPage {
+                url: page.url,
+                body: page.body,
+            }
+        }
+    };
+
+    flow_context.set(FINAL_URL_VAR, serde_json::json!(&page.url));
+    Ok(page)
+}
+
+/// 网络请求得到的响应
+struct RawPage {
+    status: u16,
+    headers: HeaderMap,
+    url: String,
+    body: String,
+}
+
+impl RawPage {
+    /// 供验证检测器使用的响应上下文
+    fn detection_context(&self) -> ResponseContext {
+        let headers = self
+            .headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+            .collect();
+        ResponseContext::new(self.status, headers, self.body.clone(), self.url.clone())
+    }
+}
+
+/// 发起请求并按声明的编码解码正文
+async fn request_page(
+    url: &str,
+    http_config: Option<&HttpConfig>,
+    runtime_context: &RuntimeContext,
+    flow_context: &FlowContext,
+) -> Result<RawPage> {
+    let timeouts = http_config
+        .map(RequestTimeouts::from_config)
+        .unwrap_or_default();
+    let mut request = RequestBuilder::new(runtime_context.http_client(), Template::literal(url))
+        .timeouts(timeouts);
+    if let Some(request_config) = http_config.and_then(|c| c.request.as_ref()) {
+        request = request.with_config(request_config);
+    }
+    let response = request.execute(flow_context).await?;
+
+    let response_config = http_config.and_then(|c| c.response.as_ref());
+    let encoding = declared_encoding(response_config, &runtime_context.rule().meta);
+    Ok(RawPage {
+        status: response.status().as_u16(),
+        headers: response.headers().clone(),
+        url: response.url().to_string(),
+        body: read_text(response, encoding, timeouts.read).await?,
+    })
+}
@@ -2,10 +2,15 @@
 //!
 //! 提供 HTTP 请求功能和配置管理
 
+pub mod body;
 pub mod client;
 pub mod config;
+pub mod cookie;
+pub mod fetch;
 pub mod request;
 
-pub use client::HttpClient;
-pub use config::HttpConfigExt;
+pub use client::{HttpClient, RequestBody, RequestTimeouts};
+pub use config::{HttpConfigExt, ResponseConfigExt, resolve_flow_config};
+pub use cookie::{CookieJar, StoredCookie};
+pub use fetch::{FetchedPage, fetch_page};
 pub use request::RequestBuilder;
@@ -2,11 +2,18 @@
 //!
 //! 提供便捷的请求构建接口
 
-use crate::{Result, context::FlowContext, http::HttpClient, template::TemplateExt};
+use crate::{
+    Result,
+    context::FlowContext,
+    http::{HttpClient, RequestBody, client::RequestTimeouts},
+    template::TemplateExt,
+};
 use crawler_schema::{
     config::{HttpMethod, RequestConfig},
     template::Template,
 };
+use serde_json::Value;
+use std::collections::HashMap;
 
 /// 请求构建器
 pub struct RequestBuilder<'a> {
@@ -14,7 +21,11 @@ pub struct RequestBuilder<'a> {
     url: Template,
     method: HttpMethod,
     body: Option<Template>,
-    headers: std::collections::HashMap<String, Template>,
+    form: Option<HashMap<String, Template>>,
+    json: Option<Value>,
+    content_type: Option<String>,
+    headers: HashMap<String, Template>,
+    timeouts: RequestTimeouts,
 }
 
 impl<'a> RequestBuilder<'a> {
@@ -25,7 +36,11 @@ impl<'a> RequestBuilder<'a> {
             url,
             method: HttpMethod::Get,
             body: None,
-            headers: std::collections::HashMap::new(),
+            form: None,
+            json: None,
+            content_type: None,
+            headers: HashMap::new(),
+            timeouts: RequestTimeouts::default(),
         }
     }
 
@@ -41,12 +56,30 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    /// 设置表单请求体
+    pub fn form(mut self, form: HashMap<String, Template>) -> Self {
+        self.form = Some(form);
+        self
+    }
+
+    /// 设置 JSON 请求体（字符串值按模板渲染）
+    pub fn json(mut self, json: Value) -> Self {
+        self.json = Some(json);
+        self
+    }
+
     /// 添加请求头
     pub fn header<K: Into<String>>(mut self, key: K, value: Template) -> Self {
         self.headers.insert(key.into(), value);
         self
     }
 
+    /// 设置本次请求的超时（覆盖客户端的全局超时）
+    pub fn timeouts(mut self, timeouts: RequestTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
     /// 应用请求配置
     pub fn with_config(mut self, config: &RequestConfig) -> Self {
         if let Some(method) = &config.method {
@@ -55,6 +88,15 @@ impl<'a> RequestBuilder<'a> {
         if let Some(body) = &config.body {
             self.body = Some(body.clone());
         }
+        if let Some(form) = &config.form {
+            self.form = Some(form.clone());
+        }
+        if let Some(json) = &config.json {
+            self.json = Some(json.clone());
+        }
+        if let Some(content_type) = &config.content_type {
+            self.content_type = Some(content_type.clone());
+        }
         if let Some(headers) = &config.headers {
             self.headers.extend(headers.clone());
         }
@@ -66,17 +108,56 @@ impl<'a> RequestBuilder<'a> {
         // 渲染 URL
         let url = self.url.render(context)?;
 
-        match self.method {
-            HttpMethod::Get => self.client.get(&url).await,
-            HttpMethod::Post => {
-                let body = if let Some(body_template) = self.body {
-                    body_template.render(context)?
-                } else {
-                    String::new()
-                };
-                self.client.post(&url, body).await
-            }
-            _ => todo!("Implement other HTTP methods"),
+        let mut headers = HashMap::new();
+        for (key, value) in &self.headers {
+            headers.insert(key.clone(), value.render(context)?);
         }
+        if let Some(content_type) = &self.content_type {
+            headers.insert("Content-Type".to_string(), content_type.clone());
+        }
+
+        // 仅 POST/PUT/PATCH 携带请求体
+        let body = if !self.method.has_body() {
+            None
+        } else if let Some(json) = &self.json {
+            Some(RequestBody::Json(Self::render_json(json, context)?))
+        } else if let Some(form) = &self.form {
+            let mut fields = Vec::with_capacity(form.len());
+            for (key, value) in form {
+                fields.push((key.clone(), value.render(context)?));
+            }
+            fields.sort();
+            Some(RequestBody::Form(fields))
+        } else {
+            match &self.body {
+                Some(body) => Some(RequestBody::Raw(body.render(context)?)),
+                None => None,
+            }
+        };
+
+        self.client
+            .request(self.method, &url, body, &headers, self.timeouts)
+            .await
+    }
+
+    /// 递归渲染 JSON 中的字符串值
+    fn render_json(value: &Value, context: &FlowContext) -> Result<Value> {
+        Ok(match value {
+            Value::String(s) => Value::String(Template::from(s.clone()).render(context)?),
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| Self::render_json(item, context))
+                    .collect::<Result<_>>()?,
+            ),
+            Value::Object(map) => {
+                let mut rendered = serde_json::Map::with_capacity(map.len());
+                for (key, item) in map {
+                    rendered.insert(key.clone(), Self::render_json(item, context)?);
+                }
+                Value::Object(rendered)
+            }
+            other => other.clone(),
+        })
     }
 }
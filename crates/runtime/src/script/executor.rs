@@ -7,7 +7,7 @@ use crate::{
     context::{FlowContext, RuntimeContext},
     error::RuntimeError,
     extractor::{SharedValue, value::ExtractValueData},
-    script::{ScriptContext, ScriptEngine, ScriptEngineFactory, ScriptLanguage},
+    script::{ScriptContext, ScriptLanguage},
 };
 use crawler_schema::script::{Script, ScriptEngine as SchemaScriptEngine, ScriptSource};
 use std::{collections::HashMap, sync::Arc};
@@ -20,14 +20,14 @@ impl ScriptExecutor {
     pub fn execute(
         script: &Script,
         input: &ExtractValueData,
-        _runtime_context: &RuntimeContext,
+        runtime_context: &RuntimeContext,
         flow_context: &FlowContext,
     ) -> Result<SharedValue> {
         // 1. 加载脚本代码
         let code = Self::load_script_code(script)?;
 
-        // 2. 获取脚本引擎
-        let engine = Self::get_engine(script);
+        // 2. 获取脚本引擎（运行时内按语言复用）
+        let engine = runtime_context.script_engine(Self::language(script));
 
         // 3. 转换输入
         let input_str = Self::value_to_input(input);
@@ -43,8 +43,8 @@ impl ScriptExecutor {
         }
 
         // 添加上下文变量
-        for (key, value) in flow_context.data() {
-            variables.insert(key.clone(), value.clone());
+        for (key, value) in flow_context.variables() {
+            variables.insert(key, value);
         }
 
         // 5. 创建脚本上下文
@@ -57,20 +57,18 @@ impl ScriptExecutor {
         Ok(Self::parse_output(result, input))
     }
 
-    /// 获取脚本使用的引擎
-    fn get_engine(script: &Script) -> Arc<dyn ScriptEngine> {
+    /// 获取脚本使用的语言
+    pub(crate) fn language(script: &Script) -> ScriptLanguage {
         match script.engine() {
-            SchemaScriptEngine::Rhai => ScriptEngineFactory::create(ScriptLanguage::Rhai),
-            SchemaScriptEngine::JavaScript => {
-                ScriptEngineFactory::create(ScriptLanguage::JavaScript)
-            }
-            SchemaScriptEngine::Lua => ScriptEngineFactory::create(ScriptLanguage::Lua),
-            SchemaScriptEngine::Python => ScriptEngineFactory::create(ScriptLanguage::Python),
+            SchemaScriptEngine::Rhai => ScriptLanguage::Rhai,
+            SchemaScriptEngine::JavaScript => ScriptLanguage::JavaScript,
+            SchemaScriptEngine::Lua => ScriptLanguage::Lua,
+            SchemaScriptEngine::Python => ScriptLanguage::Python,
         }
     }
 
     /// 加载脚本代码
-    fn load_script_code(script: &Script) -> Result<String> {
+    pub(crate) fn load_script_code(script: &Script) -> Result<String> {
         match script.source() {
             ScriptSource::Code(code) => Ok(code.to_string()),
             ScriptSource::Url(url) => {
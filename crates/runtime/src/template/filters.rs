@@ -0,0 +1,199 @@
+//! # 模板过滤器
+//!
+//! 将运行时过滤器注册表中的过滤器注册到 Tera，使模板管道
+//! `{{ title | trim | cn_num }}` 与提取步骤中的 `filter` 共用同一套实现。
+//!
+//! 与 Tera 内置过滤器同名的运行时过滤器（如 `truncate`、`round`、`replace`）不会注册，
+//! 模板中始终使用 Tera 的内置语义，已有模板的行为保持不变。
+//!
+//! Tera 过滤器只接受命名参数，转换为运行时过滤器的位置参数时：
+//! - 提供 `args` 数组时按数组顺序传入，如 `{{ s | hash(args=["md5"]) }}`
+//! - 否则按过滤器声明的参数名（见 `Filter::params`）取值，如 `{{ s | hash(algorithm="md5",
+//!   encoding="base64") }}`；未声明的参数名报错
+
+use super::syntax::RAW_BLOCK;
+use crate::extractor::{filter::registry::global_registry, value::ExtractValueData};
+use regex::Regex;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+};
+use tera::Tera;
+
+/// Tera 内置过滤器（运行时注册表中不存在时仍可使用）
+const TERA_BUILTIN_FILTERS: &[&str] = &[
+    "lower",
+    "upper",
+    "wordcount",
+    "capitalize",
+    "replace",
+    "addslashes",
+    "slugify",
+    "title",
+    "trim",
+    "trim_start",
+    "trim_end",
+    "trim_start_matches",
+    "trim_end_matches",
+    "truncate",
+    "linebreaksbr",
+    "spaceless",
+    "indent",
+    "striptags",
+    "escape",
+    "escape_xml",
+    "safe",
+    "urlencode",
+    "urlencode_strict",
+    "split",
+    "int",
+    "float",
+    "json_encode",
+    "as_str",
+    "default",
+    "first",
+    "last",
+    "nth",
+    "join",
+    "length",
+    "reverse",
+    "sort",
+    "unique",
+    "slice",
+    "group_by",
+    "filter",
+    "map",
+    "concat",
+    "get",
+    "pluralize",
+    "round",
+    "filesizeformat",
+    "date",
+    "abs",
+];
+
+/// 仅在模板中可用的辅助过滤器
+const TEMPLATE_FILTERS: &[&str] = &["default_if_empty", "truthy"];
+
+/// 模板标签 `{{ ... }}` / `{% ... %}`
+static TAG_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)\{\{(.*?)\}\}|\{%(.*?)%\}").expect("valid regex"));
+
+/// 字符串字面量
+static STRING_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""[^"]*"|'[^']*'|`[^`]*`"#).expect("valid regex"));
+
+/// 管道中的过滤器名称
+static FILTER_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\|\s*([A-Za-z_][A-Za-z0-9_]*)").expect("valid regex"));
+
+/// 将运行时过滤器注册到 Tera 实例（与 Tera 内置过滤器同名的跳过）
+pub(crate) fn register_runtime_filters(tera: &mut Tera) {
+    // 值为空（null、空字符串、空数组）时返回 `value` 参数，配合 `default:` 简写使用
+    tera.register_filter(
+        "default_if_empty",
+        |value: &Value, kwargs: &HashMap<String, Value>| -> tera::Result<Value> {
+            if ExtractValueData::from_json(value).is_empty() {
+                Ok(kwargs.get("value").cloned().unwrap_or(Value::Null))
+            } else {
+                Ok(value.clone())
+            }
+        },
+    );
+    // 按提取值的真值规则返回布尔值，用于条件表达式
+    tera.register_filter(
+        "truthy",
+        |value: &Value, _: &HashMap<String, Value>| -> tera::Result<Value> {
+            Ok(Value::Bool(ExtractValueData::from_json(value).is_truthy()))
+        },
+    );
+
+    let registry = global_registry();
+    for name in registry.names() {
+        if TERA_BUILTIN_FILTERS.contains(&name) {
+            continue;
+        }
+        let Some(filter) = registry.get(name) else {
+            continue;
+        };
+        let filter_name = name.to_string();
+        tera.register_filter(
+            name,
+            move |value: &Value, kwargs: &HashMap<String, Value>| -> tera::Result<Value> {
+                let args = positional_args(&filter_name, filter.params(), kwargs)?;
+                let input = Arc::new(ExtractValueData::from_json(value));
+                filter
+                    .apply(&input, &args)
+                    .map(|output| output.to_owned_json())
+                    .map_err(|e| {
+                        tera::Error::msg(format!("过滤器 '{}' 执行失败: {}", filter_name, e))
+                    })
+            },
+        );
+    }
+}
+
+/// 查找模板管道中引用的未知过滤器
+pub(crate) fn unknown_filters(template: &str) -> Vec<String> {
+    let registry = global_registry();
+    let source = RAW_BLOCK.replace_all(template, "");
+
+    let mut unknown = Vec::new();
+    for tag in TAG_PATTERN.captures_iter(&source) {
+        let Some(body) = tag.get(1).or_else(|| tag.get(2)) else {
+            continue;
+        };
+        let body = STRING_PATTERN.replace_all(body.as_str(), "\"\"");
+        for filter in FILTER_PATTERN.captures_iter(&body) {
+            let name = &filter[1];
+            if registry.get(name).is_none()
+                && !TERA_BUILTIN_FILTERS.contains(&name)
+                && !TEMPLATE_FILTERS.contains(&name)
+                && !unknown.iter().any(|n| n == name)
+            {
+                unknown.push(name.to_string());
+            }
+        }
+    }
+    unknown
+}
+
+/// 将 Tera 命名参数转换为位置参数
+///
+/// 按 `params` 的顺序取值，末尾未提供的可选参数省略，中间未提供的以 `null` 占位
+fn positional_args(
+    name: &str,
+    params: &[&str],
+    kwargs: &HashMap<String, Value>,
+) -> tera::Result<Vec<Value>> {
+    if let Some(args) = kwargs.get("args") {
+        if kwargs.len() > 1 {
+            return Err(tera::Error::msg(format!(
+                "过滤器 '{}' 的 args 不能与命名参数同时使用",
+                name
+            )));
+        }
+        return Ok(match args {
+            Value::Array(args) => args.clone(),
+            other => vec![other.clone()],
+        });
+    }
+
+    if let Some(unknown) = kwargs.keys().find(|key| !params.contains(&key.as_str())) {
+        return Err(tera::Error::msg(format!(
+            "过滤器 '{}' 不支持参数 '{}'（可用参数: {}）",
+            name,
+            unknown,
+            params.join(", ")
+        )));
+    }
+    let count = params
+        .iter()
+        .rposition(|param| kwargs.contains_key(*param))
+        .map_or(0, |last| last + 1);
+    Ok(params[..count]
+        .iter()
+        .map(|param| kwargs.get(*param).cloned().unwrap_or(Value::Null))
+        .collect())
+}
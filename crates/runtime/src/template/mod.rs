@@ -1,11 +1,33 @@
 //! # 模板模块
 //!
 //! 提供模板渲染和验证功能
+//!
+//! 模板管道可使用运行时过滤器注册表中的过滤器，如 `{{ title | trim | cn_num }}`
+//! （与 Tera 内置过滤器同名时使用 Tera 的实现）；
+//! 可选变量可用 `{{ page | default: 1 }}` 指定默认值
+
+mod filters;
+mod syntax;
+mod variables;
 
 use crate::{Result, RuntimeError, context::FlowContext};
 use crawler_schema::template::Template;
+use std::{collections::BTreeSet, sync::LazyLock};
 use tera::Tera;
 
+/// 渲染时使用的模板名称
+const TEMPLATE_NAME: &str = "__template";
+
+/// 注册了运行时过滤器的基础引擎，每次渲染时克隆
+///
+/// 模板渲染的是 URL、请求体等文本而非 HTML，因此不做 HTML 转义
+static BASE_ENGINE: LazyLock<Tera> = LazyLock::new(|| {
+    let mut tera = Tera::default();
+    tera.autoescape_on(vec![]);
+    filters::register_runtime_filters(&mut tera);
+    tera
+});
+
 /// 模板渲染扩展 trait
 ///
 /// 为 `crawler_schema::Template` 添加运行时渲染能力
@@ -23,11 +45,56 @@ pub trait TemplateExt {
     /// | `{{ var }}` | 先查 Flow，再查 Runtime |
     /// | `{{ $.var }}` | 仅查 Runtime 全局变量 |
     fn render(&self, flow_context: &FlowContext) -> Result<String>;
+
+    /// 预编译模板，检查语法错误与未知的过滤器名称（不渲染）
+    fn validate(&self) -> Result<()>;
+
+    /// 模板引用的变量名（变量路径的首段，如 `item.name` 为 `item`）
+    ///
+    /// 基于解析后的语法树收集，字符串字面量与 `{% raw %}` 块中的内容不计入；
+    /// `$.var` 记为 `$`。模板无法解析时返回 `TemplateError`
+    fn variables(&self) -> Result<BTreeSet<String>>;
 }
 
 impl TemplateExt for Template {
     fn render(&self, flow_context: &FlowContext) -> Result<String> {
-        Tera::one_off(self.as_str(), &flow_context.to_tera_context()?, true).map_err(|e| {
+        if self.is_literal() {
+            return Ok(self.as_str().to_string());
+        }
+        let context = flow_context.to_tera_context()?;
+        let mut tera = BASE_ENGINE.clone();
+        tera.add_raw_template(TEMPLATE_NAME, &syntax::expand(self.as_str()))
+            .and_then(|_| tera.render(TEMPLATE_NAME, &context))
+            .map_err(|e| RuntimeError::TemplateError {
+                error: e.to_string(),
+            })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.is_literal() {
+            return Ok(());
+        }
+        let source = syntax::expand(self.as_str());
+        Tera::default()
+            .add_raw_template("__validate", &source)
+            .map_err(|e| RuntimeError::TemplateError {
+                error: e.to_string(),
+            })?;
+
+        let unknown = filters::unknown_filters(&source);
+        if !unknown.is_empty() {
+            return Err(RuntimeError::TemplateError {
+                error: format!("未知的过滤器: {}", unknown.join(", ")),
+            });
+        }
+        Ok(())
+    }
+
+    fn variables(&self) -> Result<BTreeSet<String>> {
+        if self.is_literal() {
+            return Ok(BTreeSet::new());
+        }
+        variables::referenced_variables(&syntax::expand(self.as_str())).map_err(|e| {
             RuntimeError::TemplateError {
                 error: e.to_string(),
             }
@@ -0,0 +1,200 @@
+//! # 模板语法扩展
+//!
+//! 在交给 Tera 解析前，将规则中的简写语法展开为等价的 Tera 语法：
+//!
+//! | 简写 | 展开 |
+//! |------|------|
+//! | `{{ page \| default: 1 }}` | `{{ page \| default(value=1) \| default_if_empty(value=1) }}` |
+//! | `{{ sort ? sort : "new" }}` | `{% if sort is defined and sort \| truthy %}{{ sort }}{% else %}{{ "new" }}{% endif %}` |
+//!
+//! - `default:` 在变量未定义、为 null 或为空（空字符串、空数组）时使用默认值； Tera 自带的
+//!   `default(value=...)` 仅处理未定义的变量。
+//! - 三元表达式条件的真值判断与提取步骤的 `condition` 一致（见 `ExtractValueData::is_truthy`），
+//!   未定义的变量视为假。`{% if %}` 等块标签不做改写，按 Tera 的语义求值。
+//! - 标签按记号扫描，字符串字面量中的 `?`、`:` 与 `| default:` 不会触发展开。
+//! - `{% raw %}...{% endraw %}` 块内的内容原样保留，不做展开。
+//!
+//! # 三元表达式的限制
+//!
+//! 三元表达式必须占据整个 `{{ }}` 标签，优先级最低；条件与两个分支只能是变量路径
+//! （如 `sort`、`item.name`、`$.base_url`）、字符串或数字字面量，不支持嵌套与过滤器。
+//! 更复杂的逻辑请使用 `{% if %}...{% elif %}...{% else %}...{% endif %}` 块，
+//! 块可任意嵌套，但需正确闭合，否则校验时报错。
+
+use regex::{Captures, Regex};
+use std::{borrow::Cow, sync::LazyLock};
+
+/// `{% raw %}...{% endraw %}` 块，其中的内容不会被解析
+pub(super) static RAW_BLOCK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)\{%-?\s*raw\s*-?%\}.*?\{%-?\s*endraw\s*-?%\}").expect("valid regex")
+});
+
+/// 输出标签 `{{ ... }}`，保留空白控制符 `-`
+static OUTPUT_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)\{\{(-?)(.*?)(-?)\}\}").expect("valid regex"));
+
+/// `| default: 值`，值为字符串字面量或不含空白与管道的记号
+static DEFAULT_COLON: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\|\s*default\s*:\s*("[^"]*"|'[^']*'|[^\s|}]+)"#).expect("valid regex")
+});
+
+/// 输出标签中的记号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    /// 字符串、数字字面量或变量路径
+    Operand(&'a str),
+    /// `?`
+    Question,
+    /// `:`
+    Colon,
+    /// 其他记号（运算符、括号、管道等）
+    Other,
+}
+
+/// 展开模板中的简写语法，无简写时不分配
+///
+/// `{% raw %}` 块原样保留，只展开块之外的部分
+pub(crate) fn expand(template: &str) -> Cow<'_, str> {
+    if !template.contains("{{") {
+        return Cow::Borrowed(template);
+    }
+
+    let mut expanded = String::with_capacity(template.len());
+    let mut last = 0;
+    for raw in RAW_BLOCK.find_iter(template) {
+        expanded.push_str(&expand_segment(&template[last..raw.start()]));
+        expanded.push_str(raw.as_str());
+        last = raw.end();
+    }
+    expanded.push_str(&expand_segment(&template[last..]));
+
+    if expanded == template {
+        Cow::Borrowed(template)
+    } else {
+        Cow::Owned(expanded)
+    }
+}
+
+/// 展开不含 `{% raw %}` 块的模板片段
+fn expand_segment(template: &str) -> String {
+    OUTPUT_TAG
+        .replace_all(template, |tag: &Captures| {
+            let (open, body, close) = (&tag[1], &tag[2], &tag[3]);
+
+            if let Some([condition, then, otherwise]) = ternary(body) {
+                return format!(
+                    "{{%{open} if {test} %}}{{{{ {then} }}}}{{% else %}}{{{{ {otherwise} }}}}{{% endif {close}%}}",
+                    test = truthy_test(condition),
+                );
+            }
+
+            let literals = string_literals(body);
+            let body = DEFAULT_COLON.replace_all(body, |default: &Captures| {
+                let whole = default.get(0).expect("match");
+                if literals.iter().any(|range| range.contains(&whole.start())) {
+                    return whole.as_str().to_string();
+                }
+                let value = &default[1];
+                format!(
+                    "| default(value={}) | default_if_empty(value={})",
+                    value, value
+                )
+            });
+            format!("{{{{{}{}{}}}}}", open, body, close)
+        })
+        .into_owned()
+}
+
+/// 识别占据整个标签的三元表达式 `条件 ? 值 : 值`，返回三个操作数
+fn ternary(body: &str) -> Option<[&str; 3]> {
+    match tokenize(body)?.as_slice() {
+        [
+            Token::Operand(condition),
+            Token::Question,
+            Token::Operand(then),
+            Token::Colon,
+            Token::Operand(otherwise),
+        ] => Some([condition, then, otherwise]),
+        _ => None,
+    }
+}
+
+/// 将标签内容切分为记号，字符串字面量未闭合时返回 `None`
+fn tokenize(body: &str) -> Option<Vec<Token<'_>>> {
+    let bytes = body.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        match bytes[i] {
+            b if b.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            quote @ (b'"' | b'\'' | b'`') => {
+                i += 1 + body[i + 1..].find(quote as char)? + 1;
+            }
+            b'?' => {
+                tokens.push(Token::Question);
+                i += 1;
+                continue;
+            }
+            b':' => {
+                tokens.push(Token::Colon);
+                i += 1;
+                continue;
+            }
+            b'-' | b'0'..=b'9' => {
+                i += 1;
+                while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                    i += 1;
+                }
+                if bytes[start] == b'-' && i == start + 1 {
+                    tokens.push(Token::Other);
+                    continue;
+                }
+            }
+            b'$' | b'_' | b'.' | b'A'..=b'Z' | b'a'..=b'z' => {
+                i += 1;
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric()
+                        || matches!(bytes[i], b'_' | b'.' | b'[' | b']'))
+                {
+                    i += 1;
+                }
+            }
+            _ => {
+                i += body[i..].chars().next().map_or(1, char::len_utf8);
+                tokens.push(Token::Other);
+                continue;
+            }
+        }
+        tokens.push(Token::Operand(&body[start..i]));
+    }
+    Some(tokens)
+}
+
+/// 标签内容中字符串字面量的字节范围
+fn string_literals(body: &str) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut rest = body.char_indices();
+    while let Some((start, c)) = rest.next() {
+        if matches!(c, '"' | '\'' | '`')
+            && let Some((end, _)) = rest.find(|(_, next)| *next == c)
+        {
+            ranges.push(start..end + 1);
+        }
+    }
+    ranges
+}
+
+/// 按提取值的真值规则判断条件，变量未定义时为假
+fn truthy_test(condition: &str) -> String {
+    let is_literal =
+        condition.starts_with(['"', '\'', '-']) || condition.starts_with(char::is_numeric);
+    if is_literal {
+        format!("{} | truthy", condition)
+    } else {
+        format!("{} is defined and {} | truthy", condition, condition)
+    }
+}
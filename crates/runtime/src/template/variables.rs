@@ -0,0 +1,146 @@
+//! # 模板变量分析
+//!
+//! 解析模板语法树，收集模板从上下文中读取的变量名，
+//! 供规则校验判断变量是否被引用，而不是在模板源码中按子串查找。
+
+use std::collections::BTreeSet;
+use tera::ast::{Expr, ExprVal, FunctionCall, Node};
+
+/// 收集模板引用的变量（变量路径的首段，如 `item.name` 记为 `item`）
+///
+/// `{% for %}` 的循环变量与 `{% set %}` 定义的局部变量不计入
+pub(super) fn referenced_variables(source: &str) -> tera::Result<BTreeSet<String>> {
+    let template = tera::Template::new("__variables", None, source)?;
+    let mut collector = Collector::default();
+    collector.nodes(&template.ast);
+    for local in &collector.locals {
+        collector.variables.remove(local);
+    }
+    Ok(collector.variables)
+}
+
+/// 语法树遍历状态
+#[derive(Default)]
+struct Collector {
+    variables: BTreeSet<String>,
+    locals: BTreeSet<String>,
+}
+
+impl Collector {
+    fn nodes(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            self.node(node);
+        }
+    }
+
+    fn node(&mut self, node: &Node) {
+        match node {
+            Node::VariableBlock(_, expr) => self.expr(expr),
+            Node::Set(_, set) => {
+                self.expr(&set.value);
+                self.locals.insert(set.key.clone());
+            }
+            Node::FilterSection(_, section, _) => {
+                self.call(&section.filter);
+                self.nodes(&section.body);
+            }
+            Node::Block(_, block, _) => self.nodes(&block.body),
+            Node::Forloop(_, forloop, _) => {
+                self.expr(&forloop.container);
+                // 循环变量只在循环体内有效，单独收集后去除
+                let mut body = Collector::default();
+                body.nodes(&forloop.body);
+                if let Some(empty_body) = &forloop.empty_body {
+                    body.nodes(empty_body);
+                }
+                for local in forloop.key.iter().chain([&forloop.value]) {
+                    body.variables.remove(local);
+                }
+                body.variables.remove("loop");
+                self.variables.extend(body.variables);
+                self.locals.extend(body.locals);
+            }
+            Node::If(branches, _) => {
+                for (_, condition, body) in &branches.conditions {
+                    self.expr(condition);
+                    self.nodes(body);
+                }
+                if let Some((_, body)) = &branches.otherwise {
+                    self.nodes(body);
+                }
+            }
+            Node::MacroDefinition(..)
+            | Node::Super
+            | Node::Text(_)
+            | Node::Extends(..)
+            | Node::Include(..)
+            | Node::ImportMacro(..)
+            | Node::Raw(..)
+            | Node::Break(_)
+            | Node::Continue(_)
+            | Node::Comment(..) => {}
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        self.value(&expr.val);
+        for filter in &expr.filters {
+            self.call(filter);
+        }
+    }
+
+    fn call(&mut self, call: &FunctionCall) {
+        for arg in call.args.values() {
+            self.expr(arg);
+        }
+    }
+
+    fn value(&mut self, value: &ExprVal) {
+        match value {
+            ExprVal::Ident(path) => self.ident(path),
+            ExprVal::Math(math) => {
+                self.expr(&math.lhs);
+                self.expr(&math.rhs);
+            }
+            ExprVal::Logic(logic) => {
+                self.expr(&logic.lhs);
+                self.expr(&logic.rhs);
+            }
+            ExprVal::In(contains) => {
+                self.expr(&contains.lhs);
+                self.expr(&contains.rhs);
+            }
+            ExprVal::Test(test) => {
+                self.ident(&test.ident);
+                for arg in &test.args {
+                    self.expr(arg);
+                }
+            }
+            ExprVal::FunctionCall(call) => self.call(call),
+            ExprVal::MacroCall(call) => {
+                for arg in call.args.values() {
+                    self.expr(arg);
+                }
+            }
+            ExprVal::Array(items) => {
+                for item in items {
+                    self.expr(item);
+                }
+            }
+            ExprVal::StringConcat(concat) => {
+                for value in &concat.values {
+                    self.value(value);
+                }
+            }
+            ExprVal::String(_) | ExprVal::Int(_) | ExprVal::Float(_) | ExprVal::Bool(_) => {}
+        }
+    }
+
+    /// 记录变量路径的首段
+    fn ident(&mut self, path: &str) {
+        let root = path.split(['.', '[']).next().unwrap_or(path).trim();
+        if !root.is_empty() {
+            self.variables.insert(root.to_string());
+        }
+    }
+}
@@ -1,6 +1,90 @@
 //! # 并发控制工具
 //!
-//! 提供并发限制、信号量等功能
+//! 提供按主机限流的并发限制与请求间隔控制
 
-// TODO: 实现并发控制
-// 可以使用 tokio::sync::Semaphore
+use dashmap::DashMap;
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    sync::{Mutex, OwnedSemaphorePermit, Semaphore},
+    time::Instant,
+};
+
+/// 单个主机的限流状态
+#[derive(Debug)]
+struct HostState {
+    /// 并发请求数限制
+    semaphore: Option<Arc<Semaphore>>,
+    /// 上一次请求的发起时间
+    last_request: Mutex<Option<Instant>>,
+}
+
+/// 按主机限流器
+///
+/// 同一主机的请求最多 `max_concurrent` 个并发，且相邻请求的发起时间至少间隔 `min_interval`。
+/// 不同主机之间互不影响。
+#[derive(Debug)]
+pub struct HostRateLimiter {
+    max_concurrent: Option<usize>,
+    min_interval: Duration,
+    hosts: DashMap<String, Arc<HostState>>,
+}
+
+/// 限流许可，释放时归还并发名额
+#[derive(Debug)]
+pub struct HostPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl HostRateLimiter {
+    /// 创建限流器
+    ///
+    /// `max_concurrent` 为 `None` 或 0 时不限制并发
+    pub fn new(max_concurrent: Option<usize>, min_interval: Duration) -> Self {
+        Self {
+            max_concurrent: max_concurrent.filter(|n| *n > 0),
+            min_interval,
+            hosts: DashMap::new(),
+        }
+    }
+
+    /// 是否未配置任何限制
+    pub fn is_unlimited(&self) -> bool {
+        self.max_concurrent.is_none() && self.min_interval.is_zero()
+    }
+
+    /// 获取指定主机的请求许可
+    ///
+    /// 先等待并发名额，再等待距上一次请求满足最小间隔
+    pub async fn acquire(&self, host: &str) -> HostPermit {
+        if self.is_unlimited() {
+            return HostPermit { _permit: None };
+        }
+
+        let state = self
+            .hosts
+            .entry(host.to_string())
+            .or_insert_with(|| {
+                Arc::new(HostState {
+                    semaphore: self.max_concurrent.map(|n| Arc::new(Semaphore::new(n))),
+                    last_request: Mutex::new(None),
+                })
+            })
+            .clone();
+
+        let permit = match &state.semaphore {
+            // 信号量不会被关闭，acquire 只会成功
+            Some(semaphore) => Arc::clone(semaphore).acquire_owned().await.ok(),
+            None => None,
+        };
+
+        if !self.min_interval.is_zero() {
+            let mut last = state.last_request.lock().await;
+            if let Some(previous) = *last {
+                tokio::time::sleep_until(previous + self.min_interval).await;
+            }
+            *last = Some(Instant::now());
+        }
+
+        HostPermit { _permit: permit }
+    }
+}
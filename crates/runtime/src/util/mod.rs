@@ -3,4 +3,4 @@
 pub mod cache;
 pub mod concurrent;
 
-// 预留：缓存、并发控制等工具
+// 预留：缓存等工具
@@ -0,0 +1,212 @@
+//! 基于 Chromium 的无头 WebView 实现
+//!
+//! 需启用 `headless` feature。通过 `chromiumoxide` 驱动本机 Chrome/Chromium，
+//! 适合无 GUI 的服务器和测试环境运行验证流程。
+
+use super::{WebViewCloseReason, WebViewProvider, WebViewRequest, WebViewResponse};
+use crate::{Result, error::RuntimeError};
+use async_trait::async_trait;
+use chromiumoxide::{
+    Browser,
+    BrowserConfig,
+    Page,
+    cdp::browser_protocol::network::{CookieParam, Headers, SetExtraHttpHeadersParams},
+};
+use futures::StreamExt;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+/// 无头 WebView 提供者
+///
+/// 每次 `open` 启动独立的浏览器实例，结束后关闭，互不共享 Cookie
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessWebViewProvider {
+    /// 浏览器可执行文件路径（为空时自动查找）
+    executable: Option<PathBuf>,
+    /// 是否显示浏览器窗口（调试用）
+    with_head: bool,
+}
+
+impl HeadlessWebViewProvider {
+    /// 创建无头提供者
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 指定浏览器可执行文件路径
+    pub fn with_executable(mut self, path: impl Into<PathBuf>) -> Self {
+        self.executable = Some(path.into());
+        self
+    }
+
+    /// 显示浏览器窗口，便于调试脚本
+    pub fn with_head(mut self) -> Self {
+        self.with_head = true;
+        self
+    }
+
+    /// 按请求配置构建浏览器启动参数
+    fn browser_config(&self, request: &WebViewRequest) -> Result<BrowserConfig> {
+        let mut builder = BrowserConfig::builder();
+        if let Some(path) = &self.executable {
+            builder = builder.chrome_executable(path);
+        }
+        if self.with_head {
+            builder = builder.with_head();
+        }
+        if let Some((width, height)) = request.window_size {
+            builder = builder.window_size(width, height);
+        }
+        if let Some(user_agent) = &request.user_agent {
+            builder = builder.arg(format!("--user-agent={}", user_agent));
+        }
+        builder.build().map_err(RuntimeError::WebViewError)
+    }
+
+    /// 加载页面并执行检测流程，返回 `None` 表示超时
+    async fn run(page: &Page, request: &WebViewRequest) -> Result<Option<WebViewResponse>> {
+        if !request.initial_headers.is_empty() {
+            let headers = serde_json::to_value(&request.initial_headers)
+                .map_err(|e| RuntimeError::WebViewError(e.to_string()))?;
+            page.execute(SetExtraHttpHeadersParams::new(Headers::new(headers)))
+                .await
+                .map_err(webview_error)?;
+        }
+
+        if !request.initial_cookies.is_empty() {
+            let mut cookies = Vec::with_capacity(request.initial_cookies.len());
+            for (name, value) in &request.initial_cookies {
+                let cookie = CookieParam::builder()
+                    .name(name)
+                    .value(value)
+                    .url(&request.url)
+                    .build()
+                    .map_err(RuntimeError::WebViewError)?;
+                cookies.push(cookie);
+            }
+            page.set_cookies(cookies).await.map_err(webview_error)?;
+        }
+
+        page.goto(&request.url).await.map_err(webview_error)?;
+
+        if let Some(script) = &request.inject_script {
+            page.evaluate(script.as_str())
+                .await
+                .map_err(webview_error)?;
+        }
+
+        // 周期性执行成功检测脚本，未配置时页面加载完成即视为成功
+        if let Some(check) = &request.success_check {
+            let deadline = tokio::time::Instant::now() + request.timeout;
+            loop {
+                let passed = page
+                    .evaluate(check.as_str())
+                    .await
+                    .ok()
+                    .and_then(|result| result.into_value::<bool>().ok())
+                    .unwrap_or(false);
+                if passed {
+                    break;
+                }
+                if tokio::time::Instant::now() + request.check_interval > deadline {
+                    return Ok(None);
+                }
+                tokio::time::sleep(request.check_interval).await;
+            }
+        }
+
+        let mut response = WebViewResponse::success();
+
+        if let Some(script) = &request.finish_script {
+            let result = page
+                .evaluate(script.as_str())
+                .await
+                .map_err(webview_error)?
+                .into_value::<serde_json::Value>()
+                .unwrap_or(serde_json::Value::Null);
+            response = response.with_script_result(match result {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            });
+        }
+
+        let cookies: HashMap<String, String> = page
+            .get_cookies()
+            .await
+            .map_err(webview_error)?
+            .into_iter()
+            .filter(|cookie| {
+                request
+                    .extract_cookies
+                    .as_ref()
+                    .is_none_or(|names| names.contains(&cookie.name))
+            })
+            .map(|cookie| (cookie.name, cookie.value))
+            .collect();
+        response = response.with_cookies(cookies);
+
+        if let Some(url) = page.url().await.map_err(webview_error)? {
+            response = response.with_final_url(url);
+        }
+        response.html = page.content().await.ok();
+
+        Ok(Some(response))
+    }
+}
+
+#[async_trait]
+impl WebViewProvider for HeadlessWebViewProvider {
+    async fn open(&self, request: WebViewRequest) -> Result<WebViewResponse> {
+        let config = self.browser_config(&request)?;
+        let (mut browser, mut handler) = Browser::launch(config)
+            .await
+            .map_err(|e| RuntimeError::WebViewUnavailable(e.to_string()))?;
+
+        // 驱动 CDP 事件循环
+        let events = tokio::spawn(async move {
+            while let Some(event) = handler.next().await {
+                if event.is_err() {
+                    break;
+                }
+            }
+        });
+
+        // 页面创建与加载同样计入超时
+        let result = match tokio::time::timeout(request.timeout, async {
+            let page = browser
+                .new_page("about:blank")
+                .await
+                .map_err(webview_error)?;
+            Self::run(&page, &request).await
+        })
+        .await
+        {
+            Ok(Ok(Some(response))) => Ok(response),
+            Ok(Ok(None)) | Err(_) => Ok(WebViewResponse::failure(
+                WebViewCloseReason::Timeout,
+                Some(format!("等待验证完成超时 ({:?})", request.timeout)),
+            )),
+            Ok(Err(e)) => Ok(WebViewResponse::failure(
+                WebViewCloseReason::Error,
+                Some(e.to_string()),
+            )),
+        };
+
+        let _ = browser.close().await;
+        let _ = tokio::time::timeout(Duration::from_secs(5), browser.wait()).await;
+        events.abort();
+
+        result
+    }
+
+    fn supports_headless(&self) -> bool {
+        !self.with_head
+    }
+
+    fn name(&self) -> &str {
+        "HeadlessWebViewProvider"
+    }
+}
+
+fn webview_error(e: chromiumoxide::error::CdpError) -> RuntimeError {
+    RuntimeError::WebViewError(e.to_string())
+}
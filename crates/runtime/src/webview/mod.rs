@@ -7,6 +7,11 @@
 //! Runtime 不直接依赖任何 WebView 库（如 wry、tauri），
 //! 而是通过 trait 抽象，让调用方注入具体实现。
 //!
+//! 内置 [`NoopWebViewProvider`]（无浏览器环境，始终失败）与
+//! [`MockWebViewProvider`]（返回预设 Cookie，用于测试）。
+//! 启用 `headless` feature 后可使用内置的 [`HeadlessWebViewProvider`]，
+//! 基于本机 Chrome/Chromium 在无 GUI 环境下运行验证流程。
+//!
 //! ## 使用示例
 //!
 //! ```rust,ignore
@@ -26,10 +31,16 @@
 //!     .build()?;
 //! ```
 
+#[cfg(feature = "headless")]
+mod headless;
 mod provider;
+mod render;
 mod request;
 mod response;
 
+#[cfg(feature = "headless")]
+pub use headless::HeadlessWebViewProvider;
 pub use provider::*;
+pub use render::{RenderedPage, render_page};
 pub use request::*;
 pub use response::*;
@@ -1,9 +1,12 @@
 //! WebView 提供者 trait
 
-use super::{WebViewRequest, WebViewResponse};
+use super::{WebViewCloseReason, WebViewRequest, WebViewResponse};
 use crate::Result;
 use async_trait::async_trait;
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 /// WebView 提供者 trait
 ///
@@ -65,25 +68,105 @@ pub trait WebViewProvider: Send + Sync + std::fmt::Debug {
     fn name(&self) -> &str {
         "WebViewProvider"
     }
+
+    /// 是否能真正打开 WebView
+    ///
+    /// 占位实现返回 false，调用方可据此跳过依赖 WebView 的分支
+    fn is_available(&self) -> bool {
+        true
+    }
 }
 
-/// 空实现（用于不需要 WebView 的场景）
+/// 空实现（用于 CI、服务器等没有浏览器的场景）
 ///
-/// 当规则不包含 WebView 相关配置时可以使用
+/// 不打开任何窗口，立即返回 `Error` 关闭原因的失败响应
 #[derive(Debug)]
 pub struct NoopWebViewProvider;
 
 #[async_trait]
 impl WebViewProvider for NoopWebViewProvider {
-    async fn open(&self, _request: WebViewRequest) -> Result<WebViewResponse> {
-        Err(crate::error::RuntimeError::WebViewUnavailable(
-            "WebView 提供者未配置".to_string(),
+    async fn open(&self, request: WebViewRequest) -> Result<WebViewResponse> {
+        Ok(WebViewResponse::failure(
+            WebViewCloseReason::Error,
+            Some(format!(
+                "WebView 提供者未配置，无法打开 {}（请注入 WebViewProvider 实现）",
+                request.url
+            )),
         ))
     }
 
     fn name(&self) -> &str {
         "NoopWebViewProvider"
     }
+
+    fn is_available(&self) -> bool {
+        false
+    }
+}
+
+/// 模拟实现（用于测试）
+///
+/// 不打开任何窗口，立即返回预设的结果，并记录收到的请求，
+/// 便于下游在没有真实 WebView 的情况下确定性地测试验证处理分支
+#[derive(Debug, Default)]
+pub struct MockWebViewProvider {
+    /// 预设的响应
+    response: WebViewResponse,
+    /// 已收到的请求
+    requests: Mutex<Vec<WebViewRequest>>,
+}
+
+impl MockWebViewProvider {
+    /// 创建返回指定 Cookie 的成功模拟
+    pub fn new(cookies: HashMap<String, String>) -> Self {
+        Self::with_response(WebViewResponse::success().with_cookies(cookies))
+    }
+
+    /// 创建返回指定响应的模拟
+    pub fn with_response(response: WebViewResponse) -> Self {
+        Self {
+            response,
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 创建以指定原因失败的模拟
+    pub fn failing(reason: WebViewCloseReason) -> Self {
+        Self::with_response(WebViewResponse::failure(
+            reason,
+            Some("模拟 WebView 失败".to_string()),
+        ))
+    }
+
+    /// 已收到的请求
+    pub fn requests(&self) -> Vec<WebViewRequest> {
+        self.requests
+            .lock()
+            .map(|requests| requests.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl WebViewProvider for MockWebViewProvider {
+    async fn open(&self, request: WebViewRequest) -> Result<WebViewResponse> {
+        let mut response = self.response.clone();
+        if response.success && response.final_url.is_none() {
+            response.final_url = Some(request.url.clone());
+        }
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(request);
+        }
+        Ok(response)
+    }
+
+    fn supports_headless(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "MockWebViewProvider"
+    }
 }
 
 /// WebView 提供者的共享引用类型
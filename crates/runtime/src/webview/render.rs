@@ -0,0 +1,120 @@
+//! 页面渲染
+//!
+//! 流程配置了 `http.render` 时，由 WebView 加载页面并返回渲染后的 HTML，
+//! 代替直接的 HTTP 请求。请求携带共享 HTTP 客户端中的 Cookie，
+//! WebView 返回的 Cookie 写回客户端，供之后的 HTTP 请求使用。
+
+use super::{WebViewCloseReason, WebViewRequest};
+use crate::{Result, context::RuntimeContext, error::RuntimeError};
+use crawler_schema::config::RenderConfig;
+use reqwest::{
+    Url,
+    header::{HeaderMap, HeaderName, HeaderValue},
+};
+use std::time::Duration;
+
+/// 默认渲染超时（秒）
+const DEFAULT_RENDER_TIMEOUT: u32 = 30;
+
+/// 渲染后的页面
+#[derive(Debug, Clone)]
+pub struct RenderedPage {
+    /// 最终 URL（重定向后）
+    pub url: String,
+    /// 渲染后的页面 HTML
+    pub html: String,
+    /// 主文档的 HTTP 状态码（WebView 提供者无法获取时为 `None`）
+    pub status: Option<u16>,
+    /// 主文档的响应头
+    pub headers: HeaderMap,
+}
+
+/// 由 WebView 加载并渲染页面
+///
+/// 未注入可用的 WebView 提供者时立即返回 `WebViewUnavailable`，不会等待超时
+pub async fn render_page(
+    config: &RenderConfig,
+    url: &str,
+    user_agent: Option<&str>,
+    runtime_context: &RuntimeContext,
+) -> Result<RenderedPage> {
+    let provider = runtime_context.webview_provider();
+    if !provider.is_available() {
+        return Err(RuntimeError::WebViewUnavailable(format!(
+            "规则要求由 WebView 渲染页面，但 {} 不可用",
+            provider.name()
+        )));
+    }
+
+    let mut request = WebViewRequest::new(url).with_timeout(Duration::from_secs(
+        config.timeout_seconds.unwrap_or(DEFAULT_RENDER_TIMEOUT) as u64,
+    ));
+    if let Some(ua) = user_agent {
+        request = request.with_user_agent(ua);
+    }
+    let wait_script = match (&config.wait_script, &config.wait_for) {
+        (Some(script), _) => Some(script.clone()),
+        (None, Some(selector)) => Some(format!(
+            "document.querySelector({}) !== null",
+            serde_json::Value::String(selector.clone())
+        )),
+        (None, None) => None,
+    };
+    if let Some(script) = wait_script {
+        request = request.with_success_check(script);
+    }
+
+    let http_client = runtime_context.http_client();
+    let page_url = Url::parse(url).ok();
+    for (name, value) in page_url
+        .as_ref()
+        .and_then(|u| http_client.cookie_header(u))
+        .iter()
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+    {
+        request = request.with_cookie(name, value);
+    }
+
+    let response = provider.open(request).await?;
+    if !response.success {
+        return match response.close_reason {
+            WebViewCloseReason::Timeout => Err(RuntimeError::WebViewTimeout),
+            WebViewCloseReason::UserClosed => Err(RuntimeError::WebViewUserClosed),
+            _ => Err(RuntimeError::WebViewError(
+                response.error.unwrap_or_else(|| "页面渲染失败".to_string()),
+            )),
+        };
+    }
+
+    let final_url = response.final_url.unwrap_or_else(|| url.to_string());
+    if let Ok(cookie_url) = Url::parse(&final_url) {
+        let cookies = response
+            .cookies
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        http_client.set_cookies(&cookie_url, &cookies);
+    }
+
+    let headers = response
+        .headers
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((
+                HeaderName::from_bytes(name.as_bytes()).ok()?,
+                HeaderValue::from_str(value).ok()?,
+            ))
+        })
+        .collect();
+    let html = response
+        .html
+        .ok_or_else(|| RuntimeError::WebViewError("WebView 未返回页面 HTML".to_string()))?;
+    Ok(RenderedPage {
+        url: final_url,
+        html,
+        status: response.status,
+        headers,
+    })
+}
@@ -13,6 +13,9 @@ pub struct WebViewResponse {
     /// 最终 URL（可能经过重定向）
     pub final_url: Option<String>,
 
+    /// 主文档的 HTTP 状态码（提供者无法获取时为 `None`）
+    pub status: Option<u16>,
+
     /// 提取的 Cookie
     pub cookies: HashMap<String, String>,
 
@@ -84,6 +87,18 @@ impl WebViewResponse {
         self
     }
 
+    /// 设置主文档的 HTTP 状态码
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// 设置页面 HTML
+    pub fn with_html(mut self, html: impl Into<String>) -> Self {
+        self.html = Some(html.into());
+        self
+    }
+
     /// 设置脚本结果
     pub fn with_script_result(mut self, result: impl Into<String>) -> Self {
         self.script_result = Some(result.into());
@@ -0,0 +1,90 @@
+//! 人机验证：请求路径上的自动检测与处理
+
+mod common;
+
+use common::{TestServer, http_response};
+use crawler_runtime::{RuntimeError, crawler::CrawlerRuntime};
+
+/// Cloudflare "Just a moment" 验证页面（节选）
+const CLOUDFLARE_PAGE: &str = r#"<!DOCTYPE html><html lang="en-US"><head><title>Just a moment...</title>
+<meta http-equiv="refresh" content="390"></head><body class="no-js">
+<div class="main-wrapper" role="main"><div class="main-content">
+<h1 class="zone-name-title h1">example.com</h1>
+<h2 class="h2" id="challenge-running">Checking if the site connection is secure</h2>
+<noscript><div id="challenge-error-title">Enable JavaScript and cookies to continue</div></noscript>
+</div></div><script>(function(){window._cf_chl_opt={cvId: '2',cZone: 'example.com',cType: 'managed'};}());</script>
+</body></html>"#;
+
+const SEARCH_PAGE: &str = r#"<div class="item"><a class="title" href="/book/1">第一本</a></div>"#;
+
+const CHALLENGE: &str = r#"
+[challenge]
+detectors = [{ type = "cloudflare" }]
+max_attempts = 1
+
+[challenge.handler]
+type = "cookie"
+config = { cookies = "cf_clearance=ok" }
+"#;
+
+/// 未携带 `cf_clearance` Cookie 的请求返回验证页面
+fn cloudflare_server(accept: &'static str) -> impl Fn(&str) -> Vec<u8> + Send + Sync {
+    move |request: &str| {
+        if request.contains(accept) {
+            http_response(200, &[], SEARCH_PAGE)
+        } else {
+            http_response(503, &[("cf-ray", "8a1b2c3d4e5f-LAX")], CLOUDFLARE_PAGE)
+        }
+    }
+}
+
+fn runtime(server: &TestServer) -> CrawlerRuntime {
+    let rule = common::rule(&format!(
+        "[search]\nurl = \"{}\"\n{}",
+        server.url("/search?q={{ keyword }}"),
+        CHALLENGE
+    ));
+    CrawlerRuntime::new(rule, None).unwrap()
+}
+
+#[tokio::test]
+async fn challenge_page_is_handled_and_request_retried() {
+    let server = TestServer::start(cloudflare_server("cf_clearance=ok")).await;
+    let runtime = runtime(&server);
+
+    let response = runtime.search("书", 1).await.unwrap();
+    assert_eq!(response.items[0].title, "第一本");
+    assert_eq!(server.requests().len(), 2);
+
+    // 凭证已写入共享客户端，之后的请求不再触发验证
+    runtime.search("书", 1).await.unwrap();
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn challenge_that_persists_after_handling_is_an_error() {
+    let server = TestServer::start(cloudflare_server("never-accepted")).await;
+    let runtime = runtime(&server);
+
+    let error = runtime.search("书", 1).await.unwrap_err();
+    assert!(
+        matches!(error, RuntimeError::ChallengeFailed(_)),
+        "{error:?}"
+    );
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn responses_are_not_checked_without_challenge_config() {
+    let server = TestServer::start(cloudflare_server("cf_clearance=ok")).await;
+    let rule = common::rule(&format!(
+        "[search]\nurl = \"{}\"",
+        server.url("/search?q={{ keyword }}")
+    ));
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    // 验证页面按普通页面提取，找不到列表
+    let error = runtime.search("书", 1).await.unwrap_err();
+    assert!(matches!(error, RuntimeError::EmptyValue), "{error:?}");
+    assert_eq!(server.requests().len(), 1);
+}
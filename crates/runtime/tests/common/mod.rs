@@ -0,0 +1,208 @@
+//! 集成测试共用工具
+
+#![allow(dead_code)]
+
+use crawler_runtime::{
+    context::{flow::FlowContext, runtime::RuntimeContext},
+    extractor::{
+        ExtractEngine,
+        value::{ExtractValueData, SharedValue},
+    },
+};
+use crawler_schema::{core::CrawlerRule, extract::FieldExtractor};
+use std::sync::{Arc, Mutex};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use toml::Value;
+
+/// 最小可加载的规则
+const BASE_RULE: &str = r#"
+[meta]
+name = "测试规则"
+author = "test"
+version = "1.0.0"
+spec_version = "1.0.0"
+domain = "example.com"
+media_type = "book"
+
+[search]
+url = "https://example.com/search?q={{ keyword }}"
+
+[search.list]
+steps = [{ css = { expr = ".item", all = true } }]
+
+[search.fields.title]
+steps = [{ css = ".title" }, { attr = "text" }]
+
+[search.fields.url]
+steps = [{ css = "a" }, { attr = "href" }]
+
+[detail]
+url = "{{ detail_url }}"
+
+[detail.fields]
+media_type = "book"
+
+[detail.fields.title]
+steps = [{ css = "h1" }, { attr = "text" }]
+
+[detail.fields.author]
+steps = [{ css = ".author" }, { attr = "text" }]
+
+[detail.fields.chapters]
+list.steps = [{ css = { expr = "li a", all = true } }]
+title.steps = [{ attr = "text" }]
+url.steps = [{ attr = "href" }]
+"#;
+
+/// 在最小规则上合并 `overrides`（TOML 表逐层合并）后解析
+pub fn rule(overrides: &str) -> CrawlerRule {
+    let mut base: Value = toml::from_str(BASE_RULE).expect("base rule");
+    let overrides: Value = toml::from_str(overrides).expect("rule overrides");
+    merge(&mut base, overrides);
+    base.try_into().expect("rule")
+}
+
+fn merge(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Table(base), Value::Table(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, value) => *base = value,
+    }
+}
+
+/// 基于规则创建运行时上下文
+pub fn runtime_context(rule: CrawlerRule) -> Arc<RuntimeContext> {
+    Arc::new(RuntimeContext::new(rule).expect("runtime context"))
+}
+
+/// 基于最小规则创建空的流程上下文
+pub fn flow_context() -> FlowContext {
+    FlowContext::new(runtime_context(rule("")))
+}
+
+/// 用 JSON 描述的提取器在 HTML 上提取字段
+pub fn extract_html(
+    extractor: serde_json::Value,
+    html: &str,
+) -> crawler_runtime::Result<SharedValue> {
+    let extractor: FieldExtractor = serde_json::from_value(extractor).expect("extractor");
+    let flow_context = flow_context();
+    ExtractEngine::extract_field(
+        &extractor,
+        &ExtractValueData::Html(html.into()),
+        flow_context.runtime(),
+        &flow_context,
+    )
+}
+
+/// 用 JSON 描述的提取器在 JSON 输入上提取字段
+pub fn extract_json(
+    extractor: serde_json::Value,
+    input: serde_json::Value,
+) -> crawler_runtime::Result<SharedValue> {
+    let extractor: FieldExtractor = serde_json::from_value(extractor).expect("extractor");
+    let flow_context = flow_context();
+    ExtractEngine::extract_field(
+        &extractor,
+        &ExtractValueData::from(input),
+        flow_context.runtime(),
+        &flow_context,
+    )
+}
+
+/// 本地 HTTP 测试服务器
+///
+/// 每个连接处理一个请求后关闭；`handler` 接收请求报文（请求行、请求头与正文），
+/// 返回完整的响应报文
+pub struct TestServer {
+    base_url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestServer {
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&str) -> Vec<u8> + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("addr"));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(handler);
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let handler = handler.clone();
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let request = read_request(&mut stream).await;
+                    recorded.lock().unwrap().push(request.clone());
+                    let _ = stream.write_all(&handler(&request)).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+        Self { base_url, requests }
+    }
+
+    /// 服务器上指定路径的完整 URL
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// 已收到的请求报文
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> String {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    while let Ok(n) = stream.read(&mut chunk).await {
+        if n == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+        let text = String::from_utf8_lossy(&buffer);
+        if let Some(head_end) = text.find("\r\n\r\n") {
+            let content_length = text[..head_end]
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            if buffer.len() >= head_end + 4 + content_length {
+                break;
+            }
+        }
+    }
+    String::from_utf8_lossy(&buffer).into_owned()
+}
+
+/// 构造响应报文
+pub fn http_response(status: u16, headers: &[(&str, &str)], body: impl AsRef<[u8]>) -> Vec<u8> {
+    let body = body.as_ref();
+    let mut response = format!(
+        "HTTP/1.1 {} Test\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str("\r\n");
+    let mut response = response.into_bytes();
+    response.extend_from_slice(body);
+    response
+}
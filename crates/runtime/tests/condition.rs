@@ -0,0 +1,27 @@
+//! 条件步骤
+
+mod common;
+
+use serde_json::json;
+
+#[test]
+fn matches_compares_against_cached_regex() {
+    let extractor = json!({
+        "steps": [
+            { "attr": "text" },
+            {
+                "condition": {
+                    "when": [],
+                    "op": "matches",
+                    "value": "^第\\d+章",
+                    "then": [{ "filter": "prepend(章节:)" }],
+                    "otherwise": [{ "filter": "prepend(其他:)" }],
+                }
+            }
+        ]
+    });
+    let chapter = common::extract_html(extractor.clone(), "<p>第12章 开端</p>").unwrap();
+    assert_eq!(chapter.as_str(), Some("章节:第12章 开端"));
+    let other = common::extract_html(extractor, "<p>序言</p>").unwrap();
+    assert_eq!(other.as_str(), Some("其他:序言"));
+}
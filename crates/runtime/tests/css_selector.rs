@@ -0,0 +1,85 @@
+//! CSS 选择器：伪元素提取与作用域
+
+mod common;
+
+use crawler_runtime::context::RuntimeContext;
+use serde_json::json;
+
+const PAGE: &str = r#"<div class="book"><a class="link" href="/book/1">斗破<b>苍穹</b></a></div>"#;
+
+fn extract(selector: &str) -> Option<String> {
+    common::extract_html(json!({ "steps": [{ "css": selector }] }), PAGE)
+        .unwrap()
+        .as_str()
+        .map(str::to_string)
+}
+
+#[test]
+fn attr_pseudo_element_extracts_attribute() {
+    assert_eq!(extract("a.link::attr(href)").as_deref(), Some("/book/1"));
+}
+
+#[test]
+fn text_pseudo_elements_extract_text() {
+    assert_eq!(extract(".book a::text").as_deref(), Some("斗破苍穹"));
+    assert_eq!(extract(".book a::own_text").as_deref(), Some("斗破"));
+    assert_eq!(extract(".book a::html").as_deref(), Some("斗破<b>苍穹</b>"));
+}
+
+#[test]
+fn separate_attr_step_still_works() {
+    let value = common::extract_html(
+        json!({ "steps": [{ "css": "a.link" }, { "attr": "href" }] }),
+        PAGE,
+    );
+    assert_eq!(value.unwrap().as_str(), Some("/book/1"));
+}
+
+#[test]
+fn malformed_pseudo_element_is_rejected_at_load() {
+    for selector in ["a::attr(", "a::attr()", "::text"] {
+        let rule = common::rule(&format!(
+            "[search.fields.title]\nsteps = [{{ css = \"{selector}\" }}]"
+        ));
+        assert!(RuntimeContext::new(rule).is_err(), "{selector}");
+    }
+}
+
+const LIST: &str = r#"<div class="item"><span class="title">第一本</span></div>
+<div class="item"><span class="author">无题作者</span></div>
+<div class="item"><span class="title">第三本</span><span class="title">副标题</span></div>"#;
+
+#[test]
+fn selectors_inside_map_are_scoped_to_each_element() {
+    let value = common::extract_html(
+        json!({ "steps": [
+            { "css": { "expr": ".item", "all": true } },
+            { "map": { "steps": [{ "css": ".title" }, { "attr": "text" }], "on_error": "null" } },
+        ] }),
+        LIST,
+    )
+    .unwrap();
+
+    // 第二项没有标题，不能取到相邻项的标题；第三项只取自身的第一个标题
+    assert_eq!(value.to_owned_json(), json!(["第一本", null, "第三本"]));
+}
+
+#[test]
+fn merge_inside_map_is_scoped_to_each_element() {
+    let value = common::extract_html(
+        json!({ "steps": [
+            { "css": { "expr": ".item", "all": true } },
+            { "map": [{ "merge": {
+                "title": [{ "css": ".title" }, { "attr": "text" }],
+                "author": [{ "css": ".author" }, { "attr": "text" }],
+            } }] },
+        ] }),
+        LIST,
+    )
+    .unwrap();
+
+    let records = value.to_owned_json();
+    assert_eq!(records[0]["author"], serde_json::Value::Null);
+    assert_eq!(records[1]["title"], serde_json::Value::Null);
+    assert_eq!(records[1]["author"], "无题作者");
+}
@@ -0,0 +1,80 @@
+//! 提取值的合并与结构摘要
+
+use crawler_runtime::extractor::value::ExtractValueData;
+use serde_json::json;
+
+fn json(value: serde_json::Value) -> ExtractValueData {
+    ExtractValueData::Json(value.into())
+}
+
+#[test]
+fn object_merge_takes_key_union_and_other_wins() {
+    let main = json(json!({ "title": "旧标题", "author": "佚名" }));
+    let ajax = json(json!({ "title": "新标题", "score": 9.1 }));
+
+    let merged = main.merge(&ajax).unwrap();
+    assert_eq!(
+        merged.to_owned_json(),
+        json!({ "title": "新标题", "author": "佚名", "score": 9.1 })
+    );
+}
+
+#[test]
+fn array_merge_concatenates_in_order() {
+    let first = ExtractValueData::from_json(&json!(["第一章", "第二章"]));
+    let second = ExtractValueData::from_json(&json!(["第三章"]));
+
+    let merged = first.merge(&second).unwrap();
+    assert_eq!(
+        merged.to_owned_json(),
+        json!(["第一章", "第二章", "第三章"])
+    );
+}
+
+#[test]
+fn null_merge_returns_other_side() {
+    let value = json(json!({ "title": "书名" }));
+    let merged = ExtractValueData::Null.merge(&value).unwrap();
+    assert_eq!(merged.to_owned_json(), json!({ "title": "书名" }));
+}
+
+#[test]
+fn incompatible_kinds_are_an_error() {
+    let object = json(json!({ "title": "书名" }));
+    let array = ExtractValueData::from_json(&json!(["a"]));
+    assert!(object.merge(&array).is_err());
+    assert!(ExtractValueData::from("text").merge(&object).is_err());
+}
+
+#[test]
+fn shape_of_string_is_its_length() {
+    assert_eq!(ExtractValueData::from("abc").describe_shape(), "String(3)");
+    assert_eq!(ExtractValueData::Null.describe_shape(), "Null");
+}
+
+#[test]
+fn shape_of_array_describes_first_element() {
+    let items = ExtractValueData::from_json(&json!([
+        { "title": "第一本", "url": "/book/1" },
+        { "title": "第二本", "url": "/book/2" },
+    ]));
+    assert_eq!(items.describe_shape(), "Array[2] of Object{title,url}");
+    assert_eq!(
+        ExtractValueData::from_json(&json!([])).describe_shape(),
+        "Array[0]"
+    );
+}
+
+#[test]
+fn shape_truncates_deep_nesting_and_many_keys() {
+    let nested = json(json!([[[["深"]]]]));
+    assert_eq!(
+        nested.describe_shape(),
+        "Array[1] of Array[1] of Array[1] of …"
+    );
+
+    let wide = json(json!({
+        "a": 1, "b": 2, "c": 3, "d": 4, "e": 5, "f": 6, "g": 7, "h": 8, "i": 9, "j": 10
+    }));
+    assert_eq!(wide.describe_shape(), "Object{a,b,c,d,e,f,g,h,…+2}");
+}
@@ -0,0 +1,80 @@
+//! 字段提取结果的 `validate` 正则校验
+
+mod common;
+
+use crawler_runtime::{RuntimeError, extractor::validate::SelectorValidator};
+use serde_json::json;
+
+const PLAYER: &str = r#"<video src="https://cdn.example.com/1.mp4"></video>
+<a class="ad" href="javascript:void(0)">广告</a>
+<a class="backup" href="https://cdn.example.com/2.mp4">备用</a>"#;
+
+#[test]
+fn matching_value_passes_validation() {
+    let value = common::extract_html(
+        json!({ "steps": [{ "css": "video" }, { "attr": "src" }], "validate": "^https?://" }),
+        PLAYER,
+    )
+    .unwrap();
+    assert_eq!(value.as_str(), Some("https://cdn.example.com/1.mp4"));
+}
+
+#[test]
+fn mismatch_without_fallback_is_an_error() {
+    let error = common::extract_html(
+        json!({ "steps": [{ "css": ".ad" }, { "attr": "href" }], "validate": "^https?://" }),
+        PLAYER,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("^https?://"), "{error}");
+}
+
+#[test]
+fn mismatch_falls_back_then_to_default() {
+    let value = common::extract_html(
+        json!({
+            "steps": [{ "css": ".ad" }, { "attr": "href" }],
+            "fallback": [[{ "css": ".backup" }, { "attr": "href" }]],
+            "validate": "^https?://",
+        }),
+        PLAYER,
+    )
+    .unwrap();
+    assert_eq!(value.as_str(), Some("https://cdn.example.com/2.mp4"));
+
+    let value = common::extract_html(
+        json!({
+            "steps": [{ "css": ".ad" }, { "attr": "href" }],
+            "default": "https://cdn.example.com/none.mp4",
+            "validate": "^https?://",
+        }),
+        PLAYER,
+    )
+    .unwrap();
+    assert_eq!(value.as_str(), Some("https://cdn.example.com/none.mp4"));
+}
+
+#[test]
+fn every_array_element_must_match() {
+    let error = common::extract_html(
+        json!({
+            "steps": [{ "css": { "expr": "a", "all": true } }, { "attr": "href" }],
+            "validate": "^https?://",
+        }),
+        PLAYER,
+    )
+    .unwrap_err();
+    assert!(matches!(error, RuntimeError::Extraction(_)), "{error:?}");
+}
+
+#[test]
+fn invalid_pattern_is_reported_at_load() {
+    let rule = common::rule("[search.fields.url]\nvalidate = \"^(https\"");
+
+    let report = SelectorValidator::check_rule(&rule);
+    let error = report.errors().next().expect("validate error");
+    assert!(
+        matches!(error, RuntimeError::InvalidConfigValue { field, .. } if field == "search.fields.url.validate"),
+        "{error:?}"
+    );
+}
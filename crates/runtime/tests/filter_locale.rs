@@ -0,0 +1,40 @@
+//! 本地化过滤器
+
+use crawler_runtime::extractor::filter::locale::convert_cn_numerals;
+
+#[test]
+fn converts_numerals_in_numeric_context() {
+    let cases = [
+        ("第三十二章 风起", "第32章 风起"),
+        ("第十章", "第10章"),
+        ("卷 二 一百零五", "卷 2 105"),
+        ("二〇二四年", "2024年"),
+        ("三十二集", "32集"),
+        ("十万", "100000"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(convert_cn_numerals(input, false), expected, "{input}");
+    }
+}
+
+#[test]
+fn keeps_ordinary_words() {
+    for input in [
+        "一个人",
+        "万一出错",
+        "十全十美",
+        "千万小心",
+        "统一战线",
+        "三国演义",
+    ] {
+        assert_eq!(convert_cn_numerals(input, false), input);
+    }
+}
+
+#[test]
+fn only_first_converts_first_numeral() {
+    assert_eq!(
+        convert_cn_numerals("第三卷 第十二章", true),
+        "第3卷 第十二章"
+    );
+}
@@ -0,0 +1,57 @@
+//! URL 过滤器
+
+mod common;
+
+use crawler_runtime::extractor::filter::url::resolve_url;
+use serde_json::json;
+
+const BASE: &str = "https://a.com/x/y.html";
+
+#[test]
+fn resolves_relative_forms_against_base() {
+    let cases = [
+        ("../z.html", "https://a.com/z.html"),
+        ("z.html", "https://a.com/x/z.html"),
+        ("/z.html", "https://a.com/z.html"),
+        ("?p=1", "https://a.com/x/y.html?p=1"),
+        ("#top", "https://a.com/x/y.html#top"),
+        ("//img.a.com/c.jpg", "https://img.a.com/c.jpg"),
+        ("http://b.com/d.html", "http://b.com/d.html"),
+    ];
+    for (url, expected) in cases {
+        assert_eq!(resolve_url(BASE, url), expected, "{url}");
+    }
+}
+
+#[test]
+fn unparsable_base_falls_back_to_concatenation() {
+    assert_eq!(resolve_url("a.com/x", "y.html"), "a.com/x/y.html");
+    assert_eq!(resolve_url("a.com/x/", "?p=1"), "a.com/x?p=1");
+    assert_eq!(resolve_url("a.com", "https://b.com/y"), "https://b.com/y");
+    assert_eq!(
+        resolve_url("a.com/x", "//cdn.a.com/c.jpg"),
+        "https://cdn.a.com/c.jpg"
+    );
+}
+
+#[test]
+fn absolute_url_filter_uses_explicit_base() {
+    let extract = |href: &str| {
+        common::extract_html(
+            json!({ "steps": [
+                { "css": "a" },
+                { "attr": "href" },
+                { "filter": [{ "name": "absolute_url", "args": [BASE] }] },
+            ] }),
+            &format!(r#"<a href="{href}">链接</a>"#),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(extract("../z.html").as_str(), Some("https://a.com/z.html"));
+    assert_eq!(extract("?p=1").as_str(), Some("https://a.com/x/y.html?p=1"));
+    assert_eq!(
+        extract("//img.a.com/c.jpg").as_str(),
+        Some("https://img.a.com/c.jpg")
+    );
+}
@@ -0,0 +1,41 @@
+//! 登录流程
+
+mod common;
+
+use crawler_runtime::{RuntimeError, crawler::CrawlerRuntime, flow::login::LoginRequest};
+use reqwest::Url;
+
+const LOGIN: &str = r#"
+[login]
+type = "credential"
+fields = [{ key = "token", label = "API Token", field_type = "password" }]
+storage = [{ type = "header", header_name = "Authorization", header_template = "Bearer {{ token }}" }]
+check_login = { code = "input.includes('session=')" }
+"#;
+
+fn runtime() -> CrawlerRuntime {
+    CrawlerRuntime::new(common::rule(LOGIN), None).unwrap()
+}
+
+#[tokio::test]
+async fn logged_in_session_skips_required_fields() {
+    let runtime = runtime();
+    let site = Url::parse("https://example.com").unwrap();
+    runtime
+        .runtime_ctx()
+        .http_client()
+        .set_cookies(&site, "session=ok");
+
+    let response = runtime.login(LoginRequest::default()).await.unwrap();
+    assert!(response.success);
+    assert!(response.already_logged_in);
+}
+
+#[tokio::test]
+async fn missing_field_is_reported_when_not_logged_in() {
+    let error = runtime().login(LoginRequest::default()).await.unwrap_err();
+    assert!(
+        matches!(&error, RuntimeError::MissingConfig { field } if field == "login.token"),
+        "{error:?}"
+    );
+}
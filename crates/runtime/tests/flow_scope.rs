@@ -0,0 +1,61 @@
+//! 流程上下文的子作用域与变量导出
+
+mod common;
+
+use serde_json::json;
+
+#[test]
+fn child_reads_parent_but_writes_locally() {
+    let mut parent = common::flow_context();
+    parent.set("keyword", json!("书"));
+
+    let mut child = parent.child();
+    child.set("page", json!(2));
+
+    assert_eq!(child.get("keyword"), Some(&json!("书")));
+    assert_eq!(parent.get("page"), None);
+}
+
+#[test]
+fn exported_variables_are_merged_into_parent() {
+    let mut parent = common::flow_context();
+    let mut child = parent.child();
+    child.set("token", json!("abc"));
+    child.set("temp", json!(1));
+    child.export("token");
+
+    parent.merge_exports(&child);
+
+    assert_eq!(parent.get("token"), Some(&json!("abc")));
+    assert_eq!(parent.get("temp"), None);
+}
+
+#[test]
+fn exporting_undefined_variable_is_ignored() {
+    let mut parent = common::flow_context();
+    parent.set("token", json!("old"));
+    let mut child = parent.child();
+    child.export("token");
+
+    // 子作用域未重新定义 `token`，父作用域的值不被覆盖
+    parent.merge_exports(&child);
+    assert_eq!(parent.get("token"), Some(&json!("old")));
+}
+
+#[test]
+fn exports_pass_through_nested_scopes_only_when_re_exported() {
+    let mut root = common::flow_context();
+    let mut outer = root.child();
+    let mut inner = outer.child();
+    inner.set("a", json!(1));
+    inner.set("b", json!(2));
+    inner.export("a");
+    inner.export("b");
+    outer.merge_exports(&inner);
+    outer.export("a");
+
+    root.merge_exports(&outer);
+
+    assert_eq!(root.get("a"), Some(&json!(1)));
+    assert_eq!(root.get("b"), None);
+}
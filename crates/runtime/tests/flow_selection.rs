@@ -0,0 +1,57 @@
+//! 选项组参数绑定
+
+mod common;
+
+use crawler_runtime::flow::selection::bind_selections;
+use crawler_schema::flow::FilterGroup;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn group(key: &str) -> FilterGroup {
+    serde_json::from_value(json!({
+        "name": key,
+        "key": key,
+        "options": [{ "name": "默认", "value": "default" }],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn binds_defaults_for_unselected_groups() {
+    let mut flow_context = common::flow_context();
+    bind_selections(
+        "search.sorts",
+        &[group("order")],
+        &HashMap::new(),
+        &mut flow_context,
+    )
+    .unwrap();
+    assert_eq!(flow_context.get("order"), Some(&json!("default")));
+}
+
+#[test]
+fn rejects_reserved_keys() {
+    let mut flow_context = common::flow_context();
+    let result = bind_selections(
+        "search.filters",
+        &[group("keyword")],
+        &HashMap::new(),
+        &mut flow_context,
+    );
+    assert!(result.is_err());
+    assert_eq!(flow_context.get("keyword"), None);
+}
+
+#[test]
+fn rejects_keys_already_bound_by_the_flow() {
+    let mut flow_context = common::flow_context();
+    flow_context.set("offset", json!(20));
+    let result = bind_selections(
+        "search.filters",
+        &[group("offset")],
+        &HashMap::new(),
+        &mut flow_context,
+    );
+    assert!(result.is_err());
+    assert_eq!(flow_context.get("offset"), Some(&json!(20)));
+}
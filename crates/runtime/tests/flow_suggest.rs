@@ -0,0 +1,101 @@
+//! 搜索建议流程
+
+mod common;
+
+use common::{TestServer, http_response};
+use crawler_runtime::{RuntimeError, crawler::CrawlerRuntime, flow::suggest::SuggestFlowExecutor};
+
+/// 建议接口返回的 JSON 样本
+const SUGGEST_JSON: &str = r#"{"code":0,"data":[
+    {"name":"斗破苍穹","url":"/book/1"},
+    {"name":"斗罗大陆","url":"/book/2"},
+    {"name":null,"url":"/book/3"}
+]}"#;
+
+fn suggest_rule(url: &str, extra: &str) -> crawler_schema::core::CrawlerRule {
+    common::rule(&format!(
+        r#"
+[suggest]
+url = "{url}"
+list.steps = [{{ json = "$.data[*]" }}]
+
+[suggest.fields]
+title.steps = [{{ json = "$.name" }}]
+url.steps = [{{ json = "$.url" }}]
+{extra}
+"#
+    ))
+}
+
+#[tokio::test]
+async fn suggestions_are_extracted_from_json_response() {
+    let server = TestServer::start(|_| {
+        http_response(200, &[("Content-Type", "application/json")], SUGGEST_JSON)
+    })
+    .await;
+    // 内容类型在全局 HTTP 配置中声明
+    let rule = suggest_rule(
+        &server.url("/api/suggest?q={{ keyword }}"),
+        "[http.response]\ncontent_type = \"json\"",
+    );
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    let items = runtime.suggest("斗").await.unwrap();
+    let titles: Vec<_> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, ["斗破苍穹", "斗罗大陆"]);
+    assert_eq!(items[0].url, "/book/1");
+    assert!(server.requests()[0].starts_with("GET /api/suggest?q=%E6%96%97"));
+}
+
+#[test]
+fn url_must_reference_keyword_variable() {
+    let rule = suggest_rule("https://example.com/suggest?keyword=1", "");
+    let error = SuggestFlowExecutor::validate(rule.suggest.as_ref().unwrap()).unwrap_err();
+    assert!(
+        matches!(&error, RuntimeError::InvalidConfigValue { field, .. } if field == "suggest.url"),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn keyword_in_request_body_satisfies_validation() {
+    let rule = suggest_rule(
+        "https://example.com/suggest",
+        "[suggest.http.request]\nmethod = \"POST\"\nbody = \"q={{ keyword | urlencode }}\"",
+    );
+    assert!(SuggestFlowExecutor::validate(rule.suggest.as_ref().unwrap()).is_ok());
+}
+
+#[test]
+fn suggest_without_keyword_is_rejected_at_load() {
+    let rule = suggest_rule("https://example.com/suggest?keyword=1", "");
+
+    let Err(error) = CrawlerRuntime::new(rule, None) else {
+        panic!("rule without keyword should be rejected");
+    };
+    assert!(
+        matches!(&error, RuntimeError::InvalidConfigValue { field, .. } if field == "suggest.url"),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn url_without_leading_slash_is_resolved_against_base_url() {
+    let server = TestServer::start(|_| {
+        http_response(200, &[("Content-Type", "application/json")], SUGGEST_JSON)
+    })
+    .await;
+    let mut rule = suggest_rule(
+        "api/suggest?q={{ keyword }}",
+        "[http.response]\ncontent_type = \"json\"",
+    );
+    rule.meta.domain = server.url("");
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    runtime.suggest("斗").await.unwrap();
+    assert!(
+        server.requests()[0].starts_with("GET /api/suggest?q=%E6%96%97"),
+        "{}",
+        server.requests()[0]
+    );
+}
@@ -0,0 +1,42 @@
+//! 流程上下文快照
+
+mod common;
+
+use serde_json::json;
+
+#[test]
+fn nested_sensitive_fields_are_redacted() {
+    let mut flow_context = common::flow_context();
+    flow_context.set(
+        "request",
+        json!({
+            "headers": { "Authorization": "Bearer abc", "Accept": "text/html" },
+            "body": [{ "user": "alice", "password": "hunter2" }],
+        }),
+    );
+    flow_context.set("api_token", json!("abc"));
+
+    let trace = flow_context.export_trace();
+    assert_eq!(
+        trace["flow"]["request"],
+        json!({
+            "headers": { "Authorization": "[REDACTED]", "Accept": "text/html" },
+            "body": [{ "user": "alice", "password": "[REDACTED]" }],
+        })
+    );
+    assert_eq!(trace["flow"]["api_token"], json!("[REDACTED]"));
+}
+
+#[test]
+fn large_values_are_truncated_after_redaction() {
+    let mut flow_context = common::flow_context();
+    flow_context.set(
+        "page",
+        json!({ "secret": "x".repeat(64), "html": "y".repeat(64) }),
+    );
+
+    let trace = flow_context.export_trace_with(&["secret"], 80);
+    let page = trace["flow"]["page"].as_str().unwrap();
+    assert!(page.starts_with("[TRUNCATED"));
+    assert!(!page.contains("xxxx"));
+}
@@ -0,0 +1,113 @@
+//! 流程执行器的页面抓取：状态码检查、响应头捕获、重定向后的页面 URL
+
+mod common;
+
+use common::{TestServer, http_response};
+use crawler_runtime::{RuntimeError, crawler::CrawlerRuntime};
+
+const SEARCH_PAGE: &str = r#"<div class="item"><a class="title" href="book/1">第一本</a></div>"#;
+
+fn search_rule(server: &TestServer, extra: &str) -> CrawlerRuntime {
+    let rule = common::rule(&format!(
+        "[search]\nurl = \"{}\"\n{}",
+        server.url("/old?q={{ keyword }}"),
+        extra
+    ));
+    CrawlerRuntime::new(rule, None).unwrap()
+}
+
+#[tokio::test]
+async fn search_follows_redirect_and_resolves_against_final_url() {
+    let server = TestServer::start(|request| {
+        if request.starts_with("GET /old") {
+            http_response(302, &[("Location", "/list/search")], "")
+        } else {
+            http_response(200, &[], SEARCH_PAGE)
+        }
+    })
+    .await;
+    let runtime = search_rule(&server, "");
+
+    let response = runtime.search("书", 1).await.unwrap();
+    assert_eq!(response.items[0].url, server.url("/list/book/1"));
+}
+
+#[tokio::test]
+async fn search_rejects_unexpected_status() {
+    let server = TestServer::start(|_| http_response(503, &[], SEARCH_PAGE)).await;
+    let runtime = search_rule(&server, "[search.http.response]\nexpect_status = [200]");
+
+    let error = runtime.search("书", 1).await.unwrap_err();
+    assert!(!matches!(error, RuntimeError::EmptyValue), "{error:?}");
+    assert!(error.to_string().contains("503"), "{error}");
+}
+
+#[tokio::test]
+async fn search_captures_response_headers_into_flow_variables() {
+    let server =
+        TestServer::start(|_| http_response(200, &[("X-Book-Tag", "hot")], SEARCH_PAGE)).await;
+    let runtime = search_rule(
+        &server,
+        "[search.http.response]\ncapture_headers = { tag = \"X-Book-Tag\" }\n\
+         [search.fields.summary]\nsteps = [{ coalesce = [\"{{ tag }}\"] }]",
+    );
+
+    let response = runtime.search("书", 1).await.unwrap();
+    assert_eq!(response.items[0].summary.as_deref(), Some("hot"));
+}
+
+#[tokio::test]
+async fn not_found_with_expect_status_reports_actual_status() {
+    let server = TestServer::start(|_| http_response(404, &[], "<h1>页面不存在</h1>")).await;
+    let runtime = CrawlerRuntime::new(
+        common::rule("[detail.http.response]\nexpect_status = [200]"),
+        None,
+    )
+    .unwrap();
+
+    let error = runtime.detail(&server.url("/book/1")).await.unwrap_err();
+    assert!(
+        matches!(&error, RuntimeError::HttpRequest(message) if message.contains("404")),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn status_is_not_checked_without_expect_status() {
+    let server = TestServer::start(|_| http_response(404, &[], SEARCH_PAGE)).await;
+    let runtime = search_rule(&server, "");
+
+    let response = runtime.search("书", 1).await.unwrap();
+    assert_eq!(response.items.len(), 1);
+}
+
+#[tokio::test]
+async fn absolute_url_filter_uses_post_redirect_base() {
+    let server = TestServer::start(|request| {
+        if request.starts_with("GET /a ") {
+            http_response(301, &[("Location", "/b/")], "")
+        } else {
+            http_response(
+                200,
+                &[],
+                r#"<h1>书</h1><div class="author">作者</div><div class="cover"><img src="cover.jpg"></div>
+                <ul><li><a href="c/1.html">第一章</a></li></ul>"#,
+            )
+        }
+    })
+    .await;
+    let rule = common::rule(
+        r#"
+[detail.fields.cover]
+steps = [{ css = ".cover img" }, { attr = "src" }, { filter = "absolute_url" }]
+
+[detail.fields.chapters.url]
+steps = [{ attr = "href" }, { filter = "absolute_url" }]
+"#,
+    );
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    let detail = runtime.detail(&server.url("/a")).await.unwrap().to_json();
+    assert_eq!(detail["cover"], server.url("/b/cover.jpg"));
+    assert_eq!(detail["chapters"][0]["url"], server.url("/b/c/1.html"));
+}
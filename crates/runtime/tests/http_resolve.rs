@@ -0,0 +1,73 @@
+//! DNS 解析覆盖
+
+mod common;
+
+use common::{TestServer, http_response};
+use crawler_runtime::{RuntimeError, extractor::validate::SelectorValidator, http::HttpClient};
+use crawler_schema::config::HttpConfig;
+use std::collections::HashMap;
+
+fn client(host: &str, addr: String) -> HttpClient {
+    HttpClient::new(HttpConfig {
+        resolve: Some(HashMap::from([(host.to_string(), addr)])),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn overridden_host_is_sent_to_configured_address() {
+    let server = TestServer::start(|_| http_response(200, &[], "ok")).await;
+    let addr = server.url("").trim_start_matches("http://").to_string();
+    let client = client("example.test", addr);
+
+    let response = client
+        .inner()
+        .get("http://example.test/ping")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "ok");
+    let request = &server.requests()[0];
+    assert!(request.starts_with("GET /ping"), "{request}");
+    assert!(request.contains("host: example.test"), "{request}");
+}
+
+#[tokio::test]
+async fn port_in_url_takes_precedence() {
+    let server = TestServer::start(|_| http_response(200, &[], "ok")).await;
+    let port = server.url("").rsplit(':').next().unwrap().to_string();
+    let client = client("example.test", "127.0.0.1".to_string());
+
+    let response = client
+        .inner()
+        .get(format!("http://example.test:{port}/ping"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "ok");
+}
+
+#[test]
+fn resolve_accepts_ip_with_or_without_port() {
+    assert_eq!(HttpClient::resolve_addr("127.0.0.1").unwrap().port(), 0);
+    assert_eq!(
+        HttpClient::resolve_addr("127.0.0.1:8080").unwrap().port(),
+        8080
+    );
+    assert!(HttpClient::resolve_addr("[::1]:8080").is_ok());
+    assert!(HttpClient::resolve_addr("localhost:8080").is_err());
+}
+
+#[test]
+fn invalid_resolve_address_is_rejected_at_load() {
+    let rule = common::rule("[detail.http.resolve]\n\"example.test\" = \"not-an-ip\"");
+
+    let report = SelectorValidator::check_rule(&rule);
+    let error = report.errors().next().expect("resolve error");
+    assert!(
+        matches!(error, RuntimeError::InvalidConfigValue { field, .. }
+            if field == "detail.http.resolve.example.test"),
+        "{error:?}"
+    );
+}
@@ -0,0 +1,74 @@
+//! 连接、读取与总超时
+
+mod common;
+
+use crawler_runtime::{crawler::CrawlerRuntime, http::HttpClient};
+use crawler_schema::config::HttpConfig;
+use std::time::{Duration, Instant};
+use tokio::{io::AsyncWriteExt, net::TcpListener};
+
+/// 接受连接后先发送 `head`，然后停滞
+async fn stalling_server(head: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let _ = stream.write_all(head.as_bytes()).await;
+                tokio::time::sleep(Duration::from_secs(30)).await;
+            });
+        }
+    });
+    url
+}
+
+fn search_runtime(url: &str, http: &str) -> CrawlerRuntime {
+    let rule = common::rule(&format!(
+        "[search]\nurl = \"{}/search?q={{{{ keyword }}}}\"\n[search.http]\n{}",
+        url, http
+    ));
+    CrawlerRuntime::new(rule, None).unwrap()
+}
+
+#[test]
+fn client_accepts_all_three_timeouts() {
+    let config = HttpConfig {
+        timeout: Some(30),
+        connect_timeout: Some(3),
+        read_timeout: Some(5),
+        ..Default::default()
+    };
+    assert!(HttpClient::new(config).is_ok());
+}
+
+#[tokio::test]
+async fn flow_read_timeout_applies_to_stalled_body() {
+    let url = stalling_server("HTTP/1.1 200 OK\r\nContent-Length: 1000\r\n\r\n<div>").await;
+    let runtime = search_runtime(&url, "read_timeout = 1");
+
+    let started = Instant::now();
+    let error = runtime.search("书", 1).await.unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(error.to_string().contains("read timed out"), "{error}");
+}
+
+#[tokio::test]
+async fn flow_read_timeout_applies_while_waiting_for_headers() {
+    let url = stalling_server("").await;
+    let runtime = search_runtime(&url, "read_timeout = 1");
+
+    let started = Instant::now();
+    let error = runtime.search("书", 1).await.unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(error.to_string().contains("read timed out"), "{error}");
+}
+
+#[tokio::test]
+async fn flow_total_timeout_overrides_client_timeout() {
+    let url = stalling_server("").await;
+    let runtime = search_runtime(&url, "timeout = 1");
+
+    let started = Instant::now();
+    runtime.search("书", 1).await.unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
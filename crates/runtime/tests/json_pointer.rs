@@ -0,0 +1,54 @@
+//! JSON Pointer 提取步骤
+
+mod common;
+
+use crawler_runtime::extractor::validate::SelectorValidator;
+use serde_json::json;
+
+fn response() -> serde_json::Value {
+    json!({
+        "data": {
+            "book": { "title": "斗破苍穹", "author": { "name": "天蚕土豆" } },
+            "chapters": [{ "title": "第一章" }, { "title": "第二章" }],
+        }
+    })
+}
+
+#[test]
+fn pointer_navigates_nested_objects() {
+    let value = common::extract_json(
+        json!({ "steps": [{ "json_pointer": "/data/book/author/name" }] }),
+        response(),
+    );
+    assert_eq!(value.unwrap().as_str(), Some("天蚕土豆"));
+}
+
+#[test]
+fn pointer_indexes_into_arrays() {
+    let value = common::extract_json(
+        json!({ "steps": [{ "json_pointer": "/data/chapters/1/title" }] }),
+        response(),
+    );
+    assert_eq!(value.unwrap().as_str(), Some("第二章"));
+}
+
+#[test]
+fn missing_pointer_is_null_when_nullable() {
+    let value = common::extract_json(
+        json!({ "steps": [{ "json_pointer": "/data/book/cover" }], "nullable": true }),
+        response(),
+    );
+    assert!(value.unwrap().is_empty());
+}
+
+#[test]
+fn pointer_without_leading_slash_is_rejected_at_load() {
+    let rule =
+        common::rule("[search.fields.title]\nsteps = [{ json_pointer = \"data/book/title\" }]");
+    let error = SelectorValidator::check_rule(&rule)
+        .errors()
+        .next()
+        .expect("pointer error")
+        .to_string();
+    assert!(error.contains("search.fields.title.steps[0]"), "{error}");
+}
@@ -0,0 +1,52 @@
+//! 正则步骤
+
+mod common;
+
+use serde_json::json;
+
+const LINKS: &str = r#"<ul>
+<li><a href="/book/101.html">一</a></li>
+<li><a href="/author/7.html">作者</a></li>
+<li><a href="/book/205.html">二</a></li>
+</ul>"#;
+
+#[test]
+fn regex_applies_element_wise_to_arrays() {
+    let value = common::extract_html(
+        json!({ "steps": [
+            { "css": { "expr": "a", "all": true } },
+            { "attr": "href" },
+            { "regex": "/book/(\\d+)\\.html" },
+        ] }),
+        LINKS,
+    )
+    .unwrap();
+    assert_eq!(value.to_owned_json(), json!(["101", "205"]));
+}
+
+#[test]
+fn regex_on_scalar_returns_single_capture() {
+    let value = common::extract_html(
+        json!({ "steps": [{ "css": "a" }, { "attr": "href" }, { "regex": "/book/(\\d+)" }] }),
+        LINKS,
+    )
+    .unwrap();
+    assert_eq!(value.as_str(), Some("101"));
+}
+
+#[test]
+fn named_groups_apply_element_wise() {
+    let value = common::extract_html(
+        json!({ "steps": [
+            { "css": { "expr": "a", "all": true } },
+            { "attr": "href" },
+            { "regex": { "pattern": "/(?<kind>book)/(?<id>\\d+)", "named": true } },
+        ] }),
+        LINKS,
+    )
+    .unwrap();
+    assert_eq!(
+        value.to_owned_json(),
+        json!([{ "kind": "book", "id": "101" }, { "kind": "book", "id": "205" }])
+    );
+}
@@ -0,0 +1,130 @@
+//! 规则规范化与资源限制
+
+mod common;
+
+use common::{TestServer, http_response};
+use crawler_runtime::{
+    RuntimeError,
+    context::FlowLimits,
+    crawler::{CrawlerRuleExt, CrawlerRuntime},
+    flow::search::SearchRequest,
+};
+use std::collections::HashMap;
+
+#[test]
+fn normalize_fills_default_limits() {
+    let limits = common::rule("").normalize().limits.unwrap();
+    let defaults = FlowLimits::default();
+    assert_eq!(limits.max_http_requests, Some(defaults.max_http_requests));
+    assert_eq!(limits.max_depth, Some(defaults.max_depth));
+    assert_eq!(limits.max_array_len, Some(defaults.max_array_len as u64));
+    assert_eq!(limits.timeout, None);
+}
+
+#[test]
+fn normalize_keeps_configured_limits() {
+    let rule = common::rule("[limits]\nmax_http_requests = 3\ntimeout = 10");
+    let limits = rule.normalize().limits.unwrap();
+    assert_eq!(limits.max_http_requests, Some(3));
+    assert_eq!(limits.timeout, Some(10));
+    assert_eq!(limits.max_depth, Some(FlowLimits::default().max_depth));
+}
+
+#[test]
+fn normalize_resolves_http_config_for_every_flow() {
+    let rule =
+        common::rule("[http]\ntimeout = 5\n[detail.http]\nuser_agent = \"detail\"").normalize();
+    let search = rule.search.http.unwrap();
+    let detail = rule.detail.http.unwrap();
+    assert_eq!(search.timeout, Some(5));
+    assert!(search.user_agent.is_some());
+    assert_eq!(detail.timeout, Some(5));
+    assert_eq!(detail.user_agent.as_deref(), Some("detail"));
+}
+
+#[tokio::test]
+async fn runtime_applies_rule_limits() {
+    let runtime =
+        CrawlerRuntime::new(common::rule("[limits]\nmax_http_requests = 0"), None).unwrap();
+    let error = runtime.search("test", 1).await.unwrap_err();
+    assert!(
+        matches!(error, RuntimeError::ResourceLimitExceeded { limit: 0, .. }),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn builder_limits_override_rule_limits() {
+    let runtime = CrawlerRuntime::builder()
+        .rule(common::rule("[limits]\nmax_http_requests = 5"))
+        .limits(FlowLimits::default().with_max_http_requests(0))
+        .build()
+        .unwrap();
+    let error = runtime.search("test", 1).await.unwrap_err();
+    assert!(
+        matches!(error, RuntimeError::ResourceLimitExceeded { limit: 0, .. }),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn request_counter_stops_at_the_cap() {
+    let flow_context = common::flow_context().with_max_http_requests(3);
+    let recorded = (0..10)
+        .take_while(|_| flow_context.record_http_request().is_ok())
+        .count();
+    assert_eq!(recorded, 3);
+    assert_eq!(flow_context.http_request_count(), 3);
+
+    let error = flow_context.record_http_request().unwrap_err();
+    assert!(
+        matches!(&error, RuntimeError::ResourceLimitExceeded { limit_type, limit: 3 } if limit_type == "http_requests"),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn detail_prefetch_shares_the_search_request_budget() {
+    let server = TestServer::start(|request| {
+        if request.starts_with("GET /search") {
+            let items: String = (1..=4)
+                .map(|i| {
+                    format!(
+                        r#"<div class="item"><a class="title" href="/book/{i}">书{i}</a></div>"#
+                    )
+                })
+                .collect();
+            http_response(200, &[], items)
+        } else {
+            http_response(200, &[], r#"<h1>书</h1><div class="author">作者</div><ul><li><a href="/c/1">第一章</a></li></ul>"#)
+        }
+    })
+    .await;
+    let rule = common::rule(&format!(
+        "[search]\nurl = \"{}\"\n[limits]\nmax_http_requests = 3",
+        server.url("/search?q={{ keyword }}")
+    ));
+    let runtime = CrawlerRuntime::new(rule, None).unwrap();
+
+    let response = runtime
+        .search_with(SearchRequest {
+            keyword: "书".to_string(),
+            page: 1,
+            cursor: None,
+            sorts: HashMap::new(),
+            filters: HashMap::new(),
+            prefetch_detail: true,
+            prefetch_concurrency: Some(1),
+        })
+        .await
+        .unwrap();
+
+    // 搜索 1 次 + 预取 2 次后达到上限，其余预取被拒绝
+    assert_eq!(server.requests().len(), 3);
+    let prefetched = response
+        .items
+        .iter()
+        .filter(|item| item.raw.get("detail").is_some())
+        .count();
+    assert_eq!(prefetched, 2);
+}
@@ -0,0 +1,227 @@
+//! 规则校验
+
+mod common;
+
+use crawler_runtime::{
+    RuntimeError,
+    context::RuntimeContext,
+    extractor::validate::SelectorValidator,
+};
+
+#[test]
+fn base_rule_is_valid() {
+    let rule = common::rule("");
+    assert!(!SelectorValidator::check_rule(&rule).has_errors());
+}
+
+#[test]
+fn script_reading_later_variable_is_reported() {
+    let rule = common::rule(
+        r#"
+[search.fields.title]
+steps = [
+    { script = { code = "return input + suffix" } },
+    { set_var = { name = "suffix" } },
+]
+"#,
+    );
+    let errors = SelectorValidator::use_before_definition(&rule);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].to_string().contains("suffix"));
+}
+
+#[test]
+fn script_strings_and_properties_are_not_references() {
+    let rule = common::rule(
+        r#"
+[search.fields.title]
+steps = [
+    { script = { code = "// suffix\nreturn input.suffix + 'suffix'" } },
+    { set_var = { name = "suffix" } },
+]
+"#,
+    );
+    assert!(SelectorValidator::use_before_definition(&rule).is_empty());
+}
+
+#[test]
+fn coalesce_reading_later_variable_is_reported() {
+    let rule = common::rule(
+        r#"
+[search.fields.title]
+steps = [
+    { coalesce = ["{{ fallback }}", "suffix"] },
+    { set_var = { name = "fallback" } },
+    { set_var = { name = "suffix" } },
+]
+"#,
+    );
+    let errors = SelectorValidator::use_before_definition(&rule);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].to_string().contains("fallback"));
+}
+
+#[test]
+fn variable_read_by_template_is_used() {
+    let rule = common::rule(
+        r#"
+[search.fields.title]
+steps = [{ set_var = { name = "book_id" } }]
+
+[detail]
+url = "https://example.com/book/{{ book_id | trim }}"
+"#,
+    );
+    assert!(SelectorValidator::unused_variables(&rule).is_empty());
+}
+
+#[test]
+fn variable_name_in_plain_text_is_unused() {
+    let rule = common::rule(
+        r#"
+[search.fields.title]
+steps = [{ set_var = { name = "book_id" } }, { css = ".book_id" }]
+
+[detail]
+url = "https://example.com/book_id/{{ detail_url }}"
+"#,
+    );
+    assert_eq!(
+        SelectorValidator::unused_variables(&rule),
+        vec![(
+            "book_id".to_string(),
+            "search.fields.title.steps[0]".to_string()
+        )]
+    );
+}
+
+#[test]
+fn variable_read_by_script_or_zip_is_used() {
+    let rule = common::rule(
+        r#"
+[search.fields.title]
+steps = [
+    { set_var = { name = "prefix" } },
+    { set_var = { name = "tags" } },
+    { script = { code = "return prefix + input" } },
+    { filter = "zip(tags)" },
+]
+"#,
+    );
+    assert!(SelectorValidator::unused_variables(&rule).is_empty());
+}
+
+#[test]
+fn invalid_condition_regex_is_reported_at_load() {
+    let rule = common::rule(
+        r#"
+[search.fields.title]
+steps = [
+    { condition = { when = [{ attr = "text" }], op = "matches", value = "([a-z", then = [] } },
+]
+"#,
+    );
+    let report = SelectorValidator::check_rule(&rule);
+    let error = report.errors().next().expect("regex error").to_string();
+    assert!(
+        error.contains("search.fields.title.steps[0].value"),
+        "{error}"
+    );
+}
+
+#[test]
+fn detail_fields_not_matching_media_type_give_friendly_error() {
+    let rule = common::rule("[meta]\nmedia_type = \"video\"");
+    let error = SelectorValidator::validate_media_type(&rule).unwrap_err();
+    match error {
+        RuntimeError::InvalidConfigValue { field, reason } => {
+            assert_eq!(field, "detail.fields");
+            assert!(reason.contains("期望"), "{reason}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(RuntimeContext::new(rule).is_err());
+}
+
+#[test]
+fn content_fields_not_matching_media_type_are_reported() {
+    let rule = common::rule(
+        r#"
+[content]
+url = "{{ url }}"
+
+[content.fields]
+media_type = "video"
+play_url.steps = [{ css = "video" }, { attr = "src" }]
+"#,
+    );
+    let error = SelectorValidator::validate_media_type(&rule).unwrap_err();
+    assert!(
+        matches!(&error, RuntimeError::InvalidConfigValue { field, .. } if field == "content.fields"),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn known_encoding_label_is_accepted() {
+    let rule = common::rule("[meta]\nencoding = \"gbk\"");
+    assert!(SelectorValidator::validate_encoding(&rule).is_ok());
+    assert!(SelectorValidator::validate_encoding(&common::rule("")).is_ok());
+}
+
+#[test]
+fn misspelled_encoding_is_rejected_with_suggestion() {
+    let rule = common::rule("[meta]\nencoding = \"gb2313\"");
+    match SelectorValidator::validate_encoding(&rule).unwrap_err() {
+        RuntimeError::InvalidConfigValue { field, reason } => {
+            assert_eq!(field, "meta.encoding");
+            assert!(reason.contains("'gb2312'"), "{reason}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(RuntimeContext::new(rule).is_err());
+}
+
+#[test]
+fn unknown_template_filter_is_rejected_at_load() {
+    let rule = common::rule(
+        "[search]\nurl = \"https://example.com/search?q={{ keyword | no_such_filter }}\"",
+    );
+
+    let error = RuntimeContext::new(rule).unwrap_err();
+    assert!(
+        matches!(&error, RuntimeError::InvalidConfigValue { field, reason }
+            if field == "search.url" && reason.contains("no_such_filter")),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn template_syntax_errors_are_reported_with_field_path() {
+    let rule = common::rule(
+        r#"
+[search.http.request]
+headers = { Referer = "{{ base_url" }
+
+[search.fields.summary]
+steps = [{ coalesce = ["{% if %}"] }]
+"#,
+    );
+
+    let report = SelectorValidator::check_rule(&rule);
+    let fields: Vec<_> = report
+        .errors()
+        .filter_map(|error| match error {
+            RuntimeError::InvalidConfigValue { field, .. } => Some(field.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert!(
+        fields.contains(&"search.http.request.headers.Referer"),
+        "{fields:?}"
+    );
+    assert!(
+        fields.contains(&"search.fields.summary.steps[0].coalesce[0]"),
+        "{fields:?}"
+    );
+}
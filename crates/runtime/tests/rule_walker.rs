@@ -0,0 +1,75 @@
+//! 提取步骤遍历
+
+mod common;
+
+use crawler_runtime::extractor::{RuleWalker, StepVisitor};
+use crawler_schema::extract::{ExtractStep, FieldExtractor};
+use serde_json::json;
+
+#[test]
+fn walker_visits_nested_steps_with_paths() {
+    let extractor: FieldExtractor = serde_json::from_value(json!({
+        "steps": [
+            { "css": { "expr": ".item", "all": true } },
+            { "map": [
+                { "css": ".title" },
+                { "condition": {
+                    "when": [{ "attr": "class" }],
+                    "op": "contains",
+                    "value": "vip",
+                    "then": [{ "filter": "prepend(VIP:)" }],
+                    "otherwise": [{ "attr": "text" }],
+                } },
+            ] },
+        ],
+        "fallback": [[{ "css": "h1" }]],
+    }))
+    .unwrap();
+
+    let mut paths = Vec::new();
+    let mut collect = |path: &str, _: &ExtractStep| {
+        paths.push(path.to_string());
+        Ok(())
+    };
+    RuleWalker::walk_extractor(&mut collect, "field", &extractor).unwrap();
+    assert_eq!(
+        paths,
+        [
+            "field.steps[0]",
+            "field.steps[1]",
+            "field.steps[1].map[0]",
+            "field.steps[1].map[1]",
+            "field.steps[1].map[1].when[0]",
+            "field.steps[1].map[1].then[0]",
+            "field.steps[1].map[1].otherwise[0]",
+            "field.fallback[0][0]",
+        ]
+    );
+}
+
+#[test]
+fn walker_covers_every_flow_of_a_rule() {
+    struct Counter {
+        extractors: usize,
+        steps: usize,
+    }
+    impl StepVisitor for Counter {
+        fn visit_step(&mut self, _: &str, _: &ExtractStep) -> crawler_runtime::Result<()> {
+            self.steps += 1;
+            Ok(())
+        }
+        fn visit_extractor(&mut self, _: &str, _: &FieldExtractor) -> crawler_runtime::Result<()> {
+            self.extractors += 1;
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter {
+        extractors: 0,
+        steps: 0,
+    };
+    RuleWalker::walk_rule(&common::rule(""), &mut counter).unwrap();
+    // search: list/title/url；detail: title/author/chapters.list/title/url
+    assert_eq!(counter.extractors, 8);
+    assert_eq!(counter.steps, 12);
+}
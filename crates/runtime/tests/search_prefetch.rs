@@ -0,0 +1,84 @@
+//! 搜索结果的详情预取
+
+mod common;
+
+use common::{TestServer, http_response};
+use crawler_runtime::{crawler::CrawlerRuntime, flow::search::SearchRequest};
+use std::collections::HashMap;
+
+const SEARCH_PAGE: &str = r#"<div class="item"><a class="title" href="/book/1">书一</a></div>
+<div class="item"><a class="title" href="/book/2">书二</a></div>
+<div class="item"><a class="title" href="/book/missing">书三</a></div>"#;
+
+/// `/book/<id>` 返回以 `id` 命名的详情页，`/book/missing` 返回 404
+fn book_server() -> impl Fn(&str) -> Vec<u8> + Send + Sync {
+    |request: &str| {
+        let path = request.split(' ').nth(1).unwrap_or_default();
+        if path.starts_with("/search") {
+            http_response(200, &[], SEARCH_PAGE)
+        } else if path == "/book/missing" {
+            http_response(404, &[], "")
+        } else {
+            let id = path.trim_start_matches("/book/");
+            http_response(
+                200,
+                &[],
+                format!(
+                    r#"<h1>详情{id}</h1><div class="author">作者{id}</div><ul><li><a href="/c/{id}">第一章</a></li></ul>"#
+                ),
+            )
+        }
+    }
+}
+
+fn request(prefetch_detail: bool) -> SearchRequest {
+    SearchRequest {
+        keyword: "书".to_string(),
+        page: 1,
+        cursor: None,
+        sorts: HashMap::new(),
+        filters: HashMap::new(),
+        prefetch_detail,
+        prefetch_concurrency: Some(2),
+    }
+}
+
+fn runtime(server: &TestServer) -> CrawlerRuntime {
+    let rule = common::rule(&format!(
+        "[search]\nurl = \"{}\"\n[detail.http.response]\nexpect_status = [200]",
+        server.url("/search?q={{ keyword }}")
+    ));
+    CrawlerRuntime::new(rule, None).unwrap()
+}
+
+#[tokio::test]
+async fn prefetched_details_are_attached_to_their_items() {
+    let server = TestServer::start(book_server()).await;
+    let runtime = runtime(&server);
+
+    let response = runtime.search_with(request(true)).await.unwrap();
+
+    assert_eq!(response.items.len(), 3);
+    assert_eq!(response.items[0].raw["detail"]["title"], "详情1");
+    assert_eq!(response.items[0].raw["detail"]["author"], "作者1");
+    assert_eq!(response.items[1].raw["detail"]["title"], "详情2");
+    // 单个详情预取失败不影响搜索结果
+    assert!(response.items[2].raw.get("detail").is_none());
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn details_are_not_fetched_without_prefetch() {
+    let server = TestServer::start(book_server()).await;
+    let runtime = runtime(&server);
+
+    let response = runtime.search_with(request(false)).await.unwrap();
+
+    assert!(
+        response
+            .items
+            .iter()
+            .all(|item| item.raw.get("detail").is_none())
+    );
+    assert_eq!(server.requests().len(), 1);
+}
@@ -0,0 +1,79 @@
+//! 模板渲染
+
+mod common;
+
+use crawler_runtime::template::TemplateExt;
+use crawler_schema::template::Template;
+use serde_json::json;
+
+#[test]
+fn literal_template_is_rendered_verbatim() {
+    let flow_context = common::flow_context();
+    let text = r#"https://example.com/api?q={"id":1}&raw={% endraw %}{{ keyword }}"#;
+    let template = Template::literal(text);
+    assert_eq!(template.render(&flow_context).unwrap(), text);
+    assert!(template.validate().is_ok());
+    assert!(template.variables().unwrap().is_empty());
+}
+
+#[test]
+fn literal_template_serializes_as_plain_string() {
+    let template = Template::literal("{{ keyword }}");
+    assert_eq!(
+        serde_json::to_value(&template).unwrap(),
+        json!("{{ keyword }}")
+    );
+    let parsed: Template = serde_json::from_value(json!("{{ keyword }}")).unwrap();
+    assert!(!parsed.is_literal());
+}
+
+#[test]
+fn variables_are_rendered_without_html_escaping() {
+    let mut flow_context = common::flow_context();
+    flow_context.set("url", json!("https://example.com/book/1?a=1&b=<2>"));
+    let template = Template::from("{{ url }}".to_string());
+    assert_eq!(
+        template.render(&flow_context).unwrap(),
+        "https://example.com/book/1?a=1&b=<2>"
+    );
+}
+
+#[test]
+fn raw_block_is_rendered_verbatim() {
+    let mut flow_context = common::flow_context();
+    flow_context.set("keyword", json!("斗破"));
+    let template = Template::from(
+        r#"https://example.com/api?q={% raw %}{"id":1,"kw":"{{ keyword }}"}{% endraw %}&k={{ keyword }}"#
+            .to_string(),
+    );
+
+    assert_eq!(
+        template.render(&flow_context).unwrap(),
+        r#"https://example.com/api?q={"id":1,"kw":"{{ keyword }}"}&k=斗破"#
+    );
+    // `{% raw %}` 块中的变量不计入引用
+    assert_eq!(
+        template
+            .variables()
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        ["keyword"]
+    );
+}
+
+#[test]
+fn whitespace_control_trims_adjacent_whitespace() {
+    let mut flow_context = common::flow_context();
+    flow_context.set("page", json!(2));
+    flow_context.set("vip", json!(true));
+    let template = Template::from(
+        "https://example.com/list?page=\n    {{- page -}}\n    {%- if vip %}&vip=1{% endif -%}\n"
+            .to_string(),
+    );
+
+    assert_eq!(
+        template.render(&flow_context).unwrap(),
+        "https://example.com/list?page=2&vip=1"
+    );
+}
@@ -0,0 +1,28 @@
+//! 运行时预热
+
+mod common;
+
+use crawler_runtime::crawler::CrawlerRuntime;
+
+#[test]
+fn warm_up_of_valid_rule_reports_no_errors() {
+    let runtime = CrawlerRuntime::new(common::rule(""), None).unwrap();
+
+    let report = runtime.warm_up();
+    assert!(!report.has_errors(), "{:?}", report.diagnostics());
+}
+
+#[test]
+fn warm_up_runs_once() {
+    let runtime = CrawlerRuntime::new(common::rule(""), None).unwrap();
+
+    assert!(std::ptr::eq(runtime.warm_up(), runtime.warm_up()));
+}
+
+#[test]
+fn invalid_selector_is_rejected_when_creating_runtime() {
+    let rule =
+        common::rule("[search.fields.title]\nsteps = [{ css = \"div[\" }, { attr = \"text\" }]");
+
+    assert!(CrawlerRuntime::new(rule, None).is_err());
+}
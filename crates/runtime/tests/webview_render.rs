@@ -0,0 +1,51 @@
+//! 由 WebView 渲染的页面同样经过状态码检查与响应头捕获
+
+mod common;
+
+use crawler_runtime::{
+    crawler::CrawlerRuntime,
+    webview::{MockWebViewProvider, WebViewResponse},
+};
+use std::{collections::HashMap, sync::Arc};
+
+const SEARCH_PAGE: &str = r#"<div class="item"><a class="title" href="/book/1">第一本</a></div>"#;
+
+fn render_runtime(response: WebViewResponse) -> CrawlerRuntime {
+    let rule = common::rule(
+        "[search.http.render]\nwait_for = \".item\"\n\
+         [search.http.response]\nexpect_status = [200]\ncapture_headers = { tag = \"X-Book-Tag\" }\n\
+         [search.fields.summary]\nsteps = [{ coalesce = [\"{{ tag }}\"] }]",
+    );
+    let provider = MockWebViewProvider::with_response(response.with_html(SEARCH_PAGE));
+    CrawlerRuntime::new(rule, Some(Arc::new(provider))).unwrap()
+}
+
+#[tokio::test]
+async fn rendered_page_status_is_checked() {
+    let runtime = render_runtime(WebViewResponse::success().with_status(403));
+
+    let error = runtime.search("书", 1).await.unwrap_err();
+    assert!(error.to_string().contains("403"), "{error}");
+}
+
+#[tokio::test]
+async fn rendered_page_headers_are_captured() {
+    let headers = HashMap::from([("x-book-tag".to_string(), "hot".to_string())]);
+    let runtime = render_runtime(
+        WebViewResponse::success()
+            .with_status(200)
+            .with_headers(headers),
+    );
+
+    let response = runtime.search("书", 1).await.unwrap();
+    assert_eq!(response.items[0].title, "第一本");
+    assert_eq!(response.items[0].summary.as_deref(), Some("hot"));
+}
+
+#[tokio::test]
+async fn rendered_page_without_status_skips_status_check() {
+    let runtime = render_runtime(WebViewResponse::success());
+
+    let response = runtime.search("书", 1).await.unwrap();
+    assert_eq!(response.items.len(), 1);
+}
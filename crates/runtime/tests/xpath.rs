@@ -0,0 +1,56 @@
+//! XPath 转换
+
+mod common;
+
+use crawler_runtime::extractor::selector::xpath::XpathExecutor;
+use serde_json::json;
+
+#[test]
+fn translates_supported_subset() {
+    let cases = [
+        ("//div[@class='title']/a", r#"div[class="title"] > a"#),
+        (
+            "//ul/li[2][@class='x']",
+            r#"ul > li:nth-of-type(2)[class="x"]"#,
+        ),
+        (
+            "//a[contains(@href,'book')]/@href",
+            r#"a[href*="book"]::attr(href)"#,
+        ),
+        ("//h1/text()", "h1::own_text"),
+        ("//div//text()", "div::text"),
+    ];
+    for (xpath, css) in cases {
+        assert_eq!(XpathExecutor::to_css(xpath).unwrap(), css, "{xpath}");
+    }
+}
+
+#[test]
+fn rejects_expressions_without_css_equivalent() {
+    for xpath in [
+        "//div/ancestor::section",
+        "//div/..",
+        ".//a",
+        "//a | //span",
+        "//div[@class='x'][2]",
+        "//a[contains(text(),'下一页')]",
+        "//a[text()='下一页']",
+        "//div[@id and @class]",
+        "//li[position()>1]",
+        "//a[@id!='x']",
+        "//a[contains(@class,'x') or contains(@class,'y')]",
+        "//p[count(a)=1]",
+    ] {
+        let error = XpathExecutor::to_css(xpath).unwrap_err().to_string();
+        assert!(error.contains("Unsupported XPath"), "{xpath}: {error}");
+    }
+}
+
+#[test]
+fn text_steps_follow_xpath_semantics() {
+    let html = "<h1>标题<small>副标题</small></h1>";
+    let own = common::extract_html(json!({ "steps": [{ "xpath": "//h1/text()" }] }), html);
+    assert_eq!(own.unwrap().as_str(), Some("标题"));
+    let all = common::extract_html(json!({ "steps": [{ "xpath": "//h1//text()" }] }), html);
+    assert_eq!(all.unwrap().as_str(), Some("标题副标题"));
+}
@@ -3,13 +3,13 @@
 //! 定义所有 HTTP 相关的配置结构，包括：
 //! - `HttpMethod`: HTTP 请求方法
 //! - `RequestConfig`: 请求配置（方法、请求头、请求体）
-//! - `ResponseConfig`: 响应配置（编码、内容类型、预处理）
+//! - `ResponseConfig`: 响应配置（编码、内容类型、预处理、响应头捕获）
 //! - `HttpConfig`: 完整 HTTP 配置（连接参数 + 请求 + 响应）
 
 use crate::{script::Script, template::Template};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 // ============================================================================
 // HTTP 方法
@@ -57,6 +57,54 @@ impl HttpMethod {
     }
 }
 
+/// HTTP 方法解析错误
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("未知的 HTTP 方法: {0}")]
+pub struct ParseHttpMethodError(pub String);
+
+impl FromStr for HttpMethod {
+    type Err = ParseHttpMethodError;
+
+    /// 解析方法名称（不区分大小写）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "GET" => Ok(Self::Get),
+            "POST" => Ok(Self::Post),
+            "PUT" => Ok(Self::Put),
+            "DELETE" => Ok(Self::Delete),
+            "HEAD" => Ok(Self::Head),
+            "OPTIONS" => Ok(Self::Options),
+            "PATCH" => Ok(Self::Patch),
+            _ => Err(ParseHttpMethodError(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&str> for HttpMethod {
+    type Error = ParseHttpMethodError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+// ============================================================================
+// 代理策略
+// ============================================================================
+
+/// 代理选择策略 (ProxyStrategy)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyStrategy {
+    /// 依次轮换
+    #[default]
+    RoundRobin,
+    /// 随机选择
+    Random,
+    /// 同一主机固定使用同一代理
+    StickyPerHost,
+}
+
 // ============================================================================
 // 请求配置
 // ============================================================================
@@ -74,6 +122,18 @@ impl HttpMethod {
 /// body = '{"keyword": "{{ keyword }}"}'
 /// headers = { "X-Custom-Header" = "value" }
 /// ```
+///
+/// 表单与 JSON 请求体（`body`、`form`、`json` 三选一）：
+///
+/// ```toml
+/// [search.http.request]
+/// method = "POST"
+/// form = { searchkey = "{{ keyword }}", page = "{{ page }}" }
+///
+/// [login.http.request]
+/// method = "POST"
+/// json = { username = "{{ username }}", password = "{{ password }}" }
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(deny_unknown_fields)]
 pub struct RequestConfig {
@@ -81,10 +141,18 @@ pub struct RequestConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub method: Option<HttpMethod>,
 
-    /// 请求体模板（用于 POST 等请求）
+    /// 请求体模板（用于 POST 等请求），未指定内容类型时按表单编码发送
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<Template>,
 
+    /// 表单请求体，值为模板，以 `application/x-www-form-urlencoded` 发送
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form: Option<HashMap<String, Template>>,
+
+    /// JSON 请求体，其中的字符串值按模板渲染，以 `application/json` 发送
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<serde_json::Value>,
+
     /// 额外的请求头
     #[serde(skip_serializing_if = "Option::is_none")]
     pub headers: Option<HashMap<String, Template>>,
@@ -136,6 +204,25 @@ pub enum ResponseEncoding {
     Windows1252,
 }
 
+impl ResponseEncoding {
+    /// 编码标签（WHATWG 标签名），`Auto` 返回 `None`
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            Self::Auto => None,
+            Self::Utf8 => Some("utf-8"),
+            Self::Gbk => Some("gbk"),
+            Self::Gb2312 => Some("gb2312"),
+            Self::Gb18030 => Some("gb18030"),
+            Self::Big5 => Some("big5"),
+            Self::ShiftJis => Some("shift_jis"),
+            Self::EucJp => Some("euc-jp"),
+            Self::EucKr => Some("euc-kr"),
+            Self::Iso8859_1 => Some("iso-8859-1"),
+            Self::Windows1252 => Some("windows-1252"),
+        }
+    }
+}
+
 /// 响应内容类型
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
@@ -168,6 +255,18 @@ pub enum ResponseContentType {
 /// encoding = "auto"
 /// preprocess = { inline = "return decrypt(response.body, 'key');" }
 /// ```
+///
+/// ## 限定响应状态码
+/// ```toml
+/// [response]
+/// expect_status = [200]
+/// ```
+///
+/// ## 捕获响应头
+/// ```toml
+/// [response]
+/// capture_headers = { redirect = "Location", token = "X-Auth-Token" }
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ResponseConfig {
@@ -191,6 +290,45 @@ pub struct ResponseConfig {
     /// 返回值：处理后的响应体字符串
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preprocess: Option<Script>,
+
+    /// 期望的响应状态码
+    ///
+    /// 设置后，状态码不在列表中的响应将使流程失败，
+    /// 用于尽早发现软 404、限流页等错误页面。未设置时不检查状态码。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expect_status: Option<Vec<u16>>,
+
+    /// 捕获响应头到流程变量
+    ///
+    /// 键为变量名，值为响应头名称（不区分大小写）。
+    /// 响应头的值以字符串保存，多个同名响应头（如 `Set-Cookie`）以 `; ` 连接，
+    /// 响应中不存在的响应头保存为 `null`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture_headers: Option<HashMap<String, String>>,
+}
+
+// ============================================================================
+// 页面渲染配置
+// ============================================================================
+
+/// 页面渲染配置 (RenderConfig)
+///
+/// 由 WebView 加载页面，等待渲染完成后取页面 HTML 作为响应体。
+/// `wait_for` 与 `wait_script` 都未设置时，页面加载完成即取 HTML
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RenderConfig {
+    /// 等待出现的 CSS 选择器
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_for: Option<String>,
+
+    /// 等待条件脚本（JavaScript），返回 true 时视为渲染完成，优先于 `wait_for`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_script: Option<String>,
+
+    /// 等待超时（秒，默认 30）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u32>,
 }
 
 // ============================================================================
@@ -230,9 +368,24 @@ pub const DEFAULT_MAX_REDIRECTS: u32 = 10;
 /// ```toml
 /// [search.http]
 /// timeout = 10
+/// connect_timeout = 3
+/// read_timeout = 5
 /// request.method = "POST"
 /// response.encoding = "gbk"
 /// ```
+///
+/// ## 代理轮换
+/// ```toml
+/// [http]
+/// proxies = ["http://10.0.0.1:8080", "http://10.0.0.2:8080"]
+/// proxy_strategy = "sticky_per_host"
+/// ```
+///
+/// ## 由 WebView 渲染页面
+/// ```toml
+/// [detail.http.render]
+/// wait_for = ".chapter-list li"
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(deny_unknown_fields)]
 pub struct HttpConfig {
@@ -241,18 +394,37 @@ pub struct HttpConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_agent: Option<String>,
 
-    /// 请求超时时间（秒）
+    /// 请求总超时时间（秒）
+    ///
+    /// 从发起连接到读完响应体的总时长上限
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u32>,
 
     /// 连接超时时间（秒）
+    ///
+    /// 仅限制建立 TCP/TLS 连接的时长
     #[serde(skip_serializing_if = "Option::is_none")]
     pub connect_timeout: Option<u32>,
 
+    /// 读取超时时间（秒）
+    ///
+    /// 两次收到数据之间的最长空闲时间，每次读到数据后重新计时。
+    /// 适用于连接很快但中途停滞的慢速服务器；与 `timeout` 同时设置时先到者生效
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_timeout: Option<u32>,
+
     /// 代理地址
     #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy: Option<String>,
 
+    /// 代理池，设置后按 `proxy_strategy` 为每个请求选择代理（优先于 `proxy`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxies: Option<Vec<String>>,
+
+    /// 代理选择策略，默认为 `round_robin`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_strategy: Option<ProxyStrategy>,
+
     /// 是否验证 SSL 证书
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verify_ssl: Option<bool>,
@@ -265,6 +437,20 @@ pub struct HttpConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_redirects: Option<u32>,
 
+    /// DNS 解析覆盖（主机名 → `IP` 或 `IP:端口`）
+    ///
+    /// 类似 curl 的 `--resolve`，用于将域名固定到指定 IP（本地测试、绕过 DNS 调度）。
+    /// 未写端口时使用协议的默认端口，URL 中显式指定的端口总是优先；
+    /// 无效的地址在规则加载时报错
+    ///
+    /// ```toml
+    /// [http.resolve]
+    /// "www.example.com" = "127.0.0.1"
+    /// "api.example.com" = "127.0.0.1:8080"
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve: Option<HashMap<String, String>>,
+
     // ========== 限流与重试 ==========
     /// 请求间隔时间（毫秒），用于限流
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -282,6 +468,18 @@ pub struct HttpConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_delay: Option<u32>,
 
+    /// 重试退避系数，每次重试后间隔乘以该值（默认 1.0，即固定间隔）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_backoff: Option<f32>,
+
+    // ========== 页面渲染 ==========
+    /// 由 WebView 加载并渲染页面，代替直接的 HTTP 请求
+    ///
+    /// 用于由 JavaScript 渲染内容、HTTP 请求只能拿到空壳的页面。
+    /// 需要运行时注入可用的 WebView 提供者，否则流程返回错误
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub render: Option<RenderConfig>,
+
     // ========== 请求配置 ==========
     /// 默认请求配置
     #[serde(skip_serializing_if = "Option::is_none")]
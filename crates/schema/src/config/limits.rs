@@ -0,0 +1,34 @@
+//! 流程资源限制配置
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 流程资源限制配置
+///
+/// 限制单次流程调用（一次搜索、一次详情等）可消耗的资源，防止失控的规则
+/// 发起大量请求或耗尽内存。未填写的字段使用运行时的默认值；
+/// 通过运行时构建器显式设置的限制优先于规则中的配置。
+///
+/// # 示例
+///
+/// ```toml
+/// [limits]
+/// max_http_requests = 100
+/// timeout = 60
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// 单次流程最多发起的 HTTP 请求数（默认 500）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_http_requests: Option<u32>,
+    /// 单次流程的最长执行时间（秒，默认不限制）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// 最大作用域嵌套深度，`map`、组件等每层计一次（默认 32）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+    /// 单个步骤结果数组的最大长度（默认 100000）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_array_len: Option<u64>,
+}
@@ -36,6 +36,34 @@ impl MediaType {
     }
 }
 
+// ============================================================================
+// 内容分级
+// ============================================================================
+
+/// 内容分级，供客户端进行年龄限制等展示控制。
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentRating {
+    /// 全年龄。
+    #[default]
+    General,
+    /// 含成熟内容，建议提示。
+    Mature,
+    /// 成人内容。
+    Adult,
+}
+
+impl ContentRating {
+    /// 获取显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::General => "全年龄",
+            Self::Mature => "成熟",
+            Self::Adult => "成人",
+        }
+    }
+}
+
 // ============================================================================
 // 元数据
 // ============================================================================
@@ -60,7 +88,8 @@ pub struct Meta {
     /// 规则的详细描述。
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// 目标网站的编码，默认为 "UTF-8"。
+    /// 目标网站的编码（如 "gbk"），默认为 "UTF-8"。
+    /// 用于解码响应体，流程 `response.encoding` 优先于此项。
     #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<String>,
     /// 数据源的图标URL，用于UI展示。
@@ -69,4 +98,23 @@ pub struct Meta {
     /// 默认脚本引擎（可选，默认 JavaScript）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub script_engine: Option<ScriptEngine>,
+    /// 内容分级（general / mature / adult），默认为 general。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_rating: Option<ContentRating>,
+    /// 展示前是否需要用户确认年龄，未设置时成人内容默认需要确认。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires_age_confirmation: Option<bool>,
+}
+
+impl Meta {
+    /// 获取内容分级，未设置时为 [`ContentRating::General`]
+    pub fn content_rating(&self) -> ContentRating {
+        self.content_rating.unwrap_or_default()
+    }
+
+    /// 是否需要年龄确认
+    pub fn requires_age_confirmation(&self) -> bool {
+        self.requires_age_confirmation
+            .unwrap_or(self.content_rating() == ContentRating::Adult)
+    }
 }
@@ -4,8 +4,16 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    config::{ChallengeConfig, HttpConfig, Meta, ScriptSecurityConfig},
-    flow::{Components, ContentFlow, DetailFlow, DiscoveryFlow, LoginFlow, SearchFlow},
+    config::{ChallengeConfig, HttpConfig, LimitsConfig, Meta, ScriptSecurityConfig},
+    flow::{
+        Components,
+        ContentFlow,
+        DetailFlow,
+        DiscoveryFlow,
+        LoginFlow,
+        SearchFlow,
+        SuggestFlow,
+    },
 };
 
 /// 影视软件爬虫规则 (CrawlerRule)
@@ -26,6 +34,9 @@ pub struct CrawlerRule {
     /// 可被 Script 中的局部 `security` 配置覆盖。
     #[serde(skip_serializing_if = "Option::is_none")]
     pub script_security: Option<ScriptSecurityConfig>,
+    /// 单次流程调用的资源限制（可选，未填写的字段使用默认值）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limits: Option<LimitsConfig>,
     /// 可重用组件定义
     ///
     /// 以名称为键定义可复用的提取逻辑，可在各流程中通过 `use_component` 步骤引用
@@ -42,6 +53,10 @@ pub struct CrawlerRule {
     pub detail: DetailFlow,
     /// 搜索流程（必需）
     pub search: SearchFlow,
+    /// 搜索建议流程（可选）
+    /// 用于输入过程中的实时搜索建议
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggest: Option<SuggestFlow>,
     /// 内容页流程（可选）
     /// 用于播放页、阅读页等需要进一步解析内容的场景
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -39,7 +39,8 @@
 //! |------|------|------|
 //! | `css` | HTML | CSS 选择器 |
 //! | `json` | JSON | JSONPath 表达式 |
-//! | `xpath` | XML/HTML | XPath 表达式（需外部实现） |
+//! | `json_pointer` | JSON | JSON Pointer 路径 |
+//! | `xpath` | HTML | XPath 表达式（常用子集） |
 //! | `regex` | 文本 | 正则表达式匹配 |
 //!
 //! ## 过滤步骤（转换数据）
@@ -57,6 +58,7 @@
 //! | `set_var` | 保存当前值到指定上下文 |
 //! | `script` | 自定义脚本 |
 //! | `use_component` | 引用预定义组件 |
+//! | `coalesce` | 依次渲染模板，取第一个非空结果 |
 //!
 //! ## 流程控制步骤
 //!
@@ -64,10 +66,12 @@
 //! |------|------|
 //! | `map` | 对数组每个元素应用步骤 |
 //! | `condition` | 条件分支执行 |
+//! | `merge` | 对同一输入执行多组步骤，合并为对象 |
 
-use crate::{flow::ComponentRef, script::Script};
+use crate::{flow::ComponentRef, script::Script, template::Template};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // ============================================================================
 // 核心提取器
@@ -98,6 +102,10 @@ use serde::{Deserialize, Serialize};
 ///     [{ css = ".creator" }]
 /// ]
 /// author.default = "佚名"
+///
+/// # 结果校验（不匹配时视为提取失败，转入回退/默认值）
+/// play_url.steps = [{ css = "video" }, { attr = "src" }]
+/// play_url.validate = "^https?://"
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -122,6 +130,13 @@ pub struct FieldExtractor {
     /// 是否允许空值
     #[serde(default)]
     pub nullable: bool,
+
+    /// 结果校验正则
+    ///
+    /// 提取结果（数组则为每个元素）必须匹配该正则，否则视为提取失败，
+    /// 依次尝试回退步骤和默认值。用于发现选择器漂移到错误元素的问题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate: Option<String>,
 }
 
 // ============================================================================
@@ -133,23 +148,58 @@ pub struct FieldExtractor {
 /// 单个原子化操作。步骤类型：
 /// - **选择步骤**：css, json, xpath, regex
 /// - **过滤步骤**：filter, attr, index
-/// - **特殊步骤**：const, var, script, use_component
+/// - **特殊步骤**：const, var, script, use_component, coalesce
 /// - **流程控制**：map, condition
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExtractStep {
     // ========== 选择步骤 ==========
     /// CSS 选择器（HTML）
+    ///
+    /// 选择器末尾可使用伪元素直接提取内容，等价于追加一个 `attr` 步骤：
+    /// `::text`、`::html`、`::attr(name)`
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// title.steps = [{ css = "h1::text" }]
+    /// url.steps = [{ css = "a.detail::attr(href)" }]
+    /// ```
     Css(SelectorStep),
 
     /// JSONPath 表达式（JSON）
+    ///
+    /// 以 `/` 开头的表达式视为 JSON Pointer，等同于 `json_pointer` 步骤。
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// title.steps = [{ json = "$.data.items[*].title" }]
+    /// cover.steps = [{ json = "/data/cover/url" }]
+    /// ```
     Json(SelectorStep),
 
+    /// JSON Pointer（RFC 6901，JSON）
+    ///
+    /// 适用于固定路径的深层嵌套数据，必须以 `/` 开头（空字符串表示整个文档）。
+    /// 路径不存在时返回空值。
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// title.steps = [{ json_pointer = "/data/items/0/title" }]
+    /// ```
+    JsonPointer(String),
+
     /// XPath 表达式（XML/HTML）
     ///
-    /// **注意**：Rust 原生不支持完整 XPath，Runtime 通过 trait 抽象实现：
-    /// - 在 Tauri 环境下通过调用 JS 引擎执行
-    /// - 可注入其他 XPath 实现（如 libxml2 绑定）
+    /// **注意**：Runtime 将常用 XPath 子集转换为 CSS 选择器执行，支持：
+    /// - `/`、`//` 路径与 `*` 通配
+    /// - `[@attr]`、`[@attr='v']`、`[contains(@attr,'v')]`、`[starts-with(@attr,'v')]`
+    /// - 位置谓词 `[n]`、`[last()]`（须写在其他谓词之前）
+    /// - 末尾的 `/text()`（直接子文本）、`//text()`（全部后代文本）与 `/@attr` 提取
+    ///
+    /// 轴、联合、布尔运算与其他函数等无法等价转换的表达式不受支持，会在规则加载时报错。
     ///
     /// # 示例
     ///
@@ -160,6 +210,18 @@ pub enum ExtractStep {
     Xpath(SelectorStep),
 
     /// 正则表达式（文本）
+    ///
+    /// 输入为字符串数组时逐元素匹配，返回结果数组，未匹配的元素被丢弃，
+    /// 相当于 `map = [{ regex = ... }]` 再去除空值
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// ids.steps = [{ css = { expr = "a", all = true } }, { attr = "href" }, { regex = "/book/(\\d+)" }]
+    ///
+    /// # 命名捕获组解构为对象：{ "season": "01", "episode": "05" }
+    /// episode.steps = [{ regex = { pattern = "S(?<season>\\d+)E(?<episode>\\d+)", named = true } }]
+    /// ```
     Regex(RegexStep),
 
     // ========== 过滤步骤 ==========
@@ -167,9 +229,32 @@ pub enum ExtractStep {
     Filter(FilterStep),
 
     /// 属性提取
-    Attr(String),
+    ///
+    /// 可指定单个属性名，或属性名列表（返回属性名到值的对象，缺失的属性为 null）
+    ///
+    /// 单个属性名支持回退链 `first(a,b,...)`，返回第一个非空的属性值
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// url.steps = [{ css = "a" }, { attr = "href" }]
+    /// cover.steps = [{ css = "img" }, { attr = "first(data-src,data-original,src)" }]
+    /// images.steps = [{ css = "img" }, { attr = ["data-src", "data-original", "src"] }]
+    /// ```
+    Attr(AttrStep),
 
     /// 索引/切片
+    ///
+    /// 负数索引从末尾计数，`first` / `last` 分别取第一个和最后一个元素；
+    /// 索引越界时返回空值（可使用字段的 `default`）
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// latest.steps = [{ css = { expr = ".chapter a", all = true } }, { index = "last" }]
+    /// second_last.steps = [{ css = { expr = ".chapter a", all = true } }, { index = -2 }]
+    /// top3.steps = [{ css = { expr = ".rank li", all = true } }, { index = "0:3" }]
+    /// ```
     Index(IndexStep),
 
     // ========== 特殊步骤 ==========
@@ -194,11 +279,36 @@ pub enum ExtractStep {
     /// ```
     UseComponent(ComponentRef),
 
+    /// 多变量回退
+    ///
+    /// 依次渲染模板，返回第一个非空（去除首尾空白后）的结果；
+    /// 引用了不存在变量的模板视为空。全部为空时返回空值，
+    /// 可继续使用字段的 `fallback` / `default`
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// # 不同站点版本使用不同的变量名
+    /// book_id.steps = [{ coalesce = ["{{ book_id }}", "{{ bid }}", "{{ $.default_id }}"] }]
+    /// ```
+    Coalesce(Vec<Template>),
+
     // ========== 流程控制步骤 ==========
     /// 映射处理（对数组每个元素应用步骤）
     ///
     /// 输入必须是数组，对每个元素执行内部步骤，返回处理后的数组
     ///
+    /// 每个元素执行时，流程上下文中可用 `loop` 变量：`loop.index`（从 1 开始）、
+    /// `loop.index0`（从 0 开始）、`loop.first`、`loop.last`、`loop.length`；
+    /// 嵌套映射中 `loop` 指向最近一层。
+    ///
+    /// 输入为 JSON 对象时遍历其条目（按键排序）：每个条目的值作为步骤输入，
+    /// 键写入 `loop.key`（配置 `key_as` 时同时写入该变量）。默认结果为各条目结果组成的数组，
+    /// `collect = "object"` 时按原键重建对象。数组输入始终得到数组。
+    ///
+    /// 元素的步骤执行失败时默认丢弃该元素；`on_error = "fail"` 时中止并返回错误，
+    /// `on_error = "null"` 时以 null 占位，保持与输入（或 `zip` 的另一数组）位置对应。
+    ///
     /// # 示例
     ///
     /// ```toml
@@ -213,8 +323,20 @@ pub enum ExtractStep {
     ///     { json = "$.items[*]" },
     ///     { map = [{ json = "$.title" }, { filter = "trim" }] }
     /// ]
+    ///
+    /// # 将序号绑定到变量 `no`，供脚本使用
+    /// chapters.steps = [
+    ///     { css = { expr = "li a", all = true } },
+    ///     { map = { steps = [{ attr = "text" }, { script = { code = "`${no}. ${input}`" } }], index_as = "no" } }
+    /// ]
+    ///
+    /// # 遍历 `{"id1": {...}, "id2": {...}}` 形式的对象
+    /// books.steps = [
+    ///     { json = "$.data" },
+    ///     { map = { steps = [{ json = "$.name" }], key_as = "id", collect = "object" } }
+    /// ]
     /// ```
-    Map(Vec<ExtractStep>),
+    Map(MapStep),
 
     /// 条件分支
     ///
@@ -233,6 +355,26 @@ pub enum ExtractStep {
     /// }]
     /// ```
     Condition(Box<ConditionStep>),
+
+    /// 合并提取结果
+    ///
+    /// 对同一输入分别执行每个键对应的步骤，将结果组合为一个对象；
+    /// 结果为空或执行失败的键值为 `null`。常用于在 `map` 中为每个元素构造记录
+    ///
+    /// # 示例
+    ///
+    /// ```toml
+    /// # 将每个列表项转为 { title, url, cover } 对象
+    /// items.steps = [
+    ///     { css = { expr = ".item", all = true } },
+    ///     { map = [{ merge = {
+    ///         title = [{ css = ".title" }, { attr = "text" }],
+    ///         url = [{ css = "a" }, { attr = "href" }, { filter = "absolute_url" }],
+    ///         cover = [{ css = "img" }, { attr = "src" }]
+    ///     } }] }
+    /// ]
+    /// ```
+    Merge(BTreeMap<String, Vec<ExtractStep>>),
 }
 
 /// 变量上下文类型
@@ -295,6 +437,9 @@ pub enum RegexStep {
         /// 是否全局匹配
         #[serde(default)]
         global: bool,
+        /// 返回所有命名捕获组组成的对象（忽略 `group`），未参与匹配的组为 null
+        #[serde(default)]
+        named: bool,
     },
 }
 
@@ -312,16 +457,124 @@ pub enum FilterStep {
     List(Vec<FilterConfig>),
 }
 
+/// 属性提取步骤
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum AttrStep {
+    /// 单个属性名（或 `text`/`own_text`/`html`/`outer_html`/`first(a,b,...)`）
+    Single(String),
+    /// 多个属性名，返回 `{ 属性名: 值 }` 对象
+    Multiple(Vec<String>),
+}
+
 /// 索引/切片步骤
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum IndexStep {
-    /// 单个索引
+    /// 单个索引，负数从末尾计数（`-1` 为最后一个）
     Single(i32),
-    /// 切片表达式 "start:end" 或 "start:end:step"
+    /// 关键字 `first` / `last`，或切片表达式 "start:end" 或 "start:end:step"
     Slice(String),
 }
 
+/// 映射步骤配置
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum MapStep {
+    /// 仅步骤列表
+    Steps(Vec<ExtractStep>),
+    /// 带配置的映射
+    WithOptions(MapOptions),
+}
+
+/// 带配置的映射选项
+///
+/// 拒绝未知字段，拼错的选项名直接报错而不是被忽略
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MapOptions {
+    /// 对每个元素执行的步骤
+    pub steps: Vec<ExtractStep>,
+    /// 将当前元素的序号（从 1 开始）绑定到该流程变量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_as: Option<String>,
+    /// 遍历对象时，将当前条目的键绑定到该流程变量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_as: Option<String>,
+    /// 遍历对象时的结果形式（默认数组）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collect: Option<MapCollect>,
+    /// 元素的步骤执行失败时的处理方式（默认跳过）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_error: Option<MapErrorMode>,
+}
+
+/// 映射元素失败时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MapErrorMode {
+    /// 丢弃失败的元素
+    #[default]
+    Skip,
+    /// 遇到第一个失败的元素时中止，返回其错误
+    Fail,
+    /// 以 null 占位，保持与输入的位置对应
+    Null,
+}
+
+/// 映射对象时的结果形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MapCollect {
+    /// 各条目结果组成的数组
+    #[default]
+    Array,
+    /// 以原键重建对象
+    Object,
+}
+
+impl MapStep {
+    /// 对每个元素执行的步骤
+    pub fn steps(&self) -> &[ExtractStep] {
+        match self {
+            Self::Steps(steps) => steps,
+            Self::WithOptions(options) => &options.steps,
+        }
+    }
+
+    /// 序号绑定的变量名
+    pub fn index_as(&self) -> Option<&str> {
+        match self {
+            Self::Steps(_) => None,
+            Self::WithOptions(options) => options.index_as.as_deref(),
+        }
+    }
+
+    /// 键绑定的变量名
+    pub fn key_as(&self) -> Option<&str> {
+        match self {
+            Self::Steps(_) => None,
+            Self::WithOptions(options) => options.key_as.as_deref(),
+        }
+    }
+
+    /// 遍历对象时的结果形式
+    pub fn collect(&self) -> MapCollect {
+        match self {
+            Self::Steps(_) => MapCollect::default(),
+            Self::WithOptions(options) => options.collect.unwrap_or_default(),
+        }
+    }
+
+    /// 元素失败时的处理方式
+    pub fn on_error(&self) -> MapErrorMode {
+        match self {
+            Self::Steps(_) => MapErrorMode::default(),
+            Self::WithOptions(options) => options.on_error.unwrap_or_default(),
+        }
+    }
+}
+
 /// 条件步骤配置
 ///
 /// 根据条件选择执行不同的提取逻辑
@@ -330,9 +583,23 @@ pub enum IndexStep {
 pub struct ConditionStep {
     /// 条件检测步骤
     ///
-    /// 执行这些步骤，如果结果非空/非 null/非 false，则条件为真
+    /// 未设置 `op` 时，执行这些步骤，如果结果非空/非 null/非 false，则条件为真
     pub when: Vec<ExtractStep>,
 
+    /// 比较运算符（可选）
+    ///
+    /// 设置后，将 `when` 的结果与 `value` 比较，比较结果即为条件
+    ///
+    /// ```toml
+    /// condition = { when = [{ css = ".status" }, { attr = "text" }], op = "eq", value = "完结", then = [...] }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub op: Option<CompareOp>,
+
+    /// 比较值（与 `op` 配合使用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+
     /// 条件为真时执行的步骤
     pub then: Vec<ExtractStep>,
 
@@ -341,6 +608,28 @@ pub struct ConditionStep {
     pub otherwise: Option<Vec<ExtractStep>>,
 }
 
+/// 条件比较运算符
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareOp {
+    /// 等于
+    Eq,
+    /// 不等于
+    Ne,
+    /// 大于（数值比较）
+    Gt,
+    /// 小于（数值比较）
+    Lt,
+    /// 大于等于（数值比较）
+    Gte,
+    /// 小于等于（数值比较）
+    Lte,
+    /// 包含（字符串子串或数组元素）
+    Contains,
+    /// 匹配正则表达式
+    Matches,
+}
+
 /// 过滤器配置（结构化形式）
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -363,22 +652,53 @@ pub struct FilterConfig {
 /// # 字符串处理
 /// - `trim` - 去首尾空白
 /// - `lower` / `upper` - 大小写转换
+/// - `capitalize` - 首字母大写，其余不变
+/// - `title_case` - 每个单词首字母大写、其余小写
 /// - `replace(from, to)` - 文本替换
 /// - `strip_html` - 移除 HTML 标签
+/// - `normalize_whitespace(preserve_paragraphs?, convert_nbsp?)` - 折叠连续空白并去除首尾空白，
+///   `preserve_paragraphs` 时保留段落分隔 `\n\n`，`convert_nbsp` 为 `false` 时保留不换行空格
+/// - `paragraphs(min_chars?)` - 按换行与 `<br>` / `<p>` / `<div>` 拆分为段落数组，去除标签与空段，
+///   丢弃字符数少于 `min_chars` 的段落
+/// - `html_decode` - 解码 HTML 实体（`&amp;`、`&nbsp;`、`&#x27;` 等）
 /// - `split(sep)` / `join(sep)` - 分割/连接
+/// - `trim_prefix(s)` / `trim_suffix(s)` - 移除固定前缀/后缀（不存在时原样返回）
+/// - `prepend(s)` / `append(s)` - 添加前缀/后缀
+/// - `truncate(max_chars, suffix?, word_boundary?)` - 按字符数截断，截断时追加 `suffix`（默认 `…`）
+/// - `cn_num(only_first)` - 中文数字转阿拉伯数字（"第三十二章" → "第32章"）
 ///
 /// # 类型转换
 /// - `to_int` / `to_float` / `to_string` / `to_bool`
 /// - `from_json` / `to_json`
 ///
+/// # 数值处理
+/// - `add(n)` / `sub(n)` / `mul(n)` / `div(n)` - 四则运算（字符串输入按数字解析，除数为 0 时报错）
+/// - `round(precision?)` / `abs` - 四舍五入到指定小数位、取绝对值
+///
 /// # URL 处理
-/// - `absolute_url` - 转绝对 URL
+/// - `absolute_url` - 转绝对 URL（未指定基准时使用重定向后的页面 URL）
 /// - `url_encode` / `url_decode`
 /// - `extract_domain` / `query_param(name)`
 ///
+/// # 编码处理
+/// - `hash(algorithm, encoding)` - 计算 `md5` / `sha1` / `sha256` 摘要，输出 `hex`（默认）或
+///   `base64`
+///
+/// # 正则处理
+/// - `regex_replace(pattern, replacement)` - 正则替换
+/// - `match_all(pattern, group?)` - 返回所有匹配组成的数组（指定 `group`
+///   时取该捕获组，可为序号或名称），无匹配时为空数组
+///
 /// # 数组处理
 /// - `first` / `last` / `nth(n)`
 /// - `slice(start, end)` / `reverse` / `unique`
+/// - `length` - 长度（字符串字符数、数组元素数、对象键数，空值为 0）
+/// - `zip(var, key_a?, key_b?, strict?)` - 与变量中的数组按位置配对，长度不一致时截断（`strict`
+///   时报错）
+///
+/// # 判断
+/// - `contains(s)` - 字符串是否包含子串，或数组是否包含与参数相等的元素
+/// - `starts_with(s)` / `ends_with(s)` - 是否以指定字符串开头/结尾
 ///
 /// # 条件处理
 /// - `default(value)` - 默认值
@@ -390,17 +710,26 @@ pub enum Filter {
     Trim,
     TrimStart,
     TrimEnd,
+    TrimPrefix,
+    TrimSuffix,
+    Prepend,
+    Append,
     Lower,
     Upper,
     Capitalize,
+    TitleCase,
     StripHtml,
     CollapseWhitespace,
+    NormalizeWhitespace,
+    Paragraphs,
     Replace,
     RegexReplace,
     Split,
     Join,
     Substring,
+    Truncate,
     Reverse,
+    CnNum,
 
     // === 类型转换 ===
     ToInt,
@@ -435,11 +764,18 @@ pub enum Filter {
     HtmlEncode,
     HtmlDecode,
     Md5,
+    Hash,
 
     // === 正则处理 ===
     RegexExtract,
     RegexMatch,
     RegexFindAll,
+    MatchAll,
+
+    // === 判断 ===
+    Contains,
+    StartsWith,
+    EndsWith,
 
     // === 条件处理 ===
     Default,
@@ -456,4 +792,5 @@ pub enum Filter {
     Sort,
     Flatten,
     Length,
+    Zip,
 }
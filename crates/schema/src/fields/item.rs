@@ -51,3 +51,15 @@ pub struct ItemFields {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra: OptionalFieldRule,
 }
+
+/// 搜索建议字段规则 (SuggestFields)
+/// 定义搜索建议列表中每个项目需要提取的字段
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SuggestFields {
+    /// 建议标题（必需）
+    pub title: FieldRule,
+
+    /// 详情页 URL（必需）
+    pub url: FieldRule,
+}
@@ -45,6 +45,8 @@ pub use video::*;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::config::MediaType;
+
 // ============================================================================
 // 统一的详情页字段规则（根据媒体类型选择）
 // ============================================================================
@@ -66,6 +68,28 @@ pub enum DetailFields {
     Manga(Box<MangaDetailFields>),
 }
 
+impl DetailFields {
+    /// 字段定义对应的媒体类型
+    pub fn media_type(&self) -> MediaType {
+        match self {
+            Self::Video(_) => MediaType::Video,
+            Self::Audio(_) => MediaType::Audio,
+            Self::Book(_) => MediaType::Book,
+            Self::Manga(_) => MediaType::Manga,
+        }
+    }
+
+    /// 指定媒体类型的详情页字段形状说明（用于错误提示）
+    pub fn expected_shape(media_type: MediaType) -> &'static str {
+        match media_type {
+            MediaType::Video => "media_type = \"video\"，必需 title，可选 play_lines 等",
+            MediaType::Audio => "media_type = \"audio\"，必需 title，可选 tracks 等",
+            MediaType::Book => "media_type = \"book\"，必需 title、author，可选 chapters 等",
+            MediaType::Manga => "media_type = \"manga\"，必需 title，可选 chapters 等",
+        }
+    }
+}
+
 /// 内容页字段规则 (ContentFields)
 /// 用于播放页、阅读页等内容消费页面
 ///
@@ -82,3 +106,25 @@ pub enum ContentFields {
     /// 漫画阅读字段
     Manga(Box<MangaReadFields>),
 }
+
+impl ContentFields {
+    /// 字段定义对应的媒体类型
+    pub fn media_type(&self) -> MediaType {
+        match self {
+            Self::Video(_) => MediaType::Video,
+            Self::Audio(_) => MediaType::Audio,
+            Self::Book(_) => MediaType::Book,
+            Self::Manga(_) => MediaType::Manga,
+        }
+    }
+
+    /// 指定媒体类型的内容页字段形状说明（用于错误提示）
+    pub fn expected_shape(media_type: MediaType) -> &'static str {
+        match media_type {
+            MediaType::Video => "media_type = \"video\"，必需 play_url",
+            MediaType::Audio => "media_type = \"audio\"，必需 play_url",
+            MediaType::Book => "media_type = \"book\"，必需 content",
+            MediaType::Manga => "media_type = \"manga\"，必需 images",
+        }
+    }
+}
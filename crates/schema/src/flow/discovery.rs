@@ -94,6 +94,13 @@ pub struct DiscoveryFlow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filters: Option<FilterList>,
 
+    /// 排序选项组（可选）
+    ///
+    /// 复用筛选器组结构，选中的 `value` 以组的 `key` 为变量名注入 URL 模板，
+    /// 如 `url = "...?order={{ order }}"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorts: Option<Vec<FilterGroup>>,
+
     /// list 列表提取规则
     pub list: FieldExtractor,
 
@@ -52,6 +52,8 @@ use serde::{Deserialize, Serialize};
 /// tip = "请从浏览器开发者工具中复制 Cookie"
 /// docs_url = "https://example.com/help/cookie"
 /// storage = [{ type = "cookie" }]
+/// # 已有 session Cookie 时跳过登录
+/// check_login = { code = "input.includes('session=')" }
 /// ```
 ///
 /// ## Credential 模式（Header Token）
@@ -78,6 +80,17 @@ pub enum LoginFlow {
     Credential(CredentialLoginFlow),
 }
 
+impl LoginFlow {
+    /// 登录状态检查脚本
+    pub fn check_login(&self) -> Option<&Script> {
+        match self {
+            Self::Script(flow) => flow.check_login.as_ref(),
+            Self::Webview(flow) => flow.check_login.as_ref(),
+            Self::Credential(flow) => flow.check_login.as_ref(),
+        }
+    }
+}
+
 // ============================================================================
 // 脚本交互模式 (Script)
 // ============================================================================
@@ -99,6 +112,12 @@ pub struct ScriptLoginFlow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// 登录状态检查脚本（可选）
+    ///
+    /// 在登录前执行，输入为当前站点的 Cookie 字符串；返回真值表示已登录，跳过登录
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_login: Option<Script>,
+
     /// 定义原生界面元素（输入框、按钮、验证码图片）
     pub ui: Vec<LoginUIElement>,
 
@@ -225,6 +244,12 @@ pub struct WebViewLoginFlow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// 登录状态检查脚本（可选）
+    ///
+    /// 在登录前执行，输入为当前站点的 Cookie 字符串；返回真值表示已登录，跳过登录
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_login: Option<Script>,
+
     /// 登录起始页 URL
     pub start_url: Template,
 
@@ -281,6 +306,12 @@ pub struct CredentialLoginFlow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// 登录状态检查脚本（可选）
+    ///
+    /// 在登录前执行，输入为当前站点的 Cookie 字符串；返回真值表示已登录，跳过登录
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_login: Option<Script>,
+
     /// 提示用户如何获取凭证的说明文案
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tip: Option<String>,
@@ -321,7 +352,7 @@ pub enum CredentialStorage {
 }
 
 /// Cookie 存储配置
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct CookieStorage {
     /// 要存储的字段 key（对应 fields 中的 key）
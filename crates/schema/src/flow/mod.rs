@@ -5,6 +5,7 @@
 //! - DiscoveryFlow: 发现页流程（筛选和分页）
 //! - DetailFlow: 详情页流程（字段驱动）
 //! - SearchFlow: 搜索流程（字段驱动）
+//! - SuggestFlow: 搜索建议流程（字段驱动）
 //! - ContentFlow: 内容页流程（播放页、阅读页）
 //! - Component: 可重用组件
 
@@ -15,6 +16,7 @@ pub mod detail;
 pub mod discovery;
 pub mod login;
 pub mod search;
+pub mod suggest;
 
 // 重新导出所有公开类型
 pub use common::*;
@@ -24,3 +26,4 @@ pub use detail::*;
 pub use discovery::*;
 pub use login::*;
 pub use search::*;
+pub use suggest::*;
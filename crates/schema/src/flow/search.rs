@@ -4,7 +4,7 @@ use crate::{config::HttpConfig, extract::FieldExtractor, fields::ItemFields, tem
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use super::common::Pagination;
+use super::common::{FilterGroup, Pagination};
 
 /// 搜索流程 (SearchFlow)
 ///
@@ -18,6 +18,9 @@ use super::common::Pagination;
 /// |------|------|------|
 /// | `keyword` | String | 搜索关键词 |
 /// | `page` | u32 | 当前页码 |
+/// | 分页 `param` | u32 / String | 按 `pagination` 换算的页码、偏移量或游标 |
+/// | 排序组 `key` | String | 选中的排序值 |
+/// | 筛选组 `key` | String | 选中的筛选值（多选组以 `,` 连接） |
 ///
 /// ## Runtime 全局变量（通过 `$` 前缀访问）
 ///
@@ -78,6 +81,21 @@ pub struct SearchFlow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pagination: Option<Pagination>,
 
+    /// 排序选项组（可选）
+    ///
+    /// 复用筛选器组结构，选中的 `value` 以组的 `key` 为变量名注入 URL 模板，
+    /// 如 `url = "...?order={{ order }}"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sorts: Option<Vec<FilterGroup>>,
+
+    /// 筛选器组（可选）
+    ///
+    /// 选中的 `value` 以组的 `key` 为变量名注入 URL 模板，
+    /// 如 `url = "...?q={{ keyword }}&type={{ type }}"`；
+    /// `multiselect = true` 的组可选中多个值，以 `,` 连接
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Vec<FilterGroup>>,
+
     /// list 列表提取规则
     pub list: FieldExtractor,
 
@@ -0,0 +1,59 @@
+//! 搜索建议流程 (SuggestFlow)
+
+use crate::{
+    config::HttpConfig,
+    extract::FieldExtractor,
+    fields::SuggestFields,
+    template::Template,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 搜索建议流程 (SuggestFlow)
+///
+/// 用于"边输边搜"的轻量级建议接口，仅提取标题与 URL，不支持分页。
+///
+/// # 可用变量
+///
+/// ## Flow 变量（自动注入）
+///
+/// | 变量 | 类型 | 说明 |
+/// |------|------|------|
+/// | `keyword` | String | 用户已输入的部分关键词 |
+///
+/// # 示例
+///
+/// ```toml
+/// [suggest]
+/// url = "{{ $.base_url }}/api/suggest?q={{ keyword }}"
+/// list.steps = [{ json = "$.data[*]" }]
+///
+/// [suggest.http.response]
+/// content_type = "json"
+///
+/// [suggest.fields]
+/// title.steps = [{ json = "$.name" }]
+/// url.steps = [{ json = "$.url" }]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SuggestFlow {
+    /// 流程的功能描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// 建议接口 URL 模板
+    ///
+    /// 必须引用 `keyword` 变量
+    pub url: Template,
+
+    /// 流程级 HTTP 配置（可选）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http: Option<HttpConfig>,
+
+    /// list 列表提取规则
+    pub list: FieldExtractor,
+
+    /// 建议项字段提取规则
+    pub fields: SuggestFields,
+}
@@ -48,10 +48,23 @@ use serde::{Deserialize, Serialize};
 /// - 变量插值: `{{ variable }}`
 /// - 嵌套访问: `{{ user.name }}`、`{{ items[0] }}`
 /// - 全局访问: `{{ $.base_url }}`
-/// - 过滤器: `{{ name | upper }}`
-/// - 条件: `{% if condition %}...{% endif %}`
+/// - 过滤器: `{{ name | upper }}`，可使用提取步骤中的过滤器，如 `{{ title | cn_num }}`； 与 Tera
+///   内置过滤器同名时使用 Tera 的实现，参数按名称传入，如 `{{ s | hash(algorithm="md5") }}`
+/// - 默认值: `{{ page | default: 1 }}`，变量未定义或为空时使用默认值
+/// - 条件: `{% if condition %}...{% endif %}`，未定义的变量视为假
+/// - 三元表达式: `{{ sort ? sort : "new" }}`，须占据整个标签，不支持嵌套
 /// - 循环: `{% for item in items %}...{% endfor %}`
 ///
+/// # 转义与空白控制
+///
+/// - 字面量花括号：包裹在 `{% raw %}...{% endraw %}` 中的内容原样输出，不做变量解析， 适用于 URL
+///   中携带 JSON 等场景，例如 `url = "{{ $.base_url }}/api?q={% raw %}{"id":1}{% endraw %}"`；
+///   单个字面量也可写为 `{{ "{{" }}`
+/// - 空白控制：在定界符内侧加 `-` 可去除该侧相邻空白， 如 `{{- var -}}`、`{%- if cond
+///   -%}`，用于多行 TOML 模板
+///
+/// 使用 [`Template::literal`] 可从任意字符串构造不含变量的模板，渲染时原样输出、不经过模板引擎。
+///
 /// # 示例
 ///
 /// ```toml
@@ -66,59 +79,81 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash, Default)]
 #[serde(transparent)]
-pub struct Template(
+pub struct Template {
     #[schemars(pattern(
         "{{\\s*([a-zA-Z_][a-zA-Z0-9_]*(?:\\.[a-zA-Z_][a-zA-Z0-9_]*|\\[[0-9]+\\])*)\\s*}}"
     ))]
-    String,
-);
+    source: String,
+    /// 字面量模板：渲染时原样输出，不经过模板引擎
+    #[serde(skip)]
+    literal: bool,
+}
 
 impl Template {
     /// 创建新模板
     #[inline]
     pub fn new(template: impl Into<String>) -> Self {
-        Self(template.into())
+        Self {
+            source: template.into(),
+            literal: false,
+        }
     }
 
     /// 获取原始字符串
     #[inline]
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.source
     }
 
     /// 转换为内部字符串
     #[inline]
     pub fn into_string(self) -> String {
-        self.0
+        self.source
     }
 
     /// 检查是否为空
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.source.is_empty()
+    }
+
+    /// 创建字面量模板
+    ///
+    /// 渲染时原样输出文本，不经过模板引擎，其中的花括号不会被解析为变量
+    pub fn literal(text: &str) -> Self {
+        Self {
+            source: text.to_string(),
+            literal: true,
+        }
+    }
+
+    /// 是否为 [`Template::literal`] 创建的字面量模板
+    #[inline]
+    pub fn is_literal(&self) -> bool {
+        self.literal
     }
 }
 
 impl From<String> for Template {
     fn from(s: String) -> Self {
-        Self(s)
+        Self::new(s)
     }
 }
 
 impl From<&str> for Template {
     fn from(s: &str) -> Self {
-        Self(s.to_string())
+        Self::new(s)
     }
 }
 
 impl AsRef<str> for Template {
     fn as_ref(&self) -> &str {
-        &self.0
+        &self.source
     }
 }
 
 impl std::fmt::Display for Template {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.source)
     }
 }
@@ -0,0 +1,26 @@
+//! HTTP 方法名称解析
+
+use crawler_schema::config::{HttpMethod, ParseHttpMethodError};
+
+#[test]
+fn method_names_parse_case_insensitively() {
+    assert_eq!("get".parse(), Ok(HttpMethod::Get));
+    assert_eq!("GET".parse(), Ok(HttpMethod::Get));
+    assert_eq!("Post".parse(), Ok(HttpMethod::Post));
+    assert_eq!(HttpMethod::try_from(" patch "), Ok(HttpMethod::Patch));
+}
+
+#[test]
+fn parsed_method_round_trips_through_as_str() {
+    for method in ["GET", "POST", "PUT", "DELETE", "HEAD", "OPTIONS", "PATCH"] {
+        assert_eq!(method.parse::<HttpMethod>().unwrap().as_str(), method);
+    }
+}
+
+#[test]
+fn unknown_method_is_an_error() {
+    assert_eq!(
+        "FETCH".parse::<HttpMethod>(),
+        Err(ParseHttpMethodError("FETCH".to_string()))
+    );
+}
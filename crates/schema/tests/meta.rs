@@ -0,0 +1,50 @@
+//! 规则元数据：内容分级
+
+use crawler_schema::config::{ContentRating, Meta};
+use serde_json::json;
+
+fn parse_meta(extra: serde_json::Value) -> Result<Meta, serde_json::Error> {
+    let mut value = json!({
+        "name": "测试规则",
+        "author": "test",
+        "version": "1.0.0",
+        "spec_version": "1.0.0",
+        "domain": "example.com",
+        "media_type": "book",
+    });
+    value
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    serde_json::from_value(value)
+}
+
+#[test]
+fn rating_defaults_to_general_without_age_gate() {
+    let meta = parse_meta(json!({})).unwrap();
+    assert_eq!(meta.content_rating(), ContentRating::General);
+    assert!(!meta.requires_age_confirmation());
+}
+
+#[test]
+fn adult_rating_requires_age_confirmation_by_default() {
+    let meta = parse_meta(json!({ "content_rating": "adult" })).unwrap();
+    assert_eq!(meta.content_rating(), ContentRating::Adult);
+    assert!(meta.requires_age_confirmation());
+
+    let opted_out =
+        parse_meta(json!({ "content_rating": "adult", "requires_age_confirmation": false }));
+    assert!(!opted_out.unwrap().requires_age_confirmation());
+}
+
+#[test]
+fn mature_rating_can_opt_into_age_confirmation() {
+    let meta = parse_meta(json!({ "content_rating": "mature", "requires_age_confirmation": true }));
+    assert!(meta.unwrap().requires_age_confirmation());
+}
+
+#[test]
+fn unknown_rating_is_rejected() {
+    let error = parse_meta(json!({ "content_rating": "r18" })).unwrap_err();
+    assert!(error.to_string().contains("r18"), "{error}");
+}